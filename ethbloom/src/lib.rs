@@ -50,7 +50,7 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use core::{mem, ops};
+use core::{convert::TryInto, mem, ops};
 
 use crunchy::unroll;
 use fixed_hash::*;
@@ -74,6 +74,8 @@ construct_fixed_hash! {
 impl_fixed_hash_rlp!(Bloom, BLOOM_SIZE);
 #[cfg(feature = "serialize")]
 impl_fixed_hash_serde!(Bloom, BLOOM_SIZE);
+#[cfg(feature = "serialize")]
+pub mod serde_compact;
 #[cfg(feature = "codec")]
 impl_fixed_hash_codec!(Bloom, BLOOM_SIZE);
 
@@ -153,11 +155,26 @@ impl Bloom {
 		self.0.iter().all(|x| *x == 0)
 	}
 
+	/// Returns the number of bits set, a measure of how full the bloom is.
+	pub fn bits_set(&self) -> u32 {
+		BloomRef::from(self).bits_set()
+	}
+
 	pub fn contains_input(&self, input: Input<'_>) -> bool {
 		let bloom: Bloom = input.into();
 		self.contains_bloom(&bloom)
 	}
 
+	/// Returns `true` if the bloom contains all of the inputs, stopping at the first one it does not contain.
+	pub fn contains_all<'a>(&self, inputs: impl IntoIterator<Item = Input<'a>>) -> bool {
+		inputs.into_iter().all(|input| self.contains_input(input))
+	}
+
+	/// Returns `true` if the bloom contains any of the inputs, stopping at the first one it contains.
+	pub fn contains_any<'a>(&self, inputs: impl IntoIterator<Item = Input<'a>>) -> bool {
+		inputs.into_iter().any(|input| self.contains_input(input))
+	}
+
 	pub fn contains_bloom<'a, B>(&self, bloom: B) -> bool
 	where
 		BloomRef<'a>: From<B>,
@@ -216,11 +233,74 @@ impl Bloom {
 		}
 	}
 
+	/// Accrues all of the given blooms.
+	pub fn accrue_bloom_iter<'a, I, B>(&mut self, blooms: I)
+	where
+		I: IntoIterator<Item = B>,
+		BloomRef<'a>: From<B>,
+	{
+		for bloom in blooms {
+			self.accrue_bloom(bloom);
+		}
+	}
+
+	/// Returns the ratio of bits set, in thousandths.
+	pub fn fullness_per_mille(&self) -> u32 {
+		self.bits_set() * 1000 / (BLOOM_SIZE as u32 * 8)
+	}
+
+	/// Returns `true` if the blooms have a bit set in common.
+	pub fn intersects(&self, other: &Self) -> bool {
+		words(&self.0).zip(words(&other.0)).any(|(a, b)| a & b != 0)
+	}
+
+	/// Returns `true` if all bits set in `self` are also set in `other`.
+	pub fn is_subset_of(&self, other: &Self) -> bool {
+		words(&self.0).zip(words(&other.0)).all(|(a, b)| a & !b == 0)
+	}
+
+	/// Keeps only the bits which are also set in `other`.
+	pub fn intersect_with(&mut self, other: &Self) {
+		zip_words_mut(&mut self.0, &other.0, |a, b| a & b);
+	}
+
+	/// Sets all bits which are set in `other`.
+	pub fn union_with(&mut self, other: &Self) {
+		zip_words_mut(&mut self.0, &other.0, |a, b| a | b);
+	}
+
 	pub fn data(&self) -> &[u8; BLOOM_SIZE] {
 		&self.0
 	}
 }
 
+fn words(bytes: &[u8; BLOOM_SIZE]) -> impl Iterator<Item = u64> + '_ {
+	bytes.chunks_exact(8).map(|word| u64::from_ne_bytes(word.try_into().expect("chunks have 8 bytes; qed")))
+}
+
+fn zip_words_mut(bytes: &mut [u8; BLOOM_SIZE], other: &[u8; BLOOM_SIZE], f: impl Fn(u64, u64) -> u64) {
+	for (word, other) in bytes.chunks_exact_mut(8).zip(words(other)) {
+		let a = u64::from_ne_bytes((&*word).try_into().expect("chunks have 8 bytes; qed"));
+		word.copy_from_slice(&f(a, other).to_ne_bytes());
+	}
+}
+
+impl<'a> Extend<Input<'a>> for Bloom {
+	fn extend<I: IntoIterator<Item = Input<'a>>>(&mut self, inputs: I) {
+		for input in inputs {
+			self.accrue(input);
+		}
+	}
+}
+
+impl<'a> core::iter::FromIterator<Input<'a>> for Bloom {
+	fn from_iter<I: IntoIterator<Item = Input<'a>>>(inputs: I) -> Self {
+		let mut bloom = Bloom::default();
+		bloom.extend(inputs);
+		bloom
+	}
+}
+
 #[derive(Clone, Copy)]
 pub struct BloomRef<'a>(&'a [u8; BLOOM_SIZE]);
 
@@ -230,6 +310,12 @@ impl<'a> BloomRef<'a> {
 		self.0.iter().all(|x| *x == 0)
 	}
 
+	/// Returns the number of bits set, a measure of how full the bloom is.
+	#[allow(clippy::trivially_copy_pass_by_ref)]
+	pub fn bits_set(&self) -> u32 {
+		self.0.iter().map(|x| x.count_ones()).sum()
+	}
+
 	#[allow(clippy::trivially_copy_pass_by_ref)]
 	pub fn contains_input(&self, input: Input<'_>) -> bool {
 		let bloom: Bloom = input.into();
@@ -274,7 +360,7 @@ impl<'a> From<&'a Bloom> for BloomRef<'a> {
 
 #[cfg(test)]
 mod tests {
-	use super::{Bloom, Input};
+	use super::{Bloom, BloomRef, Input};
 	use core::str::FromStr;
 	use hex_literal::hex;
 
@@ -315,4 +401,148 @@ mod tests {
 		assert!(my_bloom.contains_input(Input::Raw(&topic)));
 		assert_eq!(my_bloom, bloom);
 	}
+
+	#[test]
+	fn bits_set() {
+		let empty = Bloom::default();
+		assert!(empty.is_empty());
+		assert_eq!(empty.bits_set(), 0);
+
+		let address = hex!("ef2d6d194084c2de36e0dabfce45d046b37d1106");
+		let bloom = Bloom::from(Input::Raw(&address));
+		assert!(!bloom.is_empty());
+		assert!((1..=3).contains(&bloom.bits_set()));
+		assert_eq!(bloom.bits_set(), bloom.count_ones());
+		assert_eq!(BloomRef::from(&bloom).bits_set(), bloom.bits_set());
+
+		let full = Bloom::repeat_byte(0xff);
+		assert!(!full.is_empty());
+		assert_eq!(full.bits_set(), 2048);
+	}
+
+	const INPUTS: [&[u8]; 4] = [b"first", b"second", b"third", b"fourth"];
+
+	#[test]
+	fn from_iter_and_extend_match_accrue() {
+		let mut expected = Bloom::default();
+		for input in INPUTS.iter() {
+			expected.accrue(Input::Raw(input));
+		}
+
+		let collected: Bloom = INPUTS.iter().map(|input| Input::Raw(input)).collect();
+		assert_eq!(collected, expected);
+
+		let mut extended = Bloom::from(Input::Raw(INPUTS[0]));
+		extended.extend(INPUTS[1..].iter().map(|input| Input::Raw(input)));
+		assert_eq!(extended, expected);
+	}
+
+	#[test]
+	fn accrue_bloom_iter_matches_accrue_bloom() {
+		let children: Vec<Bloom> = INPUTS.iter().map(|input| Bloom::from(Input::Raw(input))).collect();
+		let mut expected = Bloom::default();
+		for child in &children {
+			expected.accrue_bloom(child);
+		}
+
+		let mut merged = Bloom::default();
+		merged.accrue_bloom_iter(&children);
+		assert_eq!(merged, expected);
+	}
+
+	#[test]
+	fn contains_all_and_any() {
+		let bloom: Bloom = INPUTS[..2].iter().map(|input| Input::Raw(input)).collect();
+		let inputs = || INPUTS.iter().map(|input| Input::Raw(input));
+
+		assert_eq!(bloom.contains_all(inputs().take(2)), inputs().take(2).all(|input| bloom.contains_input(input)));
+		assert!(bloom.contains_all(inputs().take(2)));
+		assert!(!bloom.contains_all(inputs()));
+		assert!(bloom.contains_all(core::iter::empty()));
+
+		assert!(bloom.contains_any(inputs()));
+		assert!(!bloom.contains_any(inputs().skip(2)));
+		assert!(!bloom.contains_any(core::iter::empty()));
+	}
+
+	#[test]
+	fn set_algebra() {
+		let a: Bloom = INPUTS[..2].iter().map(|input| Input::Raw(input)).collect();
+		let b: Bloom = INPUTS[2..].iter().map(|input| Input::Raw(input)).collect();
+		let c: Bloom = INPUTS[1..3].iter().map(|input| Input::Raw(input)).collect();
+		let empty = Bloom::default();
+
+		// Disjoint.
+		let single_a = Bloom::from(Input::Raw(INPUTS[0]));
+		let single_b = Bloom::from(Input::Raw(INPUTS[3]));
+		assert_eq!(single_a & single_b, empty);
+		assert!(!single_a.intersects(&single_b));
+		assert!(!single_a.is_subset_of(&single_b));
+		assert!(!empty.intersects(&a));
+
+		// Overlapping.
+		assert!(a.intersects(&c));
+		assert!(!a.is_subset_of(&c));
+		let mut intersection = a;
+		intersection.intersect_with(&c);
+		assert_eq!(intersection, a & c);
+		assert!(intersection.contains_input(Input::Raw(INPUTS[1])));
+		let mut union = a;
+		union.union_with(&b);
+		assert_eq!(union, a | b);
+		assert!(union.contains_all(INPUTS.iter().map(|input| Input::Raw(input))));
+
+		// Identical.
+		assert!(a.intersects(&a));
+		assert!(a.is_subset_of(&a));
+		let mut same = a;
+		same.intersect_with(&a);
+		same.union_with(&a);
+		assert_eq!(same, a);
+
+		assert!(empty.is_subset_of(&a));
+		assert_eq!(empty.fullness_per_mille(), 0);
+		assert_eq!(Bloom::repeat_byte(0xff).fullness_per_mille(), 1000);
+		assert_eq!(Bloom::repeat_byte(0x0f).fullness_per_mille(), 500);
+	}
+
+	#[test]
+	fn subset_of_union() {
+		use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+
+		let mut rng = StdRng::seed_from_u64(42);
+		for _ in 0..100 {
+			let mut a = Bloom::default();
+			let mut b = Bloom::default();
+			if rng.gen() {
+				rng.fill_bytes(a.as_bytes_mut());
+				rng.fill_bytes(b.as_bytes_mut());
+			} else {
+				a.accrue(Input::Hash(&rng.gen()));
+				b.accrue(Input::Hash(&rng.gen()));
+			}
+			assert!(a.is_subset_of(&(a | b)));
+			assert!(b.is_subset_of(&(a | b)));
+			assert!((a & b).is_subset_of(&a));
+			assert_eq!(a.intersects(&b), !(a & b).is_zero());
+		}
+	}
+
+	#[test]
+	fn contains_all_and_any_short_circuit() {
+		let bloom = Bloom::from(Input::Raw(INPUTS[0]));
+		assert!(!bloom.contains_input(Input::Raw(INPUTS[1])));
+
+		// Stops at the second input, which is missing.
+		let mut consumed = 0;
+		let inputs = INPUTS.iter().inspect(|_| consumed += 1).map(|input| Input::Raw(input));
+		assert!(!bloom.contains_all(inputs));
+		assert_eq!(consumed, 2);
+
+		// Stops at the first input, which is present.
+		let mut consumed = 0;
+		let inputs = INPUTS.iter().inspect(|_| consumed += 1).map(|input| Input::Raw(input));
+		assert!(bloom.contains_any(inputs));
+		assert_eq!(consumed, 1);
+	}
 }
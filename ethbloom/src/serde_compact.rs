@@ -0,0 +1,101 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Compact serde representation of `Bloom`, for use with `#[serde(with = "ethbloom::serde_compact")]`.
+//!
+//! The bloom is written as a `0x`-prefixed big-endian hex number without leading zeros,
+//! like the uint types, so that the empty bloom becomes `"0x0"` instead of 512 zeros.
+//! Deserialization accepts both this form and the full 512 digit form of the default
+//! `Serialize` impl.
+
+use crate::{Bloom, BLOOM_SIZE};
+use impl_serde::{
+	serde::{Deserializer, Serializer},
+	serialize::{deserialize_check_len, serialize_uint, ExpectedLen},
+};
+
+/// Serializes the bloom without leading zeros.
+pub fn serialize<S>(bloom: &Bloom, serializer: S) -> Result<S::Ok, S::Error>
+where
+	S: Serializer,
+{
+	let mut slice = [0u8; 2 + 2 * BLOOM_SIZE];
+	serialize_uint(&mut slice, &bloom.0, serializer)
+}
+
+/// Deserializes a `0x`-prefixed bloom of up to 512 hex digits, padding it with leading zeros.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Bloom, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	let mut bytes = [0u8; BLOOM_SIZE];
+	let wrote = deserialize_check_len(deserializer, ExpectedLen::Between(0, &mut bytes))?;
+	let mut bloom = Bloom::zero();
+	bloom.0[BLOOM_SIZE - wrote..].copy_from_slice(&bytes[..wrote]);
+	Ok(bloom)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{Bloom, Input};
+	use serde_derive::{Deserialize, Serialize};
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct Compact(#[serde(with = "super")] Bloom);
+
+	fn roundtrip(bloom: Bloom) -> String {
+		let json = serde_json::to_string(&Compact(bloom)).unwrap();
+		assert_eq!(serde_json::from_str::<Compact>(&json).unwrap(), Compact(bloom));
+		json
+	}
+
+	#[test]
+	fn empty() {
+		assert_eq!(roundtrip(Bloom::zero()), "\"0x0\"");
+	}
+
+	#[test]
+	fn sparse() {
+		let mut bloom = Bloom::zero();
+		bloom.0[255] = 0x01;
+		assert_eq!(roundtrip(bloom), "\"0x1\"");
+
+		bloom.0[250] = 0xab;
+		assert_eq!(roundtrip(bloom), "\"0xab0000000001\"");
+
+		roundtrip(Bloom::from(Input::Raw(b"sparse")));
+	}
+
+	#[test]
+	fn dense() {
+		let full = Bloom::repeat_byte(0xff);
+		assert_eq!(roundtrip(full), format!("\"0x{}\"", "ff".repeat(256)));
+
+		let mut leading = Bloom::zero();
+		leading.0[0] = 0x80;
+		assert_eq!(roundtrip(leading).len(), 2 + 2 + 512);
+	}
+
+	#[test]
+	fn accepts_full_form() {
+		let bloom = Bloom::from(Input::Raw(b"full form"));
+		let full = serde_json::to_string(&bloom).unwrap();
+		assert_eq!(full.len(), 2 + 2 + 512);
+		assert_eq!(serde_json::from_str::<Compact>(&full).unwrap(), Compact(bloom));
+
+		let empty = serde_json::to_string(&Bloom::zero()).unwrap();
+		assert_eq!(serde_json::from_str::<Compact>(&empty).unwrap(), Compact(Bloom::zero()));
+	}
+
+	#[test]
+	fn rejects_too_long() {
+		let too_long = format!("\"0x1{}\"", "00".repeat(256));
+		assert!(serde_json::from_str::<Compact>(&too_long).is_err());
+		assert!(serde_json::from_str::<Compact>("\"0x\"").is_err());
+	}
+}
@@ -0,0 +1,69 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{Address, Bloom, BloomInput, H256};
+
+/// Computes the bloom of a single log entry from its address and topics.
+///
+/// This is the log bloom of the Yellow Paper: the address and each topic are accrued
+/// into an empty bloom. The bloom of a receipt is the union of the blooms of its logs.
+pub fn logs_bloom(address: &Address, topics: &[H256]) -> Bloom {
+	let mut bloom = Bloom::from(BloomInput::Raw(address.as_bytes()));
+	for topic in topics {
+		bloom.accrue(BloomInput::Raw(topic.as_bytes()));
+	}
+	bloom
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use core::str::FromStr;
+
+	#[test]
+	#[rustfmt::skip]
+	fn known_log() {
+		let bloom = Bloom::from_str(
+			"00000000000000000000000000000000\
+			 00000000100000000000000000000000\
+			 00000000000000000000000000000000\
+			 00000000000000000000000000000000\
+			 00000000000000000000000000000000\
+			 00000000000000000000000000000000\
+			 00000002020000000000000000000000\
+			 00000000000000000000000800000000\
+			 10000000000000000000000000000000\
+			 00000000000000000000001000000000\
+			 00000000000000000000000000000000\
+			 00000000000000000000000000000000\
+			 00000000000000000000000000000000\
+			 00000000000000000000000000000000\
+			 00000000000000000000000000000000\
+			 00000000000000000000000000000000",
+		).unwrap();
+		let address = Address::from_str("ef2d6d194084c2de36e0dabfce45d046b37d1106").unwrap();
+		let topic = H256::from_str("02c69be41d0b7e40352fc85be1cd65eb03d40ef8427a0ca4596b1ead9a00e9fc").unwrap();
+
+		assert_eq!(logs_bloom(&address, &[topic]), bloom);
+	}
+
+	#[test]
+	fn matches_accrue() {
+		let address = Address::repeat_byte(0x11);
+		let topics = [H256::repeat_byte(0x22), H256::repeat_byte(0x33), H256::zero()];
+
+		let mut expected = Bloom::default();
+		expected.accrue(BloomInput::Raw(address.as_bytes()));
+		assert_eq!(logs_bloom(&address, &[]), expected);
+		for topic in &topics {
+			expected.accrue(BloomInput::Raw(topic.as_bytes()));
+		}
+		assert_eq!(logs_bloom(&address, &topics), expected);
+		assert!(expected.contains_input(BloomInput::Raw(topics[1].as_bytes())));
+	}
+}
@@ -0,0 +1,266 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Mixed-case checksum encoding of addresses, as specified by
+//! [EIP-55](https://eips.ethereum.org/EIPS/eip-55) and, with a chain id,
+//! [EIP-1191](https://eips.ethereum.org/EIPS/eip-1191).
+
+use crate::H160;
+use alloc::string::{String, ToString};
+use core::fmt;
+use keccak_hash::keccak;
+
+const HEX_LEN: usize = 40;
+// Decimal chain id (at most 20 digits), `0x` and the lowercase address.
+const MAX_PREIMAGE_LEN: usize = 20 + 2 + HEX_LEN;
+
+/// Errors when parsing a checksummed address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumError {
+	/// The address does not have 40 hex digits.
+	InvalidLength,
+	/// The character at the given index of the input is not a hex digit.
+	InvalidCharacter(usize),
+	/// The casing of the address does not match its checksum.
+	InvalidChecksum,
+}
+
+impl fmt::Display for ChecksumError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			ChecksumError::InvalidLength => write!(f, "address must have 40 hex digits"),
+			ChecksumError::InvalidCharacter(index) => write!(f, "invalid hex character at index {}", index),
+			ChecksumError::InvalidChecksum => write!(f, "invalid address checksum"),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ChecksumError {}
+
+/// Checksummed formatting and parsing of addresses.
+pub trait AddressChecksum: Sized {
+	/// Format the address with its EIP-55 checksum, including the `0x` prefix.
+	fn to_checksum_string(&self) -> String {
+		self.checksummed(None).to_string()
+	}
+
+	/// Format the address with its EIP-1191 checksum for `chain_id`, including the `0x` prefix.
+	fn to_checksum_string_with_chain_id(&self, chain_id: u64) -> String {
+		self.checksummed(Some(chain_id)).to_string()
+	}
+
+	/// Displays the address with its checksum, using EIP-1191 when a chain id is given.
+	fn checksummed(&self, chain_id: Option<u64>) -> Checksummed;
+
+	/// Parse an address, with or without `0x` prefix, verifying its EIP-55 checksum.
+	///
+	/// All-lowercase and all-uppercase addresses carry no checksum and are accepted as is.
+	fn from_checksum_str(s: &str) -> Result<Self, ChecksumError> {
+		Self::from_checksum_str_with_chain_id(s, None)
+	}
+
+	/// Parse an address, verifying its checksum, using EIP-1191 when a chain id is given.
+	///
+	/// All-lowercase and all-uppercase addresses carry no checksum and are accepted as is.
+	fn from_checksum_str_with_chain_id(s: &str, chain_id: Option<u64>) -> Result<Self, ChecksumError>;
+}
+
+impl AddressChecksum for H160 {
+	fn checksummed(&self, chain_id: Option<u64>) -> Checksummed {
+		Checksummed { address: *self, chain_id }
+	}
+
+	fn from_checksum_str_with_chain_id(s: &str, chain_id: Option<u64>) -> Result<Self, ChecksumError> {
+		let (offset, digits) = match s.strip_prefix("0x") {
+			Some(digits) => (2, digits.as_bytes()),
+			None => (0, s.as_bytes()),
+		};
+		if digits.len() != HEX_LEN {
+			return Err(ChecksumError::InvalidLength);
+		}
+
+		let mut address = H160::zero();
+		let (mut has_lower, mut has_upper) = (false, false);
+		for (i, &c) in digits.iter().enumerate() {
+			let nibble = match c {
+				b'0'..=b'9' => c - b'0',
+				b'a'..=b'f' => {
+					has_lower = true;
+					c - b'a' + 10
+				}
+				b'A'..=b'F' => {
+					has_upper = true;
+					c - b'A' + 10
+				}
+				_ => return Err(ChecksumError::InvalidCharacter(offset + i)),
+			};
+			address.0[i / 2] |= nibble << (4 * (1 - i % 2));
+		}
+
+		if has_lower && has_upper {
+			let expected = checksum_digits(&address, chain_id);
+			if digits != &expected[..] {
+				return Err(ChecksumError::InvalidChecksum);
+			}
+		}
+		Ok(address)
+	}
+}
+
+/// Address displayed with its checksum, created by [`AddressChecksum::checksummed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checksummed {
+	address: H160,
+	chain_id: Option<u64>,
+}
+
+impl fmt::Display for Checksummed {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let digits = checksum_digits(&self.address, self.chain_id);
+		// Only ASCII hex digits are written to the buffer.
+		let digits = core::str::from_utf8(&digits).expect("hex digits are valid UTF-8; qed");
+		write!(f, "0x{}", digits)
+	}
+}
+
+fn lower_hex(address: &H160) -> [u8; HEX_LEN] {
+	const DIGITS: &[u8; 16] = b"0123456789abcdef";
+	let mut hex = [0u8; HEX_LEN];
+	for (i, byte) in address.as_bytes().iter().enumerate() {
+		hex[2 * i] = DIGITS[(byte >> 4) as usize];
+		hex[2 * i + 1] = DIGITS[(byte & 0xf) as usize];
+	}
+	hex
+}
+
+fn checksum_digits(address: &H160, chain_id: Option<u64>) -> [u8; HEX_LEN] {
+	let mut hex = lower_hex(address);
+
+	let mut preimage = [0u8; MAX_PREIMAGE_LEN];
+	let mut len = 0;
+	if let Some(chain_id) = chain_id {
+		let mut digits = [0u8; 20];
+		let mut n = chain_id;
+		let mut count = 0;
+		loop {
+			digits[count] = b'0' + (n % 10) as u8;
+			count += 1;
+			n /= 10;
+			if n == 0 {
+				break;
+			}
+		}
+		for &digit in digits[..count].iter().rev() {
+			preimage[len] = digit;
+			len += 1;
+		}
+		preimage[len..len + 2].copy_from_slice(b"0x");
+		len += 2;
+	}
+	preimage[len..len + HEX_LEN].copy_from_slice(&hex);
+	len += HEX_LEN;
+
+	let hash = keccak(&preimage[..len]);
+	for (i, c) in hex.iter_mut().enumerate() {
+		let nibble = (hash.as_bytes()[i / 2] >> (4 * (1 - i % 2))) & 0xf;
+		if c.is_ascii_alphabetic() && nibble >= 8 {
+			c.make_ascii_uppercase();
+		}
+	}
+	hex
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use core::str::FromStr;
+
+	// Test vectors from EIP-55.
+	const EIP55: [&str; 8] = [
+		"0x52908400098527886E0F7030069857D2E4169EE7",
+		"0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+		"0xde709f2102306220921060314715629080e2fb77",
+		"0x27b1fdb04752bbc536007a920d24acb045561c26",
+		"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+		"0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+		"0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+		"0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+	];
+
+	// Test vectors from EIP-1191 for chain id 30.
+	const EIP1191_CHAIN_30: [&str; 4] = [
+		"0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD",
+		"0xFb6916095cA1Df60bb79ce92cE3EA74c37c5d359",
+		"0xDBF03B407c01E7CD3cBea99509D93F8Dddc8C6FB",
+		"0xD1220A0Cf47c7B9BE7a2e6ba89F429762E7B9adB",
+	];
+
+	#[test]
+	fn eip55_vectors() {
+		for vector in EIP55.iter() {
+			let address = H160::from_str(&vector[2..]).unwrap();
+			assert_eq!(address.to_checksum_string(), *vector);
+			assert_eq!(format!("{}", address.checksummed(None)), *vector);
+			assert_eq!(H160::from_checksum_str(vector), Ok(address));
+			assert_eq!(H160::from_checksum_str(&vector[2..]), Ok(address));
+		}
+	}
+
+	#[test]
+	fn eip1191_vectors() {
+		for vector in EIP1191_CHAIN_30.iter() {
+			let address = H160::from_str(&vector[2..]).unwrap();
+			assert_eq!(address.to_checksum_string_with_chain_id(30), *vector);
+			assert_eq!(H160::from_checksum_str_with_chain_id(vector, Some(30)), Ok(address));
+			// The EIP-1191 casing is not a valid EIP-55 checksum.
+			assert_eq!(H160::from_checksum_str(vector), Err(ChecksumError::InvalidChecksum));
+		}
+	}
+
+	#[test]
+	fn accepts_unchecked_case() {
+		let lower = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+		let upper = "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED";
+		let address = H160::from_str(&lower[2..]).unwrap();
+		assert_eq!(H160::from_checksum_str(lower), Ok(address));
+		assert_eq!(H160::from_checksum_str(upper), Ok(address));
+		assert_eq!(H160::from_checksum_str_with_chain_id(upper, Some(30)), Ok(address));
+	}
+
+	#[test]
+	fn rejects_wrong_case() {
+		// First letter of a valid checksum flipped to lowercase.
+		assert_eq!(
+			H160::from_checksum_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"),
+			Err(ChecksumError::InvalidChecksum)
+		);
+		assert_eq!(
+			H160::from_checksum_str("0xd1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb"),
+			Err(ChecksumError::InvalidChecksum)
+		);
+	}
+
+	#[test]
+	fn rejects_malformed_input() {
+		assert_eq!(
+			H160::from_checksum_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAe"),
+			Err(ChecksumError::InvalidLength)
+		);
+		assert_eq!(H160::from_checksum_str(""), Err(ChecksumError::InvalidLength));
+		assert_eq!(
+			H160::from_checksum_str("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg"),
+			Err(ChecksumError::InvalidCharacter(41))
+		);
+		assert_eq!(
+			H160::from_checksum_str("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg"),
+			Err(ChecksumError::InvalidCharacter(39))
+		);
+		assert_eq!(ChecksumError::InvalidCharacter(3).to_string(), "invalid hex character at index 3");
+	}
+}
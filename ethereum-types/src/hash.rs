@@ -58,6 +58,8 @@ impl_fixed_hash_serde!(H264, 33);
 impl_fixed_hash_codec!(H264, 33);
 
 pub use primitive_types::H512;
+#[cfg(feature = "bls-hashes")]
+pub use primitive_types::{H384, H768};
 
 construct_fixed_hash! { pub struct H520(65); }
 #[cfg(feature = "rlp")]
@@ -67,6 +69,27 @@ impl_fixed_hash_serde!(H520, 65);
 #[cfg(feature = "codec")]
 impl_fixed_hash_codec!(H520, 65);
 
+impl H520 {
+	/// Joins two 32-byte words and a trailing byte, e.g. the `r`, `s` and `v` parts of a signature.
+	///
+	/// # Note
+	///
+	/// The parts are laid out in the given order: `first` in bytes `0..32`,
+	/// `second` in bytes `32..64` and `last` in byte `64`.
+	pub fn from_parts(first: H256, second: H256, last: u8) -> Self {
+		let mut ret = Self::zero();
+		ret[..32].copy_from_slice(first.as_bytes());
+		ret[32..64].copy_from_slice(second.as_bytes());
+		ret.0[64] = last;
+		ret
+	}
+
+	/// Splits into the two leading 32-byte words and the trailing byte, the inverse of `from_parts`.
+	pub fn split(self) -> (H256, H256, u8) {
+		(H256::from_slice(&self[..32]), H256::from_slice(&self[32..64]), self[64])
+	}
+}
+
 macro_rules! impl_uint_conversions {
 	($hash: ident, $uint: ident) => {
 		impl BigEndianHash for $hash {
@@ -79,7 +102,7 @@ macro_rules! impl_uint_conversions {
 			}
 
 			fn into_uint(&self) -> $uint {
-				$uint::from(self.as_ref() as &[u8])
+				$uint::from(self.as_bytes())
 			}
 		}
 	};
@@ -92,7 +115,7 @@ impl_uint_conversions!(H512, U512);
 
 #[cfg(test)]
 mod tests {
-	use super::{H160, H256};
+	use super::{H160, H256, H512, H520};
 	use serde_json as ser;
 
 	#[test]
@@ -134,6 +157,20 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn h520_parts_round_trip() {
+		let (r, s) = (H256::repeat_byte(0x11), H256::repeat_byte(0x22));
+		let signature = H520::from_parts(r, s, 0x1b);
+		assert_eq!(&signature[..32], r.as_bytes());
+		assert_eq!(&signature[32..64], s.as_bytes());
+		assert_eq!(signature[64], 0x1b);
+		assert_eq!(signature.split(), (r, s, 0x1b));
+
+		let public = H512::from_halves(r, s);
+		assert_eq!(public.split(), (r, s));
+		assert_eq!(&signature[..64], public.as_bytes());
+	}
+
 	#[test]
 	fn test_parse_0x() {
 		assert!("0x0000000000000000000000000000000000000000000000000000000000000000".parse::<H256>().is_ok())
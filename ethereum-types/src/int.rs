@@ -0,0 +1,547 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{FromDecStrErr, U256};
+use core::{cmp::Ordering, fmt, ops::Neg};
+
+const SIGN_BIT: U256 = U256([0, 0, 0, 1 << 63]);
+
+/// Signed 256-bit integer, stored as its two's complement `U256` representation.
+///
+/// `from_raw` and `into_raw` convert from and to the representation, matching how the EVM
+/// stores signed words. `sdiv`, `smod` and `asr` implement the EVM's signed operations.
+///
+/// Serialized as `0x`-prefixed two's complement hex like `U256`, see `i256_serde_decimal`
+/// for signed decimal strings. RLP and SCALE encode the two's complement representation too.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct I256(U256);
+
+impl I256 {
+	/// The smallest value, `-2^255`.
+	pub const MIN: I256 = I256(SIGN_BIT);
+	/// The largest value, `2^255 - 1`.
+	pub const MAX: I256 = I256(U256([u64::MAX, u64::MAX, u64::MAX, u64::MAX >> 1]));
+	/// Zero.
+	pub const ZERO: I256 = I256(U256([0; 4]));
+	/// One.
+	pub const ONE: I256 = I256(U256([1, 0, 0, 0]));
+	/// Minus one.
+	pub const MINUS_ONE: I256 = I256(U256([u64::MAX; 4]));
+
+	/// Interprets `raw` as a two's complement number.
+	#[inline]
+	pub const fn from_raw(raw: U256) -> Self {
+		I256(raw)
+	}
+
+	/// Returns the two's complement representation.
+	#[inline]
+	pub const fn into_raw(self) -> U256 {
+		self.0
+	}
+
+	/// Converts a `U256` with the same value, failing if it is larger than `I256::MAX`.
+	#[inline]
+	pub fn checked_from(value: U256) -> Option<Self> {
+		if value.bit(255) {
+			None
+		} else {
+			Some(I256(value))
+		}
+	}
+
+	/// Returns `true` if the number is negative.
+	#[inline]
+	pub fn is_negative(&self) -> bool {
+		self.0.bit(255)
+	}
+
+	/// Returns `true` if the number is zero.
+	#[inline]
+	pub fn is_zero(&self) -> bool {
+		self.0.is_zero()
+	}
+
+	/// Returns the absolute value as a `U256`, which can represent `|I256::MIN|`.
+	#[inline]
+	pub fn unsigned_abs(self) -> U256 {
+		if self.is_negative() {
+			self.0.wrapping_neg()
+		} else {
+			self.0
+		}
+	}
+
+	/// Negation, wrapping around for `I256::MIN`.
+	#[inline]
+	pub fn wrapping_neg(self) -> Self {
+		I256(self.0.wrapping_neg())
+	}
+
+	/// Negation, returning `None` for `I256::MIN`.
+	#[inline]
+	pub fn checked_neg(self) -> Option<Self> {
+		if self == Self::MIN {
+			None
+		} else {
+			Some(self.wrapping_neg())
+		}
+	}
+
+	/// Addition, wrapping around at the boundary of the type, like the EVM's `ADD`.
+	#[inline]
+	pub fn wrapping_add(self, other: Self) -> Self {
+		I256(self.0.overflowing_add(other.0).0)
+	}
+
+	/// Subtraction, wrapping around at the boundary of the type, like the EVM's `SUB`.
+	#[inline]
+	pub fn wrapping_sub(self, other: Self) -> Self {
+		I256(self.0.overflowing_sub(other.0).0)
+	}
+
+	/// Multiplication, wrapping around at the boundary of the type, like the EVM's `MUL`.
+	#[inline]
+	pub fn wrapping_mul(self, other: Self) -> Self {
+		I256(self.0.overflowing_mul(other.0).0)
+	}
+
+	/// Addition, returning `None` on overflow.
+	pub fn checked_add(self, other: Self) -> Option<Self> {
+		let result = self.wrapping_add(other);
+		// Overflow flips the sign of the sum of two numbers with the same sign.
+		if self.is_negative() == other.is_negative() && result.is_negative() != self.is_negative() {
+			None
+		} else {
+			Some(result)
+		}
+	}
+
+	/// Subtraction, returning `None` on overflow.
+	pub fn checked_sub(self, other: Self) -> Option<Self> {
+		let result = self.wrapping_sub(other);
+		if self.is_negative() != other.is_negative() && result.is_negative() != self.is_negative() {
+			None
+		} else {
+			Some(result)
+		}
+	}
+
+	/// Multiplication, returning `None` on overflow.
+	pub fn checked_mul(self, other: Self) -> Option<Self> {
+		let magnitude = self.unsigned_abs().checked_mul(other.unsigned_abs())?;
+		Self::from_sign_and_magnitude(self.is_negative() != other.is_negative(), magnitude)
+	}
+
+	/// Division truncating toward zero, returning `None` if `other` is zero
+	/// or for `I256::MIN / -1`, which overflows.
+	pub fn checked_div(self, other: Self) -> Option<Self> {
+		if other.is_zero() || (self == Self::MIN && other == Self::MINUS_ONE) {
+			return None;
+		}
+		let quotient = I256(self.unsigned_abs() / other.unsigned_abs());
+		Some(if self.is_negative() != other.is_negative() { quotient.wrapping_neg() } else { quotient })
+	}
+
+	/// Remainder of the division truncating toward zero, which has the sign of `self`.
+	/// Returns `None` if `other` is zero.
+	pub fn checked_rem(self, other: Self) -> Option<Self> {
+		if other.is_zero() {
+			return None;
+		}
+		let remainder = I256(self.unsigned_abs() % other.unsigned_abs());
+		Some(if self.is_negative() { remainder.wrapping_neg() } else { remainder })
+	}
+
+	/// Signed division truncating toward zero, as the EVM's `SDIV`.
+	///
+	/// Division by zero returns zero and `I256::MIN / -1` returns `I256::MIN`.
+	pub fn sdiv(self, other: Self) -> Self {
+		if self == Self::MIN && other == Self::MINUS_ONE {
+			return Self::MIN;
+		}
+		self.checked_div(other).unwrap_or(Self::ZERO)
+	}
+
+	/// Signed remainder with the sign of `self`, as the EVM's `SMOD`.
+	///
+	/// The remainder of a division by zero is zero.
+	pub fn smod(self, other: Self) -> Self {
+		self.checked_rem(other).unwrap_or(Self::ZERO)
+	}
+
+	/// Arithmetic shift right, filling with the sign bit, as the EVM's `SAR`.
+	///
+	/// Shifting by 256 bits or more returns `-1` for negative numbers and `0` otherwise.
+	pub fn asr(self, shift: usize) -> Self {
+		match (shift >= 256, self.is_negative()) {
+			(true, true) => Self::MINUS_ONE,
+			(true, false) => Self::ZERO,
+			(false, true) => I256(!(!self.0 >> shift)),
+			(false, false) => I256(self.0 >> shift),
+		}
+	}
+
+	/// Converts from a decimal string with an optional leading `-`.
+	///
+	/// The digits are parsed as strictly as by `U256::from_dec_str`.
+	pub fn from_dec_str(value: &str) -> Result<Self, FromDecStrErr> {
+		let (negative, digits) = match value.strip_prefix('-') {
+			Some(digits) => (true, digits),
+			None => (false, value),
+		};
+		let magnitude = U256::from_dec_str(digits)?;
+		Self::from_sign_and_magnitude(negative, magnitude).ok_or(FromDecStrErr::InvalidLength)
+	}
+
+	fn from_sign_and_magnitude(negative: bool, magnitude: U256) -> Option<Self> {
+		match (negative, magnitude.cmp(&SIGN_BIT)) {
+			(true, Ordering::Greater) | (false, Ordering::Greater) | (false, Ordering::Equal) => None,
+			(true, _) => Some(I256(magnitude).wrapping_neg()),
+			(false, Ordering::Less) => Some(I256(magnitude)),
+		}
+	}
+}
+
+impl Ord for I256 {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// Flipping the sign bit maps the signed order onto the unsigned one.
+		(self.0 ^ SIGN_BIT).cmp(&(other.0 ^ SIGN_BIT))
+	}
+}
+
+impl PartialOrd for I256 {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Neg for I256 {
+	type Output = I256;
+
+	fn neg(self) -> Self::Output {
+		self.checked_neg().expect("attempt to negate with overflow")
+	}
+}
+
+macro_rules! impl_from_signed {
+	($($t:ty),*) => {
+		$(
+			impl From<$t> for I256 {
+				fn from(value: $t) -> Self {
+					let value = i128::from(value);
+					let fill = if value < 0 { u64::MAX } else { 0 };
+					I256(U256([value as u64, (value >> 64) as u64, fill, fill]))
+				}
+			}
+		)*
+	};
+}
+
+macro_rules! impl_from_unsigned {
+	($($t:ty),*) => {
+		$(
+			impl From<$t> for I256 {
+				fn from(value: $t) -> Self {
+					I256(U256::from(value))
+				}
+			}
+		)*
+	};
+}
+
+impl_from_signed!(i8, i16, i32, i64, i128);
+impl_from_unsigned!(u8, u16, u32, u64, u128);
+
+/// Stack buffer for the decimal digits of a `U256`, of which there are at most 78.
+struct DecimalDigits {
+	buf: [u8; 78],
+	len: usize,
+}
+
+impl fmt::Write for DecimalDigits {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		let end = self.len + s.len();
+		self.buf.get_mut(self.len..end).ok_or(fmt::Error)?.copy_from_slice(s.as_bytes());
+		self.len = end;
+		Ok(())
+	}
+}
+
+/// Honors the formatter width, fill, alignment and `+` and `0` flags like the primitive
+/// integers do.
+impl fmt::Display for I256 {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut digits = DecimalDigits { buf: [0; 78], len: 0 };
+		fmt::Write::write_fmt(&mut digits, format_args!("{}", self.unsigned_abs()))?;
+		let digits = core::str::from_utf8(&digits.buf[..digits.len]).expect("decimal digits are ASCII; qed");
+		f.pad_integral(!self.is_negative(), "", digits)
+	}
+}
+
+impl fmt::Debug for I256 {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Display::fmt(self, f)
+	}
+}
+
+/// Formats the two's complement representation.
+impl fmt::LowerHex for I256 {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::LowerHex::fmt(&self.0, f)
+	}
+}
+
+#[cfg(feature = "serialize")]
+impl impl_serde::serde::Serialize for I256 {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: impl_serde::serde::Serializer,
+	{
+		impl_serde::serde::Serialize::serialize(&self.0, serializer)
+	}
+}
+
+#[cfg(feature = "serialize")]
+impl<'de> impl_serde::serde::Deserialize<'de> for I256 {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: impl_serde::serde::Deserializer<'de>,
+	{
+		<U256 as impl_serde::serde::Deserialize>::deserialize(deserializer).map(I256)
+	}
+}
+
+/// Serde adapter writing `I256` as a signed decimal string, for use with
+/// `#[serde(with = "ethereum_types::i256_serde_decimal")]`.
+#[cfg(feature = "serialize")]
+pub mod serde_decimal {
+	use super::I256;
+	use core::fmt;
+	use impl_serde::serde::{de, Deserializer, Serializer};
+
+	/// Serializes the number as a decimal string, e.g. `"-42"`.
+	pub fn serialize<S>(value: &I256, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.collect_str(value)
+	}
+
+	/// Deserializes a decimal string with an optional leading `-`.
+	pub fn deserialize<'de, D>(deserializer: D) -> Result<I256, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct Visitor;
+
+		impl<'de> de::Visitor<'de> for Visitor {
+			type Value = I256;
+
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				write!(formatter, "a signed decimal string")
+			}
+
+			fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+				I256::from_dec_str(v).map_err(E::custom)
+			}
+		}
+
+		deserializer.deserialize_str(Visitor)
+	}
+}
+
+#[cfg(feature = "rlp")]
+impl impl_rlp::rlp::Encodable for I256 {
+	fn rlp_append(&self, s: &mut impl_rlp::rlp::RlpStream) {
+		impl_rlp::rlp::Encodable::rlp_append(&self.0, s)
+	}
+}
+
+#[cfg(feature = "rlp")]
+impl impl_rlp::rlp::Decodable for I256 {
+	fn decode(rlp: &impl_rlp::rlp::Rlp) -> Result<Self, impl_rlp::rlp::DecoderError> {
+		<U256 as impl_rlp::rlp::Decodable>::decode(rlp).map(I256)
+	}
+}
+
+#[cfg(feature = "codec")]
+impl impl_codec::codec::Encode for I256 {
+	fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+		let mut bytes = [0u8; 32];
+		self.0.to_little_endian(&mut bytes);
+		impl_codec::codec::Encode::using_encoded(&bytes, f)
+	}
+}
+
+#[cfg(feature = "codec")]
+impl impl_codec::codec::EncodeLike for I256 {}
+
+#[cfg(feature = "codec")]
+impl impl_codec::codec::Decode for I256 {
+	fn decode<I: impl_codec::codec::Input>(input: &mut I) -> Result<Self, impl_codec::codec::Error> {
+		<[u8; 32] as impl_codec::codec::Decode>::decode(input).map(|bytes| I256(U256::from_little_endian(&bytes)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn i(value: i64) -> I256 {
+		I256::from(value)
+	}
+
+	#[test]
+	fn raw_conversions() {
+		assert_eq!(I256::from_raw(U256::MAX), I256::MINUS_ONE);
+		assert_eq!(I256::MINUS_ONE.into_raw(), U256::MAX);
+		assert_eq!(i(-1).into_raw(), U256::MAX);
+		assert_eq!(I256::from_raw(i(-12345).into_raw()), i(-12345));
+		assert_eq!(I256::MIN.into_raw(), U256::one() << 255);
+		assert_eq!(I256::MAX.into_raw(), U256::MAX >> 1);
+
+		assert_eq!(I256::checked_from(U256::from(42)), Some(i(42)));
+		assert_eq!(I256::checked_from(U256::MAX >> 1), Some(I256::MAX));
+		assert_eq!(I256::checked_from(U256::one() << 255), None);
+		assert_eq!(I256::checked_from(U256::MAX), None);
+	}
+
+	#[test]
+	fn min_and_max() {
+		assert!(I256::MIN.is_negative());
+		assert!(!I256::MAX.is_negative());
+		assert!(I256::MIN < i(-1) && i(-1) < I256::ZERO && I256::ZERO < I256::MAX);
+		assert_eq!(I256::MIN.unsigned_abs(), U256::one() << 255);
+		assert_eq!(I256::MIN.checked_neg(), None);
+		assert_eq!(I256::MIN.wrapping_neg(), I256::MIN);
+		assert_eq!(-I256::MAX, I256::MIN.wrapping_add(I256::ONE));
+		assert_eq!(I256::MAX.checked_add(I256::ONE), None);
+		assert_eq!(I256::MIN.checked_sub(I256::ONE), None);
+		assert_eq!(I256::MAX.wrapping_add(I256::ONE), I256::MIN);
+		assert_eq!(I256::MIN.checked_mul(I256::ONE), Some(I256::MIN));
+		assert_eq!(I256::MIN.checked_mul(I256::MINUS_ONE), None);
+		assert_eq!(i(-3).checked_mul(i(7)), Some(i(-21)));
+	}
+
+	#[test]
+	fn from_primitives() {
+		assert_eq!(I256::from(-1i8), I256::MINUS_ONE);
+		assert_eq!(I256::from(i128::MIN).unsigned_abs(), U256::one() << 127);
+		assert!(I256::from(i128::MIN).is_negative());
+		assert_eq!(I256::from(u128::MAX).into_raw(), U256::from(u128::MAX));
+		assert_eq!(I256::from(i64::MIN), -I256::from(1u64 << 63));
+	}
+
+	#[test]
+	fn division_truncates_toward_zero() {
+		assert_eq!(i(7).sdiv(i(2)), i(3));
+		assert_eq!(i(-7).sdiv(i(2)), i(-3));
+		assert_eq!(i(7).sdiv(i(-2)), i(-3));
+		assert_eq!(i(-7).sdiv(i(-2)), i(3));
+		assert_eq!(i(-1).sdiv(i(2)), I256::ZERO);
+
+		assert_eq!(i(7).smod(i(2)), i(1));
+		assert_eq!(i(-7).smod(i(2)), i(-1));
+		assert_eq!(i(7).smod(i(-2)), i(1));
+		assert_eq!(i(-7).smod(i(-2)), i(-1));
+
+		// EVM edge cases.
+		assert_eq!(i(5).sdiv(I256::ZERO), I256::ZERO);
+		assert_eq!(i(5).smod(I256::ZERO), I256::ZERO);
+		assert_eq!(I256::MIN.sdiv(I256::MINUS_ONE), I256::MIN);
+		assert_eq!(I256::MIN.checked_div(I256::MINUS_ONE), None);
+		assert_eq!(I256::MIN.smod(I256::MINUS_ONE), I256::ZERO);
+		assert_eq!(I256::MIN.sdiv(i(2)), -I256::from_raw(U256::one() << 254));
+	}
+
+	#[test]
+	fn arithmetic_shift_right() {
+		assert_eq!(i(-8).asr(1), i(-4));
+		assert_eq!(i(-7).asr(1), i(-4));
+		assert_eq!(i(8).asr(3), i(1));
+		assert_eq!(I256::MIN.asr(255), I256::MINUS_ONE);
+		assert_eq!(I256::MAX.asr(254), I256::ONE);
+		assert_eq!(i(-1).asr(300), I256::MINUS_ONE);
+		assert_eq!(i(1).asr(256), I256::ZERO);
+		assert_eq!(i(-5).asr(0), i(-5));
+	}
+
+	#[test]
+	fn decimal_strings() {
+		assert_eq!(I256::from_dec_str("-42"), Ok(i(-42)));
+		assert_eq!(I256::from_dec_str("42"), Ok(i(42)));
+		assert_eq!(I256::from_dec_str("-0"), Ok(I256::ZERO));
+		assert_eq!(I256::from_dec_str(&I256::MIN.to_string()), Ok(I256::MIN));
+		assert_eq!(I256::from_dec_str(&I256::MAX.to_string()), Ok(I256::MAX));
+		assert_eq!(I256::from_dec_str(&(U256::one() << 255).to_string()), Err(FromDecStrErr::InvalidLength));
+		assert_eq!(I256::from_dec_str("-"), Err(FromDecStrErr::Empty));
+		assert_eq!(I256::from_dec_str("--1"), Err(FromDecStrErr::InvalidCharacter));
+
+		assert_eq!(i(-42).to_string(), "-42");
+		assert_eq!(format!("{:?}", i(7)), "7");
+		assert_eq!(format!("{:>10}", i(-5)), "        -5");
+		assert_eq!(format!("{:<5}|", i(-5)), "-5   |");
+		assert_eq!(format!("{:05}", i(-5)), "-0005");
+		assert_eq!(format!("{:+}", i(5)), "+5");
+		assert_eq!(format!("{:*^7}", I256::ZERO), "***0***");
+		assert_eq!(I256::MIN.to_string(), format!("-{}", U256::one() << 255));
+		assert_eq!(format!("{:x}", i(-1)), "f".repeat(64));
+	}
+
+	#[cfg(feature = "serialize")]
+	#[test]
+	fn serde() {
+		use serde_json as ser;
+
+		assert_eq!(ser::to_string(&i(255)).unwrap(), "\"0xff\"");
+		assert_eq!(ser::to_string(&i(-1)).unwrap(), format!("\"0x{}\"", "f".repeat(64)));
+		for value in [I256::MIN, I256::MAX, i(-1), I256::ZERO, i(12345)].iter() {
+			let json = ser::to_string(value).unwrap();
+			assert_eq!(ser::from_str::<I256>(&json).unwrap(), *value);
+		}
+
+		let to_decimal = |value: &I256| {
+			let mut json = Vec::new();
+			serde_decimal::serialize(value, &mut ser::Serializer::new(&mut json)).unwrap();
+			String::from_utf8(json).unwrap()
+		};
+		let from_decimal = |json: &str| serde_decimal::deserialize(&mut ser::Deserializer::from_str(json));
+
+		assert_eq!(to_decimal(&i(-42)), "\"-42\"");
+		for value in [I256::MIN, I256::MAX, i(-1), I256::ZERO].iter() {
+			assert_eq!(from_decimal(&to_decimal(value)).unwrap(), *value);
+		}
+		assert!(from_decimal("\"0x1\"").is_err());
+	}
+
+	#[cfg(feature = "rlp")]
+	#[test]
+	fn rlp() {
+		use impl_rlp::rlp;
+
+		assert_eq!(rlp::encode(&i(1)).to_vec(), vec![0x01]);
+		assert_eq!(rlp::encode(&i(-1)).len(), 33);
+		for value in [I256::MIN, I256::MAX, i(-1), I256::ZERO].iter() {
+			assert_eq!(rlp::decode::<I256>(&rlp::encode(value)), Ok(*value));
+		}
+	}
+
+	#[cfg(feature = "codec")]
+	#[test]
+	fn codec() {
+		use impl_codec::codec::{Decode, Encode};
+
+		let encoded = i(-2).encode();
+		assert_eq!(encoded[0], 0xfe);
+		assert_eq!(encoded[1..], [0xff; 31][..]);
+		for value in [I256::MIN, I256::MAX, i(-1), I256::ZERO].iter() {
+			assert_eq!(I256::decode(&mut &value.encode()[..]), Ok(*value));
+		}
+	}
+}
@@ -8,12 +8,33 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "checksum")]
+extern crate alloc;
+
+mod bloom;
+#[cfg(feature = "checksum")]
+mod checksum;
 mod hash;
+mod int;
+#[cfg(feature = "keccak")]
+mod public_key;
 mod uint;
 
+pub use bloom::logs_bloom;
+#[cfg(feature = "checksum")]
+pub use checksum::{AddressChecksum, ChecksumError, Checksummed};
 pub use ethbloom::{Bloom, BloomRef, Input as BloomInput};
 pub use hash::{BigEndianHash, H128, H160, H256, H264, H32, H512, H520, H64};
-pub use uint::{FromDecStrErr, FromStrRadixErr, FromStrRadixErrKind, U128, U256, U512, U64};
+#[cfg(feature = "bls-hashes")]
+pub use hash::{H384, H768};
+#[cfg(feature = "serialize")]
+pub use int::serde_decimal as i256_serde_decimal;
+pub use int::I256;
+#[cfg(feature = "keccak")]
+pub use public_key::FromPublicKey;
+pub use uint::{
+	FromDecStrErr, FromStrRadixErr, FromStrRadixErrKind, LossyFrom, LossyInto, TryFromUintError, U128, U256, U512, U64,
+};
 
 pub type Address = H160;
 pub type Secret = H256;
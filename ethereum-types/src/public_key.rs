@@ -0,0 +1,78 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{H160, H512};
+use keccak_hash::keccak;
+
+/// Derivation of an address from an uncompressed secp256k1 public key.
+///
+/// The address is the last 20 bytes of the Keccak-256 hash of the 64-byte key,
+/// which is the concatenation of the big-endian `x` and `y` coordinates without
+/// the `0x04` SEC1 tag.
+///
+/// ```
+/// use ethereum_types::{FromPublicKey, H160, H512};
+///
+/// // The public key for the secret key `1`, i.e. the secp256k1 generator point.
+/// let public: H512 = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+/// 483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8".parse().unwrap();
+/// let address: H160 = "7e5f4552091a69125d5dfcb7b8c2659029395bdf".parse().unwrap();
+/// assert_eq!(H160::from_public_key(&public), address);
+/// ```
+pub trait FromPublicKey {
+	/// Derives the address of a public key.
+	fn from_public_key(public: &H512) -> Self;
+
+	/// Derives the address of a public key given as its 64 raw bytes.
+	fn from_uncompressed_slice(public: &[u8; 64]) -> Self;
+}
+
+impl FromPublicKey for H160 {
+	fn from_public_key(public: &H512) -> Self {
+		Self::from_uncompressed_slice(public.as_fixed_bytes())
+	}
+
+	fn from_uncompressed_slice(public: &[u8; 64]) -> Self {
+		H160::from_slice(&keccak(&public[..])[12..])
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn known_addresses() {
+		// Public keys of the secret keys 1, 2 and 3, and the example key from the web3.js documentation.
+		let vectors = [
+			(
+				"79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+				"7e5f4552091a69125d5dfcb7b8c2659029395bdf",
+			),
+			(
+				"c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee51ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
+				"2b5ad5c4795c026514f8317c7a215e218dccd6cf",
+			),
+			(
+				"f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9388f7b0f632de8140fe337e62a37f3566500a99934c2231b6cb9fd7584b8e672",
+				"6813eb9362372eef6200f3b1dbc3f819671cba69",
+			),
+			(
+				"4e3b81af9c2234cad09d679ce6035ed1392347ce64ce405f5dcd36228a25de6e47fd35c4215d1edf53e6f83de344615ce719bdb0fd878f6ed76f06dd277956de",
+				"2c7536e3605d9c16a7a3d7b1898e529396a65c23",
+			),
+		];
+
+		for (public, address) in vectors.iter() {
+			let public: H512 = public.parse().unwrap();
+			let address: H160 = address.parse().unwrap();
+			assert_eq!(H160::from_public_key(&public), address);
+			assert_eq!(H160::from_uncompressed_slice(public.as_fixed_bytes()), address);
+		}
+	}
+}
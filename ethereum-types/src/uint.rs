@@ -27,11 +27,16 @@ impl_uint_serde!(U64, 1);
 #[cfg(feature = "codec")]
 impl_uint_codec!(U64, 1);
 
-pub use primitive_types::{U128, U256, U512};
+pub use primitive_types::{LossyFrom, LossyInto, TryFromUintError, U128, U256, U512};
+
+primitive_types::impl_uint_conversions!(U64, 1, U128, 2);
+primitive_types::impl_uint_conversions!(U64, 1, U256, 4);
+primitive_types::impl_uint_conversions!(U64, 1, U512, 8);
 
 #[cfg(test)]
 mod tests {
-	use super::{U256, U512};
+	use super::{LossyInto, TryFromUintError, U128, U256, U512, U64};
+	use core::convert::TryFrom;
 	use serde_json as ser;
 	use std::u64::MAX;
 
@@ -195,4 +200,22 @@ mod tests {
 		let result = U256([1, 2, 3, 4]).full_mul(U256([5, 6, 7, 8]));
 		assert_eq!(U512([5, 16, 34, 60, 61, 52, 32, 0]), result);
 	}
+
+	#[test]
+	fn u64_conversions() {
+		let max = U64::max_value();
+		assert_eq!(U128::from(max), U128::from(MAX));
+		assert_eq!(U256::from(max), U256::from(MAX));
+		assert_eq!(U512::from(&max), U512::from(MAX));
+
+		assert_eq!(U64::try_from(U128::from(MAX)), Ok(max));
+		assert_eq!(U64::try_from(U256::zero()), Ok(U64::zero()));
+		assert_eq!(U64::try_from(&U512::from(7)), Ok(U64::from(7)));
+		assert_eq!(U64::try_from(U128::from(MAX) + 1), Err(TryFromUintError { required_bits: 65 }));
+		assert_eq!(U64::try_from(U512::MAX), Err(TryFromUintError { required_bits: 512 }));
+
+		assert_eq!(LossyInto::<U64>::overflowing_into(U256::from(MAX) + 2), (U64::one(), true));
+		assert_eq!(LossyInto::<U64>::saturating_into(U256::MAX), max);
+		assert_eq!(LossyInto::<U256>::overflowing_into(max), (U256::from(MAX), false));
+	}
 }
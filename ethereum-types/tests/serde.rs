@@ -114,3 +114,34 @@ fn test_invalid_char() {
 	const EXPECTED_MSG: &str = "invalid hex character: g, at 65 at line 1 column 68";
 	assert_eq!(ser::from_str::<H256>(INVALID_STR).unwrap_err().to_string(), EXPECTED_MSG);
 }
+
+#[test]
+fn test_hash_without_prefix() {
+	let hash = H160::from_low_u64_be(1_000);
+	assert_eq!(hash, ser::from_str("\"00000000000000000000000000000000000003e8\"").unwrap());
+	assert_eq!(ser::to_string(&hash).unwrap(), "\"0x00000000000000000000000000000000000003e8\"");
+}
+
+#[test]
+fn test_invalid_length() {
+	const INVALID_STR: &str = "\"0x00000000000000000000000000000000000003\"";
+	const EXPECTED_MSG: &str = "invalid length: expected 20 bytes, got 19 bytes at line 1 column 42";
+	assert_eq!(ser::from_str::<H160>(INVALID_STR).unwrap_err().to_string(), EXPECTED_MSG);
+}
+
+#[cfg(feature = "bls-hashes")]
+#[test]
+fn test_bls_hashes() {
+	use ethereum_types::{H384, H768};
+
+	let hash = H384::from_low_u64_be(0xabcd);
+	let expected = format!("\"0x{:0>96}\"", "abcd");
+	assert_eq!(ser::to_string(&hash).unwrap(), expected);
+	assert_eq!(hash, ser::from_str(&expected).unwrap());
+
+	let hash = H768::repeat_byte(0x5a);
+	let expected = format!("\"0x{}\"", "5a".repeat(96));
+	assert_eq!(ser::to_string(&hash).unwrap(), expected);
+	assert_eq!(hash, ser::from_str(&expected).unwrap());
+	assert!(ser::from_str::<H768>(&format!("\"0x{}\"", "5a".repeat(95))).unwrap_err().is_data());
+}
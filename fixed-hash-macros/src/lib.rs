@@ -0,0 +1,121 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hash literals for types created by `fixed_hash::construct_fixed_hash!`,
+//! checked at compile time.
+//!
+//! The literal is a string of hex digits, optionally `0x`-prefixed, with exactly
+//! two digits per byte of the hash. The macros expand to a call of the `const`
+//! `from_array` constructor, so they can be used to define constants:
+//!
+//! ```ignore
+//! const EMPTY_ROOT: H256 = h256!("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421");
+//! const SYSTEM: H160 = fixed_hash!(Address, "fffffffffffffffffffffffffffffffffffffffe");
+//! ```
+//!
+//! `h160!` and `h256!` expand to `H160` and `H256` respectively, which must be in scope.
+
+#![warn(clippy::all, clippy::pedantic, clippy::nursery)]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote_spanned;
+use syn::{
+	parse::{Parse, ParseStream},
+	parse_macro_input, LitStr, Path, Token,
+};
+
+/// `H160` literal, e.g. `h160!("0xfffffffffffffffffffffffffffffffffffffffe")`.
+#[proc_macro]
+pub fn h160(input: TokenStream) -> TokenStream {
+	let lit = parse_macro_input!(input as LitStr);
+	let path = syn::parse_quote!(H160);
+	expand(&path, Some(20), &lit).into()
+}
+
+/// `H256` literal, e.g. `h256!("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421")`.
+#[proc_macro]
+pub fn h256(input: TokenStream) -> TokenStream {
+	let lit = parse_macro_input!(input as LitStr);
+	let path = syn::parse_quote!(H256);
+	expand(&path, Some(32), &lit).into()
+}
+
+/// Literal of any fixed hash type, e.g. `fixed_hash!(H512, "0x…")`.
+///
+/// For types named `H` followed by their number of bits, such as `ethereum_types::H520`,
+/// the length of the literal is checked against the name. For other types, a literal
+/// of the wrong length fails to type check.
+#[proc_macro]
+pub fn fixed_hash(input: TokenStream) -> TokenStream {
+	let FixedHashInput { path, lit } = parse_macro_input!(input as FixedHashInput);
+	let len = path.segments.last().and_then(|segment| len_from_name(&segment.ident.to_string()));
+	expand(&path, len, &lit).into()
+}
+
+struct FixedHashInput {
+	path: Path,
+	lit: LitStr,
+}
+
+impl Parse for FixedHashInput {
+	fn parse(input: ParseStream) -> syn::Result<Self> {
+		let path = input.parse()?;
+		input.parse::<Token![,]>()?;
+		let lit = input.parse()?;
+		// Allow a trailing comma.
+		let _ = input.parse::<Option<Token![,]>>()?;
+		Ok(Self { path, lit })
+	}
+}
+
+/// Number of bytes of a type named like `H256`, if the name has this form.
+fn len_from_name(name: &str) -> Option<usize> {
+	let bits: usize = name.strip_prefix('H')?.parse().ok()?;
+	if bits == 0 || bits % 8 != 0 {
+		return None;
+	}
+	Some(bits / 8)
+}
+
+fn expand(path: &Path, len: Option<usize>, lit: &LitStr) -> proc_macro2::TokenStream {
+	match parse_hex(&lit.value(), len) {
+		Ok(bytes) => {
+			let span = lit.span();
+			quote_spanned! {span=> #path::from_array([#(#bytes),*]) }
+		}
+		Err(message) => syn::Error::new(lit.span(), message).to_compile_error(),
+	}
+}
+
+fn parse_hex(s: &str, len: Option<usize>) -> Result<Vec<u8>, String> {
+	let (digits, offset) = s.strip_prefix("0x").map_or((s, 0), |digits| (digits, 2));
+
+	if let Some((index, character)) = digits.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+		return Err(format!("invalid hex character {:?} at position {}", character, offset + index));
+	}
+	if let Some(len) = len {
+		if digits.len() != 2 * len {
+			return Err(format!("expected {} bytes ({} hex digits), got {} hex digits", len, 2 * len, digits.len()));
+		}
+	}
+	if digits.len() % 2 != 0 {
+		return Err(format!("odd number of hex digits: {}", digits.len()));
+	}
+
+	let bytes = digits
+		.as_bytes()
+		.chunks(2)
+		.map(|pair| {
+			let pair = std::str::from_utf8(pair).expect("checked to be ASCII hex digits; qed");
+			u8::from_str_radix(pair, 16).expect("checked to be hex digits; qed")
+		})
+		.collect();
+	Ok(bytes)
+}
@@ -0,0 +1,65 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use fixed_hash::construct_fixed_hash;
+use fixed_hash_macros::{fixed_hash, h160, h256};
+
+construct_fixed_hash! { pub struct H160(20); }
+construct_fixed_hash! { pub struct H256(32); }
+construct_fixed_hash! { pub struct H512(64); }
+construct_fixed_hash! { pub struct Digest(4); }
+
+const EMPTY_ROOT: H256 = h256!("0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421");
+const SYSTEM: H160 = h160!("fffffffffffffffffffffffffffffffffffffffe");
+const DIGEST: Digest = fixed_hash!(Digest, "0xDEADbeef");
+
+#[test]
+fn const_literals() {
+	assert_eq!(EMPTY_ROOT.as_bytes()[0], 0x56);
+	assert_eq!(EMPTY_ROOT.as_bytes()[31], 0x21);
+	let mut system = [0xff; 20];
+	system[19] = 0xfe;
+	assert_eq!(SYSTEM, H160(system));
+	assert_eq!(DIGEST, Digest([0xde, 0xad, 0xbe, 0xef]));
+	assert_eq!(h160!("0x0000000000000000000000000000000000000001"), H160::from_low_u64_be(1));
+}
+
+#[test]
+fn generic_form() {
+	assert_eq!(fixed_hash!(H256, "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"), EMPTY_ROOT);
+	assert_eq!(
+		fixed_hash!(
+			H512,
+			"0x0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000002a",
+		),
+		H512::from_low_u64_be(42)
+	);
+}
+
+fn classify(hash: H256) -> &'static str {
+	match hash {
+		EMPTY_ROOT => "empty",
+		H256::ZERO => "zero",
+		hash if hash == h256!("0x0000000000000000000000000000000000000000000000000000000000000001") => "one",
+		_ => "other",
+	}
+}
+
+#[test]
+fn match_on_consts() {
+	assert_eq!(classify(EMPTY_ROOT), "empty");
+	assert_eq!(classify(H256::zero()), "zero");
+	assert_eq!(classify(H256::from_low_u64_be(1)), "one");
+	assert_eq!(classify(H256::from_low_u64_be(2)), "other");
+}
+
+#[test]
+fn compile_errors() {
+	let t = trybuild::TestCases::new();
+	t.compile_fail("tests/ui/*.rs");
+}
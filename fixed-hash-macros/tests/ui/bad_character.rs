@@ -0,0 +1,8 @@
+use fixed_hash::construct_fixed_hash;
+use fixed_hash_macros::h160;
+
+construct_fixed_hash! { pub struct H160(20); }
+
+const HASH: H160 = h160!("0x000000000000000000000000000000000000000g");
+
+fn main() {}
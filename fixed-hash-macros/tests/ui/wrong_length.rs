@@ -0,0 +1,8 @@
+use fixed_hash::construct_fixed_hash;
+use fixed_hash_macros::h256;
+
+construct_fixed_hash! { pub struct H256(32); }
+
+const HASH: H256 = h256!("0x1234");
+
+fn main() {}
@@ -0,0 +1,8 @@
+use fixed_hash::construct_fixed_hash;
+use fixed_hash_macros::fixed_hash;
+
+construct_fixed_hash! { pub struct H160(20); }
+
+const HASH: H160 = fixed_hash!(H160, "0x00000000000000000000000000000000000001");
+
+fn main() {}
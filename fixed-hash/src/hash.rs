@@ -42,6 +42,7 @@
 macro_rules! construct_fixed_hash {
 	( $(#[$attr:meta])* $visibility:vis struct $name:ident ( $n_bytes:expr ); ) => {
 		#[repr(C)]
+		#[derive(PartialEq, Eq)]
 		$(#[$attr])*
 		$visibility struct $name (pub [u8; $n_bytes]);
 
@@ -104,7 +105,59 @@ macro_rules! construct_fixed_hash {
 			}
 		}
 
+		impl<'a> From<&'a $name> for [u8; $n_bytes] {
+			#[inline]
+			fn from(s: &'a $name) -> Self {
+				s.0
+			}
+		}
+
+		impl AsRef<[u8; $n_bytes]> for $name {
+			#[inline]
+			fn as_ref(&self) -> &[u8; $n_bytes] {
+				&self.0
+			}
+		}
+
+		impl AsMut<[u8; $n_bytes]> for $name {
+			#[inline]
+			fn as_mut(&mut self) -> &mut [u8; $n_bytes] {
+				&mut self.0
+			}
+		}
+
+		impl<'a> $crate::core_::convert::TryFrom<&'a [u8]> for $name {
+			type Error = $crate::core_::array::TryFromSliceError;
+
+			/// Constructs a hash type from the given bytes slice,
+			/// failing if its length is not the length of the hash.
+			///
+			/// # Note
+			///
+			/// The given bytes are interpreted in big endian order.
+			#[inline]
+			fn try_from(bytes: &'a [u8]) -> Result<Self, Self::Error> {
+				<[u8; $n_bytes] as $crate::core_::convert::TryFrom<&'a [u8]>>::try_from(bytes).map($name)
+			}
+		}
+
 		impl $name {
+			/// The size of this hash in bytes.
+			pub const LEN: usize = $n_bytes;
+
+			/// The zero-initialized fixed hash.
+			pub const ZERO: $name = $name([0u8; $n_bytes]);
+
+			/// Constructs a hash type from the given bytes array of fixed length.
+			///
+			/// # Note
+			///
+			/// The given bytes are interpreted in big endian order.
+			#[inline]
+			pub const fn from_array(bytes: [u8; $n_bytes]) -> $name {
+				$name(bytes)
+			}
+
 			/// Returns a new fixed hash where all bits are set to the given byte.
 			#[inline]
 			pub const fn repeat_byte(byte: u8) -> $name {
@@ -123,6 +176,97 @@ macro_rules! construct_fixed_hash {
 				$n_bytes
 			}
 
+			const fn from_low_u64_bytes(buf: [u8; 8]) -> Self {
+				let capped = if $n_bytes < 8 { $n_bytes } else { 8 };
+				let mut bytes = [0u8; $n_bytes];
+				let mut i = 0;
+				while i < capped {
+					bytes[$n_bytes - capped + i] = buf[i];
+					i += 1;
+				}
+				$name(bytes)
+			}
+
+			const fn to_low_u64_bytes(&self) -> [u8; 8] {
+				let capped = if $n_bytes < 8 { $n_bytes } else { 8 };
+				let mut buf = [0u8; 8];
+				let mut i = 0;
+				while i < capped {
+					buf[8 - capped + i] = self.0[$n_bytes - capped + i];
+					i += 1;
+				}
+				buf
+			}
+
+			/// Returns the lowest 8 bytes interpreted as big-endian.
+			///
+			/// # Note
+			///
+			/// For hash type with less than 8 bytes the missing bytes
+			/// are interpreted as being zero.
+			#[inline]
+			pub const fn to_low_u64_be(&self) -> u64 {
+				u64::from_be_bytes(self.to_low_u64_bytes())
+			}
+
+			/// Returns the lowest 8 bytes interpreted as little-endian.
+			///
+			/// # Note
+			///
+			/// For hash type with less than 8 bytes the missing bytes
+			/// are interpreted as being zero.
+			#[inline]
+			pub const fn to_low_u64_le(&self) -> u64 {
+				u64::from_le_bytes(self.to_low_u64_bytes())
+			}
+
+			/// Returns the lowest 8 bytes interpreted as native-endian.
+			///
+			/// # Note
+			///
+			/// For hash type with less than 8 bytes the missing bytes
+			/// are interpreted as being zero.
+			#[inline]
+			pub const fn to_low_u64_ne(&self) -> u64 {
+				u64::from_ne_bytes(self.to_low_u64_bytes())
+			}
+
+			/// Creates a new hash type from the given `u64` value.
+			///
+			/// # Note
+			///
+			/// - The given `u64` value is interpreted as big endian.
+			/// - Ignores the most significant bits of the given value
+			///   if the hash type has less than 8 bytes.
+			#[inline]
+			pub const fn from_low_u64_be(val: u64) -> Self {
+				Self::from_low_u64_bytes(val.to_be_bytes())
+			}
+
+			/// Creates a new hash type from the given `u64` value.
+			///
+			/// # Note
+			///
+			/// - The given `u64` value is interpreted as little endian.
+			/// - Ignores the most significant bits of the given value
+			///   if the hash type has less than 8 bytes.
+			#[inline]
+			pub const fn from_low_u64_le(val: u64) -> Self {
+				Self::from_low_u64_bytes(val.to_le_bytes())
+			}
+
+			/// Creates a new hash type from the given `u64` value.
+			///
+			/// # Note
+			///
+			/// - The given `u64` value is interpreted as native endian.
+			/// - Ignores the most significant bits of the given value
+			///   if the hash type has less than 8 bytes.
+			#[inline]
+			pub const fn from_low_u64_ne(val: u64) -> Self {
+				Self::from_low_u64_bytes(val.to_ne_bytes())
+			}
+
 			/// Extracts a byte slice containing the entire fixed hash.
 			#[inline]
 			pub fn as_bytes(&self) -> &[u8] {
@@ -195,6 +339,113 @@ macro_rules! construct_fixed_hash {
 				ret
 			}
 
+			/// Creates a hash from the bytes yielded by `iter`, failing if it yields more or
+			/// fewer bytes than the length of the hash.
+			///
+			/// # Note
+			///
+			/// The given bytes are interpreted in big endian order.
+			#[allow(clippy::result_unit_err)]
+			pub fn try_from_iter<I>(iter: I) -> $crate::core_::result::Result<Self, ()>
+			where
+				I: $crate::core_::iter::IntoIterator<Item = u8>,
+			{
+				let mut iter = iter.into_iter();
+				let mut ret = Self::zero();
+				for byte in ret.as_bytes_mut() {
+					*byte = iter.next().ok_or(())?;
+				}
+				match iter.next() {
+					Some(_) => Err(()),
+					None => Ok(ret),
+				}
+			}
+
+			/// Creates a hash from a hex string, tolerating common formatting variations.
+			///
+			/// Unlike `from_str`, this accepts input which
+			///
+			/// - contains ASCII whitespace or `_` between the digits,
+			/// - has an optional `0x` or `0X` prefix,
+			/// - has fewer digits than the hash, or an odd number of digits; the value is
+			///   then left-padded with zeros.
+			///
+			/// # Note
+			///
+			/// The given input string is interpreted in big endian.
+			///
+			/// # Errors
+			///
+			/// - Upon input without any hex digits
+			/// - Upon more hex digits than fit in the hash
+			/// - When encountering any other character, reporting its byte position
+			pub fn from_str_lenient(input: &str) -> $crate::core_::result::Result<Self, $crate::FromHexLenientError> {
+				let mut result = Self::zero();
+				$crate::decode_lenient(input, result.as_bytes_mut())?;
+				Ok(result)
+			}
+
+			/// Creates a hash from ASCII hex digits, e.g. from a network buffer, without
+			/// going through `str`.
+			///
+			/// The input must have exactly two hex digits per byte, with an optional `0x` prefix.
+			///
+			/// # Note
+			///
+			/// The given input is interpreted in big endian.
+			pub fn from_hex_bytes(input: &[u8]) -> $crate::core_::result::Result<Self, $crate::FromHexBytesError> {
+				let mut result = Self::zero();
+				$crate::decode_hex_bytes(input, result.as_bytes_mut())?;
+				Ok(result)
+			}
+
+			/// Writes the lowercase hex digits of the hash to `buf` without allocating,
+			/// returning them as a string slice.
+			pub fn hex_encode_into<'a>(&self, buf: &'a mut [u8; $n_bytes * 2]) -> &'a str {
+				$crate::encode_hex(&self.0, &mut buf[..])
+			}
+
+			/// Writes the `0x`-prefixed lowercase hex digits of the hash to `buf` without
+			/// allocating, returning them as a string slice.
+			pub fn hex_encode_prefixed_into<'a>(&self, buf: &'a mut [u8; $n_bytes * 2 + 2]) -> &'a str {
+				buf[0] = b'0';
+				buf[1] = b'x';
+				$crate::encode_hex(&self.0, &mut buf[2..]);
+				// `0x` followed by hex digits is guaranteed to be a valid UTF8 string
+				unsafe { $crate::core_::str::from_utf8_unchecked(&buf[..]) }
+			}
+
+			/// Creates a hash from the least significant bytes of `src`, truncating or zero-extending.
+			///
+			/// # Note
+			///
+			/// The given bytes are interpreted in big endian order and aligned at the end:
+			/// when `src` is longer than `Self` its leading bytes are dropped, when it is
+			/// shorter the leading bytes of the result are zero. This matches the `From`
+			/// conversions of `impl_fixed_hash_conversions!`, e.g. the low 20 bytes of an
+			/// `H256` are kept when converting it to an `H160`.
+			pub fn from_low_bytes(src: &[u8]) -> Self {
+				let len = $crate::core_::cmp::min(src.len(), $n_bytes);
+				let mut ret = Self::zero();
+				ret.as_bytes_mut()[($n_bytes - len)..].copy_from_slice(&src[(src.len() - len)..]);
+				ret
+			}
+
+			/// Creates a hash from the most significant bytes of `src`, truncating or zero-extending.
+			///
+			/// # Note
+			///
+			/// The given bytes are interpreted in big endian order and aligned at the start:
+			/// when `src` is longer than `Self` its trailing bytes are dropped, when it is
+			/// shorter the trailing bytes of the result are zero. For example the high 20
+			/// bytes of an `H256` are kept when converting it to an `H160`.
+			pub fn from_high_bytes(src: &[u8]) -> Self {
+				let len = $crate::core_::cmp::min(src.len(), $n_bytes);
+				let mut ret = Self::zero();
+				ret.as_bytes_mut()[..len].copy_from_slice(&src[..len]);
+				ret
+			}
+
 			/// Returns `true` if all bits set in `b` are also set in `self`.
 			#[inline]
 			pub fn covers(&self, b: &Self) -> bool {
@@ -203,58 +454,220 @@ macro_rules! construct_fixed_hash {
 
 			/// Returns `true` if no bits are set.
 			#[inline]
-			pub fn is_zero(&self) -> bool {
-				self.as_bytes().iter().all(|&byte| byte == 0u8)
+			pub const fn is_zero(&self) -> bool {
+				let mut i = 0;
+				while i < $n_bytes {
+					if self.0[i] != 0 {
+						return false;
+					}
+					i += 1;
+				}
+				true
+			}
+
+			/// Returns whether the bit at the given index is set.
+			///
+			/// # Note
+			///
+			/// Bits are numbered as in the big endian number represented by the hash:
+			/// bit `0` is the least significant bit of the last byte, and the highest
+			/// index is the most significant bit of the first byte.
+			///
+			/// # Panics
+			///
+			/// If `index` is not less than the number of bits in `self`.
+			#[inline]
+			pub fn bit(&self, index: usize) -> bool {
+				$crate::core_::assert!(index < $n_bytes * 8, "bit index out of range");
+				self.0[$n_bytes - 1 - index / 8] & (1 << (index % 8)) != 0
+			}
+
+			/// Sets or clears the bit at the given index, numbered as in `bit`.
+			///
+			/// # Panics
+			///
+			/// If `index` is not less than the number of bits in `self`.
+			#[inline]
+			pub fn set_bit(&mut self, index: usize, value: bool) {
+				$crate::core_::assert!(index < $n_bytes * 8, "bit index out of range");
+				let byte = &mut self.0[$n_bytes - 1 - index / 8];
+				if value {
+					*byte |= 1 << (index % 8);
+				} else {
+					*byte &= !(1 << (index % 8));
+				}
+			}
+
+			/// Returns the number of set bits.
+			#[inline]
+			pub fn count_ones(&self) -> u32 {
+				self.0.iter().map(|byte| byte.count_ones()).sum()
+			}
+
+			/// Returns the number of leading zero bits, starting at the most significant bit of the first byte.
+			pub fn leading_zeros(&self) -> u32 {
+				match self.0.iter().position(|&byte| byte != 0) {
+					Some(index) => index as u32 * 8 + self.0[index].leading_zeros(),
+					None => Self::len_bytes() as u32 * 8,
+				}
+			}
+
+			/// Returns the number of trailing zero bits, starting at the least significant bit of the last byte.
+			pub fn trailing_zeros(&self) -> u32 {
+				match self.0.iter().rposition(|&byte| byte != 0) {
+					Some(index) => ($n_bytes - 1 - index) as u32 * 8 + self.0[index].trailing_zeros(),
+					None => Self::len_bytes() as u32 * 8,
+				}
+			}
+
+			/// Returns an iterator over the indices of the set bits in ascending order, numbered as in `bit`.
+			#[inline]
+			pub fn iter_ones(&self) -> impl $crate::core_::iter::Iterator<Item = usize> + '_ {
+				(0..$n_bytes * 8).filter(move |&index| self.bit(index))
+			}
+
+			/// Returns the XOR distance between `self` and `other`, the metric used by Kademlia.
+			///
+			/// Distances compare as big endian numbers, like hashes do.
+			#[inline]
+			pub fn xor_distance(&self, other: &Self) -> Self {
+				let mut ret = Self::zero();
+				for ((r, a), b) in ret.0.iter_mut().zip(self.0.iter()).zip(other.0.iter()) {
+					*r = a ^ b;
+				}
+				ret
+			}
+
+			/// Returns the base 2 logarithm of the XOR distance between `self` and `other`,
+			/// rounded down, or `None` if they are equal.
+			///
+			/// This is the index of the highest differing bit, numbered as in `bit`, and
+			/// hence the index of the Kademlia bucket `other` falls into as seen from `self`.
+			#[inline]
+			pub fn log2_distance(&self, other: &Self) -> Option<u32> {
+				let leading_zeros = self.xor_distance(other).leading_zeros();
+				(Self::len_bytes() as u32 * 8).checked_sub(leading_zeros + 1)
+			}
+
+			/// Compares the XOR distances of `a` and `b` to `self`.
+			///
+			/// Returns `Less` if `a` is closer to `self` than `b`, and `Greater` if it is
+			/// further away. As the XOR distance to a fixed point is unique per hash, the
+			/// distances are only equal, and `Equal` is returned, if `a == b`.
+			pub fn cmp_distance(&self, a: &Self, b: &Self) -> $crate::core_::cmp::Ordering {
+				for ((s, a), b) in self.0.iter().zip(a.0.iter()).zip(b.0.iter()) {
+					let (da, db) = (s ^ a, s ^ b);
+					if da != db {
+						return da.cmp(&db);
+					}
+				}
+				$crate::core_::cmp::Ordering::Equal
+			}
+
+			/// Returns the 4-bit value at the given nibble index.
+			///
+			/// # Note
+			///
+			/// Nibbles are indexed in big endian order: index `0` is the high
+			/// nibble of the first byte, index `1` its low nibble and so on.
+			///
+			/// # Panics
+			///
+			/// If `index` is not less than twice the number of bytes in `self`.
+			#[inline]
+			pub fn nibble_at(&self, index: usize) -> u8 {
+				let byte = self.0[index / 2];
+				if index % 2 == 0 {
+					byte >> 4
+				} else {
+					byte & 0x0f
+				}
+			}
+
+			/// Returns an iterator over the 4-bit values of the hash in big endian order,
+			/// see `nibble_at`.
+			#[inline]
+			pub fn nibbles(
+				&self,
+			) -> impl $crate::core_::iter::DoubleEndedIterator<Item = u8> + $crate::core_::iter::ExactSizeIterator + '_ {
+				(0..$n_bytes * 2).map(move |index| self.nibble_at(index))
+			}
+
+			/// Returns the number of leading nibbles which `self` and `other` have in common.
+			pub fn common_prefix_nibbles(&self, other: &Self) -> usize {
+				match self.0.iter().zip(other.0.iter()).position(|(a, b)| a != b) {
+					Some(index) if self.0[index] >> 4 == other.0[index] >> 4 => index * 2 + 1,
+					Some(index) => index * 2,
+					None => $n_bytes * 2,
+				}
+			}
+
+			/// Increments the big endian number represented by the hash by one.
+			///
+			/// Returns `true` if it wrapped around, i.e. if all bytes were `0xff` and are now zero.
+			/// Hashes compare as big endian numbers, so this steps to the next hash in order.
+			#[inline]
+			pub fn increment(&mut self) -> bool {
+				for byte in self.0.iter_mut().rev() {
+					let (incremented, overflow) = byte.overflowing_add(1);
+					*byte = incremented;
+					if !overflow {
+						return false;
+					}
+				}
+				true
 			}
 		}
 
 		impl $crate::core_::fmt::Debug for $name {
 			fn fmt(&self, f: &mut $crate::core_::fmt::Formatter) -> $crate::core_::fmt::Result {
-				$crate::core_::write!(f, "{:#x}", self)
+				let mut buf = [0_u8; $n_bytes * 2 + 2];
+				f.pad(self.hex_encode_prefixed_into(&mut buf))
 			}
 		}
 
 		impl $crate::core_::fmt::Display for $name {
 			fn fmt(&self, f: &mut $crate::core_::fmt::Formatter) -> $crate::core_::fmt::Result {
-				$crate::core_::write!(f, "0x")?;
-				for i in &self.0[0..2] {
-					$crate::core_::write!(f, "{:02x}", i)?;
-				}
-				$crate::core_::write!(f, "…")?;
-				for i in &self.0[$n_bytes - 2..$n_bytes] {
-					$crate::core_::write!(f, "{:02x}", i)?;
+				// `0x`, two leading bytes, the three byte UTF8 ellipsis and two trailing bytes
+				let mut buf = [0_u8; 13];
+				buf[..2].copy_from_slice(b"0x");
+				$crate::encode_hex(&self.0[0..2], &mut buf[2..6]);
+				buf[6..9].copy_from_slice("…".as_bytes());
+				$crate::encode_hex(&self.0[$n_bytes - 2..$n_bytes], &mut buf[9..13]);
+				// hex digits and the ellipsis are guaranteed to be a valid UTF8 string
+				let s = unsafe { $crate::core_::str::from_utf8_unchecked(&buf) };
+				f.pad(s)
+			}
+		}
+
+		impl $name {
+			/// Writes all hex digits, honoring the formatter's width, fill and `#` flag.
+			fn fmt_hex(&self, f: &mut $crate::core_::fmt::Formatter, digits: &[u8; 16]) -> $crate::core_::fmt::Result {
+				let mut buf = [0_u8; $n_bytes * 2];
+				for (i, byte) in self.0.iter().enumerate() {
+					buf[2 * i] = digits[(byte >> 4) as usize];
+					buf[2 * i + 1] = digits[(byte & 0xf) as usize];
 				}
-				Ok(())
+				// sequence of hex digits is guaranteed to be a valid UTF8 string
+				let s = unsafe { $crate::core_::str::from_utf8_unchecked(&buf) };
+				f.pad_integral(true, "0x", s)
 			}
 		}
 
 		impl $crate::core_::fmt::LowerHex for $name {
 			fn fmt(&self, f: &mut $crate::core_::fmt::Formatter) -> $crate::core_::fmt::Result {
-				if f.alternate() {
-					$crate::core_::write!(f, "0x")?;
-				}
-				for i in &self.0[..] {
-					$crate::core_::write!(f, "{:02x}", i)?;
-				}
-				Ok(())
+				self.fmt_hex(f, b"0123456789abcdef")
 			}
 		}
 
 		impl $crate::core_::fmt::UpperHex for $name {
 			fn fmt(&self, f: &mut $crate::core_::fmt::Formatter) -> $crate::core_::fmt::Result {
-				if f.alternate() {
-					$crate::core_::write!(f, "0X")?;
-				}
-				for i in &self.0[..] {
-					$crate::core_::write!(f, "{:02X}", i)?;
-				}
-				Ok(())
+				self.fmt_hex(f, b"0123456789ABCDEF")
 			}
 		}
 
 		impl $crate::core_::marker::Copy for $name {}
 
-		#[cfg_attr(feature = "dev", allow(expl_impl_clone_on_copy))]
 		impl $crate::core_::clone::Clone for $name {
 			fn clone(&self) -> $name {
 				let mut ret = $name::zero();
@@ -263,8 +676,6 @@ macro_rules! construct_fixed_hash {
 			}
 		}
 
-		impl $crate::core_::cmp::Eq for $name {}
-
 		impl $crate::core_::cmp::PartialOrd for $name {
 			fn partial_cmp(&self, other: &Self) -> Option<$crate::core_::cmp::Ordering> {
 				Some(self.cmp(other))
@@ -311,148 +722,16 @@ macro_rules! construct_fixed_hash {
 		impl_ops_for_hash!($name, BitAnd, bitand, BitAndAssign, bitand_assign, &, &=);
 		impl_ops_for_hash!($name, BitXor, bitxor, BitXorAssign, bitxor_assign, ^, ^=);
 
-		impl_byteorder_for_fixed_hash!($name);
 		impl_rand_for_fixed_hash!($name);
 		impl_cmp_for_fixed_hash!($name);
 		impl_rustc_hex_for_fixed_hash!($name);
 		impl_quickcheck_for_fixed_hash!($name);
 		impl_arbitrary_for_fixed_hash!($name);
+		impl_zeroize_for_fixed_hash!($name);
+		impl_subtle_for_fixed_hash!($name);
 	}
 }
 
-// Implementation for disabled byteorder crate support.
-//
-// # Note
-//
-// Feature guarded macro definitions instead of feature guarded impl blocks
-// to work around the problems of introducing `byteorder` crate feature in
-// a user crate.
-#[cfg(not(feature = "byteorder"))]
-#[macro_export]
-#[doc(hidden)]
-macro_rules! impl_byteorder_for_fixed_hash {
-	( $name:ident ) => {};
-}
-
-// Implementation for enabled byteorder crate support.
-//
-// # Note
-//
-// Feature guarded macro definitions instead of feature guarded impl blocks
-// to work around the problems of introducing `byteorder` crate feature in
-// a user crate.
-#[cfg(feature = "byteorder")]
-#[macro_export]
-#[doc(hidden)]
-macro_rules! impl_byteorder_for_fixed_hash {
-	( $name:ident ) => {
-		/// Utilities using the `byteorder` crate.
-		impl $name {
-			/// Returns the least significant `n` bytes as slice.
-			///
-			/// # Panics
-			///
-			/// If `n` is greater than the number of bytes in `self`.
-			#[inline]
-			fn least_significant_bytes(&self, n: usize) -> &[u8] {
-				$crate::core_::assert_eq!(true, n <= Self::len_bytes());
-				&self[(Self::len_bytes() - n)..]
-			}
-
-			fn to_low_u64_with_byteorder<B>(&self) -> u64
-			where
-				B: $crate::byteorder::ByteOrder,
-			{
-				let mut buf = [0x0; 8];
-				let capped = $crate::core_::cmp::min(Self::len_bytes(), 8);
-				buf[(8 - capped)..].copy_from_slice(self.least_significant_bytes(capped));
-				B::read_u64(&buf)
-			}
-
-			/// Returns the lowest 8 bytes interpreted as big-endian.
-			///
-			/// # Note
-			///
-			/// For hash type with less than 8 bytes the missing bytes
-			/// are interpreted as being zero.
-			#[inline]
-			pub fn to_low_u64_be(&self) -> u64 {
-				self.to_low_u64_with_byteorder::<$crate::byteorder::BigEndian>()
-			}
-
-			/// Returns the lowest 8 bytes interpreted as little-endian.
-			///
-			/// # Note
-			///
-			/// For hash type with less than 8 bytes the missing bytes
-			/// are interpreted as being zero.
-			#[inline]
-			pub fn to_low_u64_le(&self) -> u64 {
-				self.to_low_u64_with_byteorder::<$crate::byteorder::LittleEndian>()
-			}
-
-			/// Returns the lowest 8 bytes interpreted as native-endian.
-			///
-			/// # Note
-			///
-			/// For hash type with less than 8 bytes the missing bytes
-			/// are interpreted as being zero.
-			#[inline]
-			pub fn to_low_u64_ne(&self) -> u64 {
-				self.to_low_u64_with_byteorder::<$crate::byteorder::NativeEndian>()
-			}
-
-			fn from_low_u64_with_byteorder<B>(val: u64) -> Self
-			where
-				B: $crate::byteorder::ByteOrder,
-			{
-				let mut buf = [0x0; 8];
-				B::write_u64(&mut buf, val);
-				let capped = $crate::core_::cmp::min(Self::len_bytes(), 8);
-				let mut bytes = [0x0; $crate::core_::mem::size_of::<Self>()];
-				bytes[(Self::len_bytes() - capped)..].copy_from_slice(&buf[..capped]);
-				Self::from_slice(&bytes)
-			}
-
-			/// Creates a new hash type from the given `u64` value.
-			///
-			/// # Note
-			///
-			/// - The given `u64` value is interpreted as big endian.
-			/// - Ignores the most significant bits of the given value
-			///   if the hash type has less than 8 bytes.
-			#[inline]
-			pub fn from_low_u64_be(val: u64) -> Self {
-				Self::from_low_u64_with_byteorder::<$crate::byteorder::BigEndian>(val)
-			}
-
-			/// Creates a new hash type from the given `u64` value.
-			///
-			/// # Note
-			///
-			/// - The given `u64` value is interpreted as little endian.
-			/// - Ignores the most significant bits of the given value
-			///   if the hash type has less than 8 bytes.
-			#[inline]
-			pub fn from_low_u64_le(val: u64) -> Self {
-				Self::from_low_u64_with_byteorder::<$crate::byteorder::LittleEndian>(val)
-			}
-
-			/// Creates a new hash type from the given `u64` value.
-			///
-			/// # Note
-			///
-			/// - The given `u64` value is interpreted as native endian.
-			/// - Ignores the most significant bits of the given value
-			///   if the hash type has less than 8 bytes.
-			#[inline]
-			pub fn from_low_u64_ne(val: u64) -> Self {
-				Self::from_low_u64_with_byteorder::<$crate::byteorder::NativeEndian>(val)
-			}
-		}
-	};
-}
-
 // Implementation for disabled rand crate support.
 //
 // # Note
@@ -524,6 +803,49 @@ macro_rules! impl_rand_for_fixed_hash {
 				hash.randomize();
 				hash
 			}
+
+			/// Randomize all bits of `self` except the leading `keep_bits` bits, using the given
+			/// random number generator.
+			///
+			/// # Note
+			///
+			/// Leading bits are counted from the most significant bit of the first byte, so
+			/// the kept bits form a prefix of the big endian representation.
+			///
+			/// # Panics
+			///
+			/// If `keep_bits` exceeds the number of bits in `self`.
+			pub fn randomize_suffix<R>(&mut self, rng: &mut R, keep_bits: u32)
+			where
+				R: $crate::rand::Rng + ?Sized,
+			{
+				let keep_bits = keep_bits as usize;
+				$crate::core_::assert!(keep_bits <= Self::len_bytes() * 8, "prefix exceeds the hash size");
+				let first = keep_bits / 8;
+				for (index, byte) in self.as_bytes_mut().iter_mut().enumerate().skip(first) {
+					let random: u8 = rng.gen();
+					// Keep the high bits of the partially kept byte.
+					let mask = if index == first { 0xffu8 >> (keep_bits % 8) } else { 0xff };
+					*byte = (*byte & !mask) | (random & mask);
+				}
+			}
+
+			/// Create a new hash whose leading `prefix_bits` bits are those of `prefix` and
+			/// whose remaining bits are random, using the given random number generator.
+			///
+			/// See `randomize_suffix` for how the prefix bits are counted.
+			///
+			/// # Panics
+			///
+			/// If `prefix_bits` exceeds the number of bits in `Self`.
+			pub fn random_with_prefix<R>(rng: &mut R, prefix: &Self, prefix_bits: u32) -> Self
+			where
+				R: $crate::rand::Rng + ?Sized,
+			{
+				let mut ret = *prefix;
+				ret.randomize_suffix(rng, prefix_bits);
+				ret
+			}
 		}
 	};
 }
@@ -532,13 +854,6 @@ macro_rules! impl_rand_for_fixed_hash {
 #[doc(hidden)]
 macro_rules! impl_cmp_for_fixed_hash {
 	( $name:ident ) => {
-		impl $crate::core_::cmp::PartialEq for $name {
-			#[inline]
-			fn eq(&self, other: &Self) -> bool {
-				self.as_bytes() == other.as_bytes()
-			}
-		}
-
 		impl $crate::core_::cmp::Ord for $name {
 			#[inline]
 			fn cmp(&self, other: &Self) -> $crate::core_::cmp::Ordering {
@@ -591,7 +906,7 @@ macro_rules! impl_rustc_hex_for_fixed_hash {
 				let input = input.strip_prefix("0x").unwrap_or(input);
 				let mut iter = $crate::rustc_hex::FromHexIter::new(input);
 				let mut result = Self::zero();
-				for byte in result.as_mut() {
+				for byte in result.as_bytes_mut() {
 					*byte = iter.next().ok_or(Self::Err::InvalidHexLength)??;
 				}
 				if iter.next().is_some() {
@@ -671,6 +986,94 @@ macro_rules! impl_arbitrary_for_fixed_hash {
 				u.fill_buffer(&mut res.0)?;
 				Ok(Self::from(res))
 			}
+
+			#[inline]
+			fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+				(Self::len_bytes(), Some(Self::len_bytes()))
+			}
+		}
+	};
+}
+
+// When the `zeroize` feature is disabled.
+//
+// # Note
+//
+// Feature guarded macro definitions instead of feature guarded impl blocks
+// to work around the problems of introducing `zeroize` crate feature in
+// a user crate.
+#[cfg(not(feature = "zeroize"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_zeroize_for_fixed_hash {
+	( $name:ident ) => {};
+}
+
+// When the `zeroize` feature is enabled.
+//
+// # Note
+//
+// Feature guarded macro definitions instead of feature guarded impl blocks
+// to work around the problems of introducing `zeroize` crate feature in
+// a user crate.
+#[cfg(feature = "zeroize")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_zeroize_for_fixed_hash {
+	( $name:ident ) => {
+		impl $crate::zeroize::Zeroize for $name {
+			/// Overwrites the hash with zeros, using volatile writes which are not optimized away.
+			///
+			/// Hashes are `Copy`, so copies made before calling this are not wiped.
+			/// Wrap secrets in `zeroize::Zeroizing` to wipe them on drop.
+			#[inline]
+			fn zeroize(&mut self) {
+				$crate::zeroize::Zeroize::zeroize(&mut self.0[..]);
+			}
+		}
+	};
+}
+
+// When the `subtle` feature is disabled.
+//
+// # Note
+//
+// Feature guarded macro definitions instead of feature guarded impl blocks
+// to work around the problems of introducing `subtle` crate feature in
+// a user crate.
+#[cfg(not(feature = "subtle"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_subtle_for_fixed_hash {
+	( $name:ident ) => {};
+}
+
+// When the `subtle` feature is enabled.
+//
+// # Note
+//
+// Feature guarded macro definitions instead of feature guarded impl blocks
+// to work around the problems of introducing `subtle` crate feature in
+// a user crate.
+#[cfg(feature = "subtle")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_subtle_for_fixed_hash {
+	( $name:ident ) => {
+		impl $crate::subtle::ConstantTimeEq for $name {
+			#[inline]
+			fn ct_eq(&self, other: &Self) -> $crate::subtle::Choice {
+				$crate::subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..])
+			}
+		}
+
+		impl $name {
+			/// Compares two hashes in time independent of their contents,
+			/// unlike `==` which returns at the first differing byte.
+			#[inline]
+			pub fn constant_time_eq(&self, other: &Self) -> bool {
+				$crate::subtle::ConstantTimeEq::ct_eq(self, other).into()
+			}
 		}
 	};
 }
@@ -784,3 +1187,49 @@ macro_rules! impl_fixed_hash_conversions {
 		}
 	};
 }
+
+/// Implements splitting a hash into two halves and joining it back.
+///
+/// # Note
+///
+/// - Type `full_ty` must be exactly twice the size of `half_ty`.
+/// - Halves are in big endian order: the first half holds the most significant
+///   (leading) bytes and the second half the least significant (trailing) bytes.
+///
+/// # Example
+///
+/// ```
+/// use fixed_hash::{construct_fixed_hash, impl_fixed_hash_halves};
+/// construct_fixed_hash!{ struct H256(32); }
+/// construct_fixed_hash!{ struct H512(64); }
+/// impl_fixed_hash_halves!(H512, H256);
+/// // now use it!
+/// let (high, low) = H512::repeat_byte(1).split();
+/// assert_eq!(high, H256::repeat_byte(1));
+/// assert_eq!(H512::from_halves(high, low), H512::repeat_byte(1));
+/// ```
+#[macro_export(local_inner_macros)]
+macro_rules! impl_fixed_hash_halves {
+	($full_ty:ident, $half_ty:ident) => {
+		$crate::static_assertions::const_assert!(
+			$crate::core_::mem::size_of::<$full_ty>() == 2 * $crate::core_::mem::size_of::<$half_ty>()
+		);
+
+		impl $full_ty {
+			/// Splits the hash into its most significant (leading) and least significant (trailing) halves.
+			pub fn split(self) -> ($half_ty, $half_ty) {
+				let (high, low) = self.as_bytes().split_at($half_ty::len_bytes());
+				($half_ty::from_slice(high), $half_ty::from_slice(low))
+			}
+
+			/// Joins the most significant (leading) and least significant (trailing) halves of a hash.
+			pub fn from_halves(high: $half_ty, low: $half_ty) -> Self {
+				let mut ret = Self::zero();
+				let (ret_high, ret_low) = ret.as_bytes_mut().split_at_mut($half_ty::len_bytes());
+				ret_high.copy_from_slice(high.as_bytes());
+				ret_low.copy_from_slice(low.as_bytes());
+				ret
+			}
+		}
+	};
+}
@@ -0,0 +1,156 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::fmt;
+
+/// Errors of the lenient hex parsing of fixed hashes, see `from_str_lenient`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromHexLenientError {
+	/// The input contains no hex digits.
+	Empty,
+	/// The input contains more hex digits than fit in the hash.
+	TooLong {
+		/// Number of hex digits in the input.
+		digits: usize,
+		/// Maximum number of hex digits of the hash.
+		max: usize,
+	},
+	/// The input contains a character which is neither a hex digit nor ignored.
+	InvalidCharacter {
+		/// The invalid character.
+		character: char,
+		/// Byte position of the character in the input.
+		index: usize,
+	},
+}
+
+impl fmt::Display for FromHexLenientError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			FromHexLenientError::Empty => write!(f, "no hex digits in input"),
+			FromHexLenientError::TooLong { digits, max } => {
+				write!(f, "too many hex digits: {} (at most {} allowed)", digits, max)
+			}
+			FromHexLenientError::InvalidCharacter { character, index } => {
+				write!(f, "invalid character {:?} at position {}", character, index)
+			}
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FromHexLenientError {}
+
+/// Errors of decoding fixed hashes from ASCII hex bytes, see `from_hex_bytes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromHexBytesError {
+	/// The input does not have exactly two hex digits per byte of the hash.
+	InvalidLength {
+		/// Number of hex digits in the input.
+		digits: usize,
+		/// Number of hex digits of the hash.
+		expected: usize,
+	},
+	/// The input contains a byte which is not an ASCII hex digit.
+	InvalidCharacter {
+		/// The invalid byte.
+		byte: u8,
+		/// Position of the byte in the input.
+		index: usize,
+	},
+}
+
+impl fmt::Display for FromHexBytesError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			FromHexBytesError::InvalidLength { digits, expected } => {
+				write!(f, "invalid number of hex digits: {} (expected {})", digits, expected)
+			}
+			FromHexBytesError::InvalidCharacter { byte, index } => {
+				write!(f, "invalid hex character {:?} at position {}", byte as char, index)
+			}
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FromHexBytesError {}
+
+/// Decodes `input` into `out` as described by `from_str_lenient`.
+///
+/// `out` must be zero-initialized; its leading bytes are left untouched when the input is short.
+#[doc(hidden)]
+pub fn decode_lenient(input: &str, out: &mut [u8]) -> Result<(), FromHexLenientError> {
+	let start = input.len() - input.trim_start_matches(|c: char| c.is_ascii_whitespace()).len();
+	let start = match input.as_bytes().get(start..start + 2) {
+		Some(b"0x") | Some(b"0X") => start + 2,
+		_ => start,
+	};
+
+	let mut digits = 0;
+	for (index, character) in input[start..].char_indices() {
+		match character {
+			'0'..='9' | 'a'..='f' | 'A'..='F' => digits += 1,
+			'_' => {}
+			c if c.is_ascii_whitespace() => {}
+			character => return Err(FromHexLenientError::InvalidCharacter { character, index: start + index }),
+		}
+	}
+	let max = out.len() * 2;
+	if digits == 0 {
+		return Err(FromHexLenientError::Empty);
+	}
+	if digits > max {
+		return Err(FromHexLenientError::TooLong { digits, max });
+	}
+
+	let nibbles = input[start..].bytes().rev().filter_map(|c| (c as char).to_digit(16));
+	for (position, nibble) in nibbles.enumerate() {
+		out[out.len() - 1 - position / 2] |= (nibble as u8) << (4 * (position % 2));
+	}
+	Ok(())
+}
+
+/// Writes the lowercase hex digits of `bytes` to `out`, which must be twice as long.
+///
+/// # Panics
+///
+/// Panics if `out` is not exactly twice as long as `bytes`.
+#[doc(hidden)]
+pub fn encode_hex<'a>(bytes: &[u8], out: &'a mut [u8]) -> &'a str {
+	const DIGITS: &[u8; 16] = b"0123456789abcdef";
+	assert_eq!(out.len(), bytes.len() * 2, "hex output buffer must be twice the input length");
+	for (byte, pair) in bytes.iter().zip(out.chunks_exact_mut(2)) {
+		pair[0] = DIGITS[(byte >> 4) as usize];
+		pair[1] = DIGITS[(byte & 0xf) as usize];
+	}
+	// sequence of hex digits is guaranteed to be a valid UTF8 string
+	unsafe { core::str::from_utf8_unchecked(out) }
+}
+
+/// Decodes `input` into `out` as described by `from_hex_bytes`.
+#[doc(hidden)]
+pub fn decode_hex_bytes(input: &[u8], out: &mut [u8]) -> Result<(), FromHexBytesError> {
+	let start = if input.starts_with(b"0x") { 2 } else { 0 };
+	let digits = &input[start..];
+	if digits.len() != out.len() * 2 {
+		return Err(FromHexBytesError::InvalidLength { digits: digits.len(), expected: out.len() * 2 });
+	}
+
+	let nibble = |index: usize| {
+		let byte = digits[index];
+		(byte as char)
+			.to_digit(16)
+			.map(|nibble| nibble as u8)
+			.ok_or(FromHexBytesError::InvalidCharacter { byte, index: start + index })
+	};
+	for (i, byte) in out.iter_mut().enumerate() {
+		*byte = (nibble(2 * i)? << 4) | nibble(2 * i + 1)?;
+	}
+	Ok(())
+}
@@ -30,10 +30,6 @@ pub use static_assertions;
 #[doc(hidden)]
 pub use static_assertions::const_assert;
 
-#[cfg(feature = "byteorder")]
-#[doc(hidden)]
-pub use byteorder;
-
 #[cfg(feature = "rustc-hex")]
 #[doc(hidden)]
 pub use rustc_hex;
@@ -50,8 +46,24 @@ pub use quickcheck;
 #[doc(hidden)]
 pub use arbitrary;
 
+#[cfg(feature = "zeroize")]
+#[doc(hidden)]
+pub use zeroize;
+
+#[cfg(feature = "subtle")]
+#[doc(hidden)]
+pub use subtle;
+
+#[cfg(feature = "macros")]
+pub use fixed_hash_macros::{fixed_hash, h160, h256};
+
 #[macro_use]
 mod hash;
+mod hex;
+
+#[doc(hidden)]
+pub use hex::{decode_hex_bytes, decode_lenient, encode_hex};
+pub use hex::{FromHexBytesError, FromHexLenientError};
 
 #[cfg(test)]
 mod tests;
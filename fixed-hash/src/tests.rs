@@ -13,6 +13,8 @@ construct_fixed_hash! { pub struct H160(20); }
 construct_fixed_hash! { pub struct H256(32); }
 
 impl_fixed_hash_conversions!(H256, H160);
+impl_fixed_hash_halves!(H256, H128);
+impl_fixed_hash_halves!(H64, H32);
 
 mod repeat_byte {
 	use super::*;
@@ -28,6 +30,18 @@ mod repeat_byte {
 		assert_eq!(H32::repeat_byte(0x0), H32::zero());
 		assert_eq!(H32::repeat_byte(0x0), H32::from([0x0; 4]));
 	}
+
+	#[test]
+	fn const_construction() {
+		const ZERO: H256 = H256::zero();
+		const ONES: H256 = H256::repeat_byte(0xff);
+		const IS_ZERO: [bool; 2] = [ZERO.is_zero(), ONES.is_zero()];
+
+		assert_eq!(IS_ZERO, [true, false]);
+		assert_eq!(ONES, H256::from([0xff; 32]));
+		assert!(!H256::from_low_u64_be(1).is_zero());
+		assert!(!H256::from_low_u64_be(1 << 63).is_zero());
+	}
 }
 
 #[test]
@@ -45,6 +59,92 @@ fn as_bytes() {
 	assert_eq!(H32::from([0x42; 4]).as_bytes_mut(), &mut [0x42; 4]);
 }
 
+mod hex_bytes {
+	use super::*;
+	use crate::FromHexBytesError;
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn encode_matches_format() {
+		for hash in [H256::zero(), H256::repeat_byte(0xff), H256::from_low_u64_be(0x0123_4567_89ab_cdef)].iter() {
+			let mut buf = [0u8; 64];
+			assert_eq!(hash.hex_encode_into(&mut buf), format!("{:x}", hash));
+			let mut buf = [0u8; 66];
+			assert_eq!(hash.hex_encode_prefixed_into(&mut buf), format!("{:#x}", hash));
+		}
+	}
+
+	#[test]
+	fn roundtrip() {
+		let hash = H32::from([0xde, 0xad, 0xbe, 0xef]);
+		let mut buf = [0u8; 8];
+		assert_eq!(hash.hex_encode_into(&mut buf), "deadbeef");
+		assert_eq!(H32::from_hex_bytes(&buf), Ok(hash));
+		let mut buf = [0u8; 10];
+		assert_eq!(hash.hex_encode_prefixed_into(&mut buf), "0xdeadbeef");
+		assert_eq!(H32::from_hex_bytes(&buf), Ok(hash));
+		assert_eq!(H32::from_hex_bytes(b"DEADBEEF"), Ok(hash));
+	}
+
+	#[test]
+	fn invalid_input() {
+		assert_eq!(H32::from_hex_bytes(b"0xdeadbee"), Err(FromHexBytesError::InvalidLength { digits: 7, expected: 8 }));
+		assert_eq!(H32::from_hex_bytes(b""), Err(FromHexBytesError::InvalidLength { digits: 0, expected: 8 }));
+		assert_eq!(
+			H32::from_hex_bytes(b"0xdeadbexf"),
+			Err(FromHexBytesError::InvalidCharacter { byte: b'x', index: 8 })
+		);
+		assert_eq!(
+			H32::from_hex_bytes(b"dead\xffeef"),
+			Err(FromHexBytesError::InvalidCharacter { byte: 0xff, index: 4 })
+		);
+	}
+}
+
+mod reference_conversions {
+	use super::*;
+	use core::convert::TryFrom;
+
+	#[test]
+	fn arrays() {
+		let mut hash = H32::from(&[0x42; 4]);
+		assert_eq!(AsRef::<[u8; 4]>::as_ref(&hash), &[0x42; 4]);
+		AsMut::<[u8; 4]>::as_mut(&mut hash)[0] = 0x10;
+		assert_eq!(<[u8; 4]>::from(&hash), [0x10, 0x42, 0x42, 0x42]);
+	}
+
+	#[test]
+	fn try_from_slice() {
+		let bytes = [0x42; 33];
+		assert_eq!(H256::try_from(&bytes[..32]).ok(), Some(H256::repeat_byte(0x42)));
+		assert!(H256::try_from(&bytes[..31]).is_err());
+		assert!(H256::try_from(&bytes[..]).is_err());
+		assert!(H256::try_from(&[0u8; 0][..]).is_err());
+	}
+
+	#[test]
+	fn try_from_iter() {
+		assert_eq!(H32::try_from_iter(1..=4), Ok(H32::from([1, 2, 3, 4])));
+		assert_eq!(H256::try_from_iter([0x42; 32].iter().copied()), Ok(H256::repeat_byte(0x42)));
+		assert_eq!(H32::try_from_iter(1..=3), Err(()));
+		assert_eq!(H32::try_from_iter(1..=5), Err(()));
+		assert_eq!(H32::try_from_iter(core::iter::empty()), Err(()));
+		// Only one byte past the length of the hash is taken from the iterator.
+		assert_eq!(H32::try_from_iter(0..), Err(()));
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn array_keyed_map_lookup() {
+		let mut map = std::collections::HashMap::<[u8; 32], &str>::new();
+		map.insert([0xab; 32], "found");
+
+		let hash = H256::repeat_byte(0xab);
+		assert_eq!(map.get(AsRef::<[u8; 32]>::as_ref(&hash)), Some(&"found"));
+		assert_eq!(map.get(H256::zero().as_fixed_bytes()), None);
+	}
+}
+
 mod assign_from_slice {
 	use super::*;
 
@@ -155,7 +255,6 @@ mod is_zero {
 	}
 }
 
-#[cfg(feature = "byteorder")]
 mod to_low_u64 {
 	use super::*;
 
@@ -195,7 +294,6 @@ mod to_low_u64 {
 	}
 }
 
-#[cfg(feature = "byteorder")]
 mod from_low_u64 {
 	use super::*;
 
@@ -247,6 +345,47 @@ mod rand {
 		let mut rng = StdRng::seed_from_u64(123);
 		assert_eq!(H32::random_using(&mut rng), H32::from([0xeb, 0x96, 0xaf, 0x1c]));
 	}
+
+	fn has_prefix(hash: &H256, prefix: &H256, bits: u32) -> bool {
+		(0..bits as usize).all(|i| hash.bit(255 - i) == prefix.bit(255 - i))
+	}
+
+	#[test]
+	fn random_with_prefix() {
+		let mut rng = StdRng::seed_from_u64(123);
+		let prefix = H256::repeat_byte(0xA5);
+		for &bits in [0u32, 1, 7, 8, 13, 31, 200, 255, 256].iter() {
+			let hash = H256::random_with_prefix(&mut rng, &prefix, bits);
+			assert!(has_prefix(&hash, &prefix, bits), "prefix of {} bits", bits);
+		}
+		assert_eq!(H256::random_with_prefix(&mut rng, &prefix, 256), prefix);
+	}
+
+	#[test]
+	fn randomize_suffix_keeps_awkward_prefix() {
+		let mut rng = StdRng::seed_from_u64(42);
+		let prefix = H256::repeat_byte(0xFF);
+		let mut suffix_ones = [0u32; 256];
+		for _ in 0..64 {
+			let mut hash = prefix;
+			hash.randomize_suffix(&mut rng, 13);
+			assert_eq!(&hash[..1], &[0xFF]);
+			assert_eq!(hash[1] & 0xF8, 0xF8);
+			for (i, ones) in suffix_ones.iter_mut().enumerate() {
+				*ones += hash.bit(i) as u32;
+			}
+		}
+		// Every bit after the prefix is randomized, the prefix is always kept.
+		assert!(suffix_ones[..243].iter().all(|&ones| ones > 0 && ones < 64));
+		assert!(suffix_ones[243..].iter().all(|&ones| ones == 64));
+	}
+
+	#[test]
+	#[should_panic(expected = "prefix exceeds the hash size")]
+	fn randomize_suffix_too_long_prefix() {
+		let mut rng = StdRng::seed_from_u64(123);
+		H32::zero().randomize_suffix(&mut rng, 33);
+	}
 }
 
 #[cfg(feature = "rustc-hex")]
@@ -282,6 +421,413 @@ mod from_str {
 	}
 }
 
+mod low_u64_round_trip {
+	use super::*;
+
+	const VALUES: [u64; 4] = [0, 1, 0x0123_4567_89AB_CDEF, u64::max_value()];
+
+	#[test]
+	fn big_endian() {
+		for &value in VALUES.iter() {
+			assert_eq!(H256::from_low_u64_be(value).to_low_u64_be(), value);
+			assert_eq!(H64::from_low_u64_be(value).to_low_u64_be(), value);
+		}
+	}
+
+	#[test]
+	fn little_endian() {
+		for &value in VALUES.iter() {
+			assert_eq!(H256::from_low_u64_le(value).to_low_u64_le(), value);
+			assert_eq!(H64::from_low_u64_le(value).to_low_u64_le(), value);
+		}
+	}
+
+	#[test]
+	fn high_bytes_are_zero() {
+		for &value in VALUES.iter() {
+			assert!(H256::from_low_u64_be(value)[..24].iter().all(|&byte| byte == 0));
+			assert!(H256::from_low_u64_le(value)[..24].iter().all(|&byte| byte == 0));
+		}
+		assert_eq!(&H256::from_low_u64_be(0x0102)[24..], &[0, 0, 0, 0, 0, 0, 0x01, 0x02]);
+		assert_eq!(&H256::from_low_u64_le(0x0102)[24..], &[0x02, 0x01, 0, 0, 0, 0, 0, 0]);
+	}
+}
+
+mod const_constructors {
+	use super::*;
+
+	const GENESIS: H32 = H32::from_array([0xDE, 0xAD, 0xBE, 0xEF]);
+	const ONE_BE: H64 = H64::from_low_u64_be(1);
+	const ONE_LE: H64 = H64::from_low_u64_le(1);
+	const FILLED: H128 = H128::repeat_byte(0xAB);
+	const EMPTY: H256 = H256::zero();
+	const SIZE: [u8; H160::LEN] = [0u8; H160::LEN];
+
+	static TABLE: [H32; 4] = [H32::ZERO, H32::repeat_byte(0xFF), H32::from_low_u64_le(0x0403_0201), GENESIS];
+
+	#[test]
+	fn const_items() {
+		assert_eq!(GENESIS, H32::from([0xDE, 0xAD, 0xBE, 0xEF]));
+		assert_eq!(ONE_BE, H64::from([0, 0, 0, 0, 0, 0, 0, 1]));
+		assert_eq!(ONE_LE, H64::from([1, 0, 0, 0, 0, 0, 0, 0]));
+		assert_eq!(FILLED.as_bytes(), &[0xAB; 16]);
+		assert!(EMPTY.is_zero());
+		assert_eq!(SIZE.len(), H160::len_bytes());
+		assert_eq!(H256::ZERO, H256::zero());
+	}
+
+	#[test]
+	fn static_table() {
+		assert!(TABLE[0].is_zero());
+		assert_eq!(TABLE[1], H32::from([0xFF; 4]));
+		assert_eq!(TABLE[2], H32::from([0x01, 0x02, 0x03, 0x04]));
+		assert_eq!(TABLE[3], GENESIS);
+	}
+
+	#[test]
+	fn match_on_associated_consts() {
+		fn name(hash: H32) -> &'static str {
+			match hash {
+				H32::ZERO => "zero",
+				GENESIS => "genesis",
+				_ => "other",
+			}
+		}
+		assert_eq!(name(TABLE[0]), "zero");
+		assert_eq!(name(TABLE[3]), "genesis");
+		assert_eq!(name(TABLE[1]), "other");
+	}
+}
+
+mod bits {
+	use super::*;
+
+	#[test]
+	fn numbering_convention() {
+		let mut hash = H32::zero();
+		hash.set_bit(0, true);
+		assert_eq!(hash, H32::from([0, 0, 0, 0x01]));
+		hash.set_bit(31, true);
+		assert_eq!(hash, H32::from([0x80, 0, 0, 0x01]));
+		assert!(H32::from([0, 0, 0, 0b1010]).iter_ones().eq([1, 3].iter().copied()));
+		// Consistent with the bitwise operators.
+		assert_eq!(H32::from([0, 0, 0x02, 0]) | H32::from([0, 0, 0, 0x01]), {
+			let mut h = H32::zero();
+			h.set_bit(9, true);
+			h.set_bit(0, true);
+			h
+		});
+	}
+
+	#[test]
+	fn set_and_get_across_bytes() {
+		for index in 0..64 {
+			let mut hash = H64::zero();
+			hash.set_bit(index, true);
+			assert_eq!(hash, H64::from_low_u64_be(1 << index));
+			assert!(hash.bit(index));
+			assert_eq!(hash.count_ones(), 1);
+			assert!(hash.iter_ones().eq(Some(index)));
+			hash.set_bit(index, false);
+			assert!(hash.is_zero());
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "bit index out of range")]
+	fn bit_out_of_range() {
+		H32::zero().bit(32);
+	}
+
+	#[test]
+	fn count_ones() {
+		assert_eq!(H256::zero().count_ones(), 0);
+		assert_eq!(H256::repeat_byte(0xFF).count_ones(), 256);
+		assert_eq!(H32::from([0x01, 0x80, 0x0F, 0x00]).count_ones(), 6);
+	}
+
+	#[test]
+	fn leading_and_trailing_zeros() {
+		assert_eq!(H32::zero().leading_zeros(), 32);
+		assert_eq!(H32::zero().trailing_zeros(), 32);
+		assert_eq!(H32::from([0x00, 0x10, 0x04, 0x00]).leading_zeros(), 11);
+		assert_eq!(H32::from([0x00, 0x10, 0x04, 0x00]).trailing_zeros(), 10);
+		assert_eq!(H256::repeat_byte(0x80).leading_zeros(), 0);
+		assert_eq!(H256::repeat_byte(0x80).trailing_zeros(), 7);
+		for index in 0..64 {
+			let hash = H64::from_low_u64_be(1 << index);
+			assert_eq!(hash.trailing_zeros(), index as u32);
+			assert_eq!(hash.leading_zeros(), 63 - index as u32);
+		}
+	}
+}
+
+mod xor_distance {
+	use super::*;
+	use crate::core_::cmp::Ordering;
+
+	#[test]
+	fn distance() {
+		let a = H32::from([0x12, 0x34, 0x56, 0x78]);
+		let b = H32::from([0x12, 0x30, 0x56, 0x79]);
+		assert_eq!(a.xor_distance(&b), H32::from([0x00, 0x04, 0x00, 0x01]));
+		assert_eq!(a.xor_distance(&b), a ^ b);
+		assert_eq!(a.xor_distance(&a), H32::zero());
+		assert_eq!(a.xor_distance(&b), b.xor_distance(&a));
+	}
+
+	#[test]
+	fn log2_distance() {
+		let zero = H256::zero();
+		assert_eq!(zero.log2_distance(&zero), None);
+		assert_eq!(zero.log2_distance(&H256::from_low_u64_be(1)), Some(0));
+		assert_eq!(zero.log2_distance(&H256::from_low_u64_be(2)), Some(1));
+		assert_eq!(zero.log2_distance(&H256::from_low_u64_be(3)), Some(1));
+		assert_eq!(zero.log2_distance(&H256::from_low_u64_be(0xFF)), Some(7));
+		assert_eq!(zero.log2_distance(&H256::from_low_u64_be(0x100)), Some(8));
+		assert_eq!(zero.log2_distance(&H256::repeat_byte(0xFF)), Some(255));
+
+		// The bucket is given by the highest differing bit, regardless of the common prefix.
+		let node = H32::from([0xA0, 0x00, 0x00, 0x00]);
+		assert_eq!(node.log2_distance(&H32::from([0x20, 0x00, 0x00, 0x00])), Some(31));
+		assert_eq!(node.log2_distance(&H32::from([0xA0, 0x00, 0x80, 0x00])), Some(15));
+		assert_eq!(node.log2_distance(&H32::from([0xA0, 0x00, 0x00, 0x01])), Some(0));
+	}
+
+	#[test]
+	fn cmp_distance() {
+		let node = H32::from([0xA0, 0x00, 0x00, 0x00]);
+		let near = H32::from([0xA0, 0x00, 0x00, 0x01]);
+		let far = H32::from([0x20, 0x00, 0x00, 0x00]);
+		assert_eq!(node.cmp_distance(&near, &far), Ordering::Less);
+		assert_eq!(node.cmp_distance(&far, &near), Ordering::Greater);
+		assert_eq!(node.cmp_distance(&near, &near), Ordering::Equal);
+		assert_eq!(node.cmp_distance(&node, &near), Ordering::Less);
+		// Not the numeric order of the hashes: `far` is numerically smaller than `node`.
+		assert!(far < near);
+	}
+
+	#[test]
+	fn cmp_distance_is_antisymmetric() {
+		let hashes = [
+			H32::zero(),
+			H32::repeat_byte(0xFF),
+			H32::from([0xA0, 0x00, 0x00, 0x01]),
+			H32::from([0x20, 0x00, 0x00, 0x00]),
+			H32::from([0x12, 0x34, 0x56, 0x78]),
+			H32::from([0x12, 0x34, 0x56, 0x79]),
+		];
+		for target in hashes.iter() {
+			for a in hashes.iter() {
+				for b in hashes.iter() {
+					let ordering = target.cmp_distance(a, b);
+					assert_eq!(ordering, target.cmp_distance(b, a).reverse());
+					assert_eq!(ordering, target.xor_distance(a).cmp(&target.xor_distance(b)));
+					assert_eq!(ordering == Ordering::Equal, a == b);
+				}
+			}
+		}
+	}
+}
+
+mod nibbles {
+	use super::*;
+
+	fn sample() -> H32 {
+		H32::from([0x12, 0x34, 0xAB, 0xCD])
+	}
+
+	#[test]
+	fn first_and_last() {
+		let hash = sample();
+		assert_eq!(hash.nibble_at(0), 0x1);
+		assert_eq!(hash.nibble_at(1), 0x2);
+		assert_eq!(hash.nibble_at(7), 0xD);
+		assert_eq!(hash.nibbles().next(), Some(0x1));
+		assert_eq!(hash.nibbles().last(), Some(0xD));
+	}
+
+	#[test]
+	#[should_panic]
+	fn out_of_range() {
+		sample().nibble_at(8);
+	}
+
+	#[test]
+	fn iteration() {
+		let hash = sample();
+		assert_eq!(hash.nibbles().len(), 8);
+		assert!(hash.nibbles().eq([0x1, 0x2, 0x3, 0x4, 0xA, 0xB, 0xC, 0xD].iter().copied()));
+		assert!(hash.nibbles().rev().eq([0xD, 0xC, 0xB, 0xA, 0x4, 0x3, 0x2, 0x1].iter().copied()));
+		assert!(hash.nibbles().enumerate().all(|(i, nibble)| nibble == hash.nibble_at(i)));
+		assert_eq!(H256::zero().nibbles().len(), 64);
+	}
+
+	fn naive_common_prefix(a: &H32, b: &H32) -> usize {
+		a.nibbles().zip(b.nibbles()).take_while(|(x, y)| x == y).count()
+	}
+
+	#[test]
+	fn common_prefix() {
+		let hash = sample();
+		assert_eq!(hash.common_prefix_nibbles(&hash), 8);
+		assert_eq!(hash.common_prefix_nibbles(&H32::from([0x12, 0x34, 0xAB, 0xCE])), 7);
+		assert_eq!(hash.common_prefix_nibbles(&H32::from([0x12, 0x35, 0xAB, 0xCD])), 3);
+		assert_eq!(hash.common_prefix_nibbles(&H32::from([0x22, 0x34, 0xAB, 0xCD])), 0);
+
+		for i in 0..32 {
+			for j in 0..32 {
+				let a = H32::from(0xDEAD_BEEF_u32.rotate_left(i).to_be_bytes());
+				let b = H32::from((0xDEAD_BEEF_u32.rotate_left(i) ^ (1 << j)).to_be_bytes());
+				assert_eq!(a.common_prefix_nibbles(&b), naive_common_prefix(&a, &b));
+				assert_eq!(a.common_prefix_nibbles(&b), (31 - j as usize) / 4);
+			}
+		}
+	}
+}
+
+mod increment {
+	use super::*;
+
+	#[test]
+	fn without_carry() {
+		let mut hash = H32::from([0x12, 0x34, 0x56, 0xfe]);
+		assert!(!hash.increment());
+		assert_eq!(hash, H32::from([0x12, 0x34, 0x56, 0xff]));
+	}
+
+	#[test]
+	fn with_carry() {
+		let mut hash = H32::from([0x12, 0x34, 0xff, 0xff]);
+		assert!(!hash.increment());
+		assert_eq!(hash, H32::from([0x12, 0x35, 0x00, 0x00]));
+	}
+
+	#[test]
+	fn wraps_around() {
+		let mut hash = H256::repeat_byte(0xff);
+		assert!(hash.increment());
+		assert!(hash.is_zero());
+	}
+
+	#[test]
+	fn matches_integer_increment() {
+		for &value in &[0u32, 1, 0xff, 0x1234_ffff, 0x00ff_ffff, 0xdead_beef] {
+			let mut hash = H32::from(value.to_be_bytes());
+			let previous = hash;
+			assert!(!hash.increment());
+			assert_eq!(hash, H32::from((value + 1).to_be_bytes()));
+			assert!(previous < hash);
+		}
+	}
+}
+
+mod from_str_lenient {
+	use super::*;
+	use crate::FromHexLenientError;
+
+	#[test]
+	fn optional_prefix() {
+		let expected = H32::from([0x01, 0x23, 0xab, 0xcd]);
+		assert_eq!(H32::from_str_lenient("0123abcd"), Ok(expected));
+		assert_eq!(H32::from_str_lenient("0x0123abcd"), Ok(expected));
+		assert_eq!(H32::from_str_lenient("0X0123ABCD"), Ok(expected));
+	}
+
+	#[test]
+	fn ignores_whitespace_and_underscores() {
+		let expected = H32::from([0x01, 0x23, 0xab, 0xcd]);
+		assert_eq!(H32::from_str_lenient("  0x0123_abcd\n"), Ok(expected));
+		assert_eq!(H32::from_str_lenient("01 23 ab cd"), Ok(expected));
+		assert_eq!(H32::from_str_lenient("\t0x_0123_ab\tcd_"), Ok(expected));
+	}
+
+	#[test]
+	fn pads_short_input() {
+		assert_eq!(H32::from_str_lenient("1"), Ok(H32::from([0, 0, 0, 0x01])));
+		assert_eq!(H32::from_str_lenient("0x123"), Ok(H32::from([0, 0, 0x01, 0x23])));
+		assert_eq!(H32::from_str_lenient("abcde"), Ok(H32::from([0, 0x0a, 0xbc, 0xde])));
+		let mut one = H256::zero();
+		one.0[31] = 1;
+		assert_eq!(H256::from_str_lenient("0x1"), Ok(one));
+	}
+
+	#[test]
+	fn rejects_too_long_input() {
+		assert_eq!(H32::from_str_lenient("0x123456789"), Err(FromHexLenientError::TooLong { digits: 9, max: 8 }));
+		// Leading zeros count as digits.
+		assert_eq!(H32::from_str_lenient("0012345678"), Err(FromHexLenientError::TooLong { digits: 10, max: 8 }));
+	}
+
+	#[test]
+	fn rejects_empty_input() {
+		assert_eq!(H32::from_str_lenient(""), Err(FromHexLenientError::Empty));
+		assert_eq!(H32::from_str_lenient(" 0x_ "), Err(FromHexLenientError::Empty));
+	}
+
+	#[test]
+	fn reports_invalid_character_position() {
+		assert_eq!(
+			H32::from_str_lenient("0x12g4"),
+			Err(FromHexLenientError::InvalidCharacter { character: 'g', index: 4 })
+		);
+		assert_eq!(
+			H32::from_str_lenient("  12-34"),
+			Err(FromHexLenientError::InvalidCharacter { character: '-', index: 4 })
+		);
+		assert_eq!(
+			H32::from_str_lenient("12é4"),
+			Err(FromHexLenientError::InvalidCharacter { character: 'é', index: 2 })
+		);
+		// The prefix is only recognized at the start of the input.
+		assert_eq!(
+			H32::from_str_lenient("120x34"),
+			Err(FromHexLenientError::InvalidCharacter { character: 'x', index: 3 })
+		);
+	}
+
+	#[cfg(feature = "std")]
+	#[test]
+	fn display_error() {
+		assert_eq!(
+			FromHexLenientError::InvalidCharacter { character: 'g', index: 4 }.to_string(),
+			"invalid character 'g' at position 4"
+		);
+		assert_eq!(
+			FromHexLenientError::TooLong { digits: 9, max: 8 }.to_string(),
+			"too many hex digits: 9 (at most 8 allowed)"
+		);
+		assert_eq!(FromHexLenientError::Empty.to_string(), "no hex digits in input");
+	}
+
+	#[cfg(feature = "rustc-hex")]
+	#[test]
+	fn strict_vs_lenient() {
+		use crate::core_::str::FromStr;
+
+		// Input, accepted by `from_str`, accepted by `from_str_lenient`.
+		let table = [
+			("0123abcd", true, true),
+			("0x0123abcd", true, true),
+			("0X0123abcd", false, true),
+			(" 0123abcd ", true, true),
+			("0123_abcd", false, true),
+			("123abcd", false, true),
+			("abcd", false, true),
+			("", false, false),
+			("0123abcd0", false, false),
+			("0123abcg", false, false),
+		];
+		for &(input, strict, lenient) in table.iter() {
+			assert_eq!(H32::from_str(input).is_ok(), strict, "from_str({:?})", input);
+			assert_eq!(H32::from_str_lenient(input).is_ok(), lenient, "from_str_lenient({:?})", input);
+			if strict {
+				assert_eq!(H32::from_str_lenient(input), Ok(H32::from_str(input).unwrap()));
+			}
+		}
+	}
+}
+
 #[test]
 fn from_h160_to_h256() {
 	let h160 = H160::from([
@@ -328,7 +874,75 @@ fn from_h256_to_h160_lossy() {
 	assert_eq!(h160, expected);
 }
 
-#[cfg(all(feature = "std", feature = "byteorder"))]
+mod halves {
+	use super::*;
+
+	#[test]
+	fn split_keeps_big_endian_order() {
+		let h64 = H64::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]);
+		let (high, low) = h64.split();
+		assert_eq!(high, H32::from([0x01, 0x02, 0x03, 0x04]));
+		assert_eq!(low, H32::from([0x05, 0x06, 0x07, 0x08]));
+	}
+
+	#[test]
+	fn round_trip() {
+		let mut bytes = [0u8; 32];
+		for (i, byte) in bytes.iter_mut().enumerate() {
+			*byte = i as u8;
+		}
+		let h256 = H256::from(bytes);
+		let (high, low) = h256.split();
+		assert_eq!(high.as_bytes(), &bytes[..16]);
+		assert_eq!(low.as_bytes(), &bytes[16..]);
+		assert_eq!(H256::from_halves(high, low), h256);
+		assert_eq!(H256::from_halves(low, high).split(), (low, high));
+	}
+}
+
+mod low_high_bytes {
+	use super::*;
+
+	#[test]
+	#[rustfmt::skip]
+	fn truncating_keeps_named_end() {
+		let h256 = H256::from([
+			0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+			0xEF, 0x2D, 0x6D, 0x19, 0x40, 0x84, 0xC2, 0xDE, 0x36, 0xE0,
+			0xDA, 0xBF, 0xCE, 0x45, 0xD0, 0x46, 0xB3, 0x7D, 0x11, 0x06,
+		]);
+		let low = H160::from([
+			0xEF, 0x2D, 0x6D, 0x19, 0x40, 0x84, 0xC2, 0xDE, 0x36, 0xE0,
+			0xDA, 0xBF, 0xCE, 0x45, 0xD0, 0x46, 0xB3, 0x7D, 0x11, 0x06,
+		]);
+		let high = H160::from([
+			0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+			0xFF, 0xFF, 0xEF, 0x2D, 0x6D, 0x19, 0x40, 0x84, 0xC2, 0xDE,
+		]);
+		assert_eq!(H160::from_low_bytes(h256.as_bytes()), low);
+		assert_eq!(H160::from_low_bytes(h256.as_bytes()), H160::from(h256));
+		assert_eq!(H160::from_high_bytes(h256.as_bytes()), high);
+	}
+
+	#[test]
+	fn extending_pads_with_zeros() {
+		let h32 = H32::from([0x01, 0x02, 0x03, 0x04]);
+		assert_eq!(H64::from_low_bytes(h32.as_bytes()), H64::from([0, 0, 0, 0, 0x01, 0x02, 0x03, 0x04]));
+		assert_eq!(H64::from_high_bytes(h32.as_bytes()), H64::from([0x01, 0x02, 0x03, 0x04, 0, 0, 0, 0]));
+	}
+
+	#[test]
+	fn round_trip() {
+		let h160 = H160::repeat_byte(0xAB);
+		assert_eq!(H160::from_low_bytes(H256::from_low_bytes(h160.as_bytes()).as_bytes()), h160);
+		assert_eq!(H160::from_high_bytes(H256::from_high_bytes(h160.as_bytes()).as_bytes()), h160);
+		assert_eq!(H256::from_low_bytes(h160.as_bytes()), H256::from(h160));
+		assert_eq!(H160::from_low_bytes(h160.as_bytes()), h160);
+		assert_eq!(H160::from_high_bytes(&[]), H160::zero());
+	}
+}
+
+#[cfg(feature = "std")]
 #[test]
 fn display_and_debug() {
 	fn test_for(x: u64, hex: &'static str, display: &'static str) {
@@ -349,6 +963,47 @@ fn display_and_debug() {
 	test_for(0x1000, "0000000000001000", "0000…1000");
 }
 
+#[cfg(feature = "std")]
+#[test]
+fn hex_formatting_honors_width_and_flags() {
+	let hash = H32::from([0xde, 0xad, 0xbe, 0xef]);
+
+	assert_eq!(format!("{:#x}", hash), "0xdeadbeef");
+	assert_eq!(format!("{:#X}", hash), "0xDEADBEEF");
+	assert_eq!(format!("{:#012x}", hash), "0x00deadbeef");
+	assert_eq!(format!("{:>10x}", hash), "  deadbeef");
+	assert_eq!(format!("{:-<#12x}", hash), "0xdeadbeef--");
+	// A width smaller than the natural length does not truncate.
+	assert_eq!(format!("{:#04x}", hash), "0xdeadbeef");
+	assert_eq!(format!("{:?}", hash), "0xdeadbeef");
+}
+
+#[test]
+fn formatting_matrix() {
+	let hash = H32::from([0xde, 0xad, 0xbe, 0xef]);
+	let cases = [
+		(format!("{}", hash), "0xdead…beef"),
+		(format!("{:>14}", hash), "   0xdead…beef"),
+		(format!("{:*<13}", hash), "0xdead…beef**"),
+		(format!("{:^15}", hash), "  0xdead…beef  "),
+		(format!("{:#}", hash), "0xdead…beef"),
+		(format!("{:?}", hash), "0xdeadbeef"),
+		(format!("{:>12?}", hash), "  0xdeadbeef"),
+		(format!("{:x}", hash), "deadbeef"),
+		(format!("{:X}", hash), "DEADBEEF"),
+		(format!("{:#x}", hash), "0xdeadbeef"),
+		(format!("{:#X}", hash), "0xDEADBEEF"),
+		(format!("{:>12x}", hash), "    deadbeef"),
+		(format!("{:<12X}", hash), "DEADBEEF    "),
+		(format!("{:^#14x}", hash), "  0xdeadbeef  "),
+		(format!("{:012x}", hash), "0000deadbeef"),
+		(format!("{:#014X}", hash), "0x0000DEADBEEF"),
+	];
+	for (formatted, expected) in cases.iter() {
+		assert_eq!(formatted, expected);
+	}
+}
+
 mod ops {
 	use super::*;
 
@@ -399,3 +1054,71 @@ mod ops {
 		)
 	}
 }
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary {
+	use super::*;
+	use crate::arbitrary::{Arbitrary, Unstructured};
+
+	#[test]
+	fn size_hint() {
+		assert_eq!(H256::size_hint(0), (32, Some(32)));
+		assert_eq!(H160::size_hint(0), (20, Some(20)));
+	}
+
+	#[test]
+	fn from_corpus() {
+		let corpus: Vec<u8> = (0..40).collect();
+		let mut u = Unstructured::new(&corpus);
+		let hash = H256::arbitrary(&mut u).unwrap();
+		assert_eq!(hash.as_bytes(), &corpus[..32]);
+
+		let hash = H160::arbitrary(&mut u).unwrap();
+		let mut expected = [0u8; 20];
+		expected[..8].copy_from_slice(&corpus[32..]);
+		assert_eq!(hash, H160::from(expected));
+	}
+}
+
+#[cfg(feature = "zeroize")]
+mod zeroize {
+	use super::*;
+	use crate::zeroize::{Zeroize, Zeroizing};
+
+	#[test]
+	fn zeroize_clears_bytes() {
+		let mut hash = H256::repeat_byte(0xab);
+		hash.zeroize();
+		assert_eq!(hash.as_bytes(), &[0u8; 32]);
+		assert!(hash.is_zero());
+	}
+
+	#[test]
+	fn zeroizing_wrapper() {
+		let secret = Zeroizing::new(H160::repeat_byte(0x42));
+		assert_eq!(*secret, H160::repeat_byte(0x42));
+	}
+}
+
+#[cfg(feature = "subtle")]
+mod subtle {
+	use super::*;
+	use crate::subtle::ConstantTimeEq;
+
+	#[test]
+	fn agrees_with_eq() {
+		for i in 0..=255u8 {
+			let a = H256::repeat_byte(i);
+			// Also compare hashes differing in a single byte.
+			let mut b = a;
+			if i % 2 == 1 {
+				b.0[usize::from(i) % 32] ^= i;
+			}
+			assert_eq!(a.constant_time_eq(&b), a == b);
+			assert_eq!(bool::from(a.ct_eq(&b)), a == b);
+			assert!(a.constant_time_eq(&a));
+			assert!(!a.constant_time_eq(&H256::repeat_byte(i.wrapping_add(1))));
+		}
+		assert!(!H160::zero().constant_time_eq(&H160::repeat_byte(1)));
+	}
+}
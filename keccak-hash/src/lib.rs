@@ -38,6 +38,26 @@ pub fn keccak<T: AsRef<[u8]>>(s: T) -> H256 {
 	H256(result)
 }
 
+/// Computes the keccak256 hash of the concatenation of `parts`, without joining them into a buffer.
+///
+/// # Example
+///
+/// ```
+/// use keccak_hash::{keccak, keccak256_concat};
+///
+/// let hash = keccak256_concat(&["domain".as_bytes(), "message".as_bytes()]);
+/// assert_eq!(hash, keccak(b"domainmessage"));
+/// ```
+pub fn keccak256_concat(parts: &[&[u8]]) -> H256 {
+	let mut keccak256 = Keccak::v256();
+	for part in parts {
+		keccak256.update(part);
+	}
+	let mut result = [0u8; 32];
+	keccak256.finalize(&mut result);
+	H256(result)
+}
+
 /// Computes in-place keccak256 hash of `data`.
 pub fn keccak256(data: &mut [u8]) {
 	let mut keccak256 = Keccak::v256();
@@ -169,6 +189,15 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn keccak256_concat_matches_joined_input() {
+		let a: &[u8] = b"hello";
+		let b: &[u8] = &[0x41u8; 200];
+		assert_eq!(keccak256_concat(&[a, b]), keccak(&[a, b].concat()));
+		assert_eq!(keccak256_concat(&[b, &[], a]), keccak(&[b, a].concat()));
+		assert_eq!(keccak256_concat(&[]), KECCAK_EMPTY);
+	}
+
 	#[test]
 	fn write_keccak_with_content() {
 		let data: Vec<u8> = From::from("hello world");
@@ -12,6 +12,7 @@ use parking_lot::RwLock;
 use std::{
 	collections::{BTreeMap, HashMap},
 	io,
+	ops::Bound,
 };
 
 /// A key-value database fulfilling the `KeyValueDB` trait, living in memory.
@@ -19,6 +20,7 @@ use std::{
 #[derive(Default, MallocSizeOf)]
 pub struct InMemory {
 	columns: RwLock<HashMap<u32, BTreeMap<Vec<u8>, DBValue>>>,
+	max_key_len: Option<usize>,
 }
 
 /// Create an in-memory database with the given number of columns.
@@ -30,7 +32,33 @@ pub fn create(num_cols: u32) -> InMemory {
 		cols.insert(idx, BTreeMap::new());
 	}
 
-	InMemory { columns: RwLock::new(cols) }
+	InMemory { columns: RwLock::new(cols), max_key_len: None }
+}
+
+/// Create an in-memory database with the given number of columns, which rejects writes
+/// of keys longer than `max_key_len` bytes, like databases with limited key sizes do.
+pub fn create_with_max_key_len(num_cols: u32, max_key_len: usize) -> InMemory {
+	InMemory { max_key_len: Some(max_key_len), ..create(num_cols) }
+}
+
+impl InMemory {
+	/// Add a new empty column, indexable by the previous number of columns.
+	pub fn add_column(&self) {
+		let mut columns = self.columns.write();
+		let col = columns.len() as u32;
+		columns.insert(col, BTreeMap::new());
+	}
+
+	/// Remove the last column and its data, like `kvdb_rocksdb::Database::remove_last_column`.
+	/// Fails if there are no columns.
+	pub fn remove_last_column(&self) -> io::Result<()> {
+		let mut columns = self.columns.write();
+		let col = (columns.len() as u32)
+			.checked_sub(1)
+			.ok_or_else(|| io::Error::new(io::ErrorKind::Other, "No column family to remove"))?;
+		columns.remove(&col);
+		Ok(())
+	}
 }
 
 impl KeyValueDB for InMemory {
@@ -42,18 +70,25 @@ impl KeyValueDB for InMemory {
 		}
 	}
 
-	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> Option<Box<[u8]>> {
+	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<Option<DBValue>> {
 		let columns = self.columns.read();
 		match columns.get(&col) {
-			None => None,
-			Some(map) => {
-				map.iter().find(|&(ref k, _)| k.starts_with(prefix)).map(|(_, v)| v.to_vec().into_boxed_slice())
-			}
+			None => Err(io::Error::new(io::ErrorKind::Other, format!("No such column family: {:?}", col))),
+			Some(map) => Ok(map
+				.range::<[u8], _>((Bound::Included(prefix), Bound::Unbounded))
+				.next()
+				.filter(|&(k, _)| k.starts_with(prefix))
+				.map(|(_, v)| v.clone())),
 		}
 	}
 
 	fn write(&self, transaction: DBTransaction) -> io::Result<()> {
+		if transaction.ops.iter().any(|op| matches!(op, DBOp::Merge { .. })) {
+			return Err(io::Error::new(io::ErrorKind::Other, "Merge is not supported by in-memory database"));
+		}
 		let mut columns = self.columns.write();
+		// Validate like `write_with_report`, so that nothing is written on failure.
+		kvdb::validate_transaction(&transaction, columns.len() as u32, self.max_key_len)?;
 		let ops = transaction.ops;
 		for op in ops {
 			match op {
@@ -69,7 +104,6 @@ impl KeyValueDB for InMemory {
 				}
 				DBOp::DeletePrefix { col, prefix } => {
 					if let Some(col) = columns.get_mut(&col) {
-						use std::ops::Bound;
 						if prefix.is_empty() {
 							col.clear();
 						} else {
@@ -85,6 +119,7 @@ impl KeyValueDB for InMemory {
 						}
 					}
 				}
+				DBOp::Merge { .. } => unreachable!("merge operations are rejected above; qed"),
 			}
 		}
 		Ok(())
@@ -116,14 +151,35 @@ impl KeyValueDB for InMemory {
 		}
 	}
 
+	fn iter_from<'a>(&'a self, col: u32, start: &'a [u8]) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a> {
+		match self.columns.read().get(&col) {
+			Some(map) => Box::new(
+				map.range::<[u8], _>((Bound::Included(start), Bound::Unbounded))
+					.map(|(k, v)| (k.clone().into_boxed_slice(), v.clone().into_boxed_slice()))
+					.collect::<Vec<_>>()
+					.into_iter(),
+			),
+			None => Box::new(None.into_iter()),
+		}
+	}
+
 	fn restore(&self, _new_db: &str) -> io::Result<()> {
 		Err(io::Error::new(io::ErrorKind::Other, "Attempted to restore in-memory database"))
 	}
+
+	fn num_columns(&self) -> u32 {
+		self.columns.read().len() as u32
+	}
+
+	fn max_key_len(&self) -> Option<usize> {
+		self.max_key_len
+	}
 }
 
 #[cfg(test)]
 mod tests {
-	use super::create;
+	use super::{create, create_with_max_key_len};
+	use kvdb::{InvalidOp, InvalidOpReason, KeyValueDB};
 	use kvdb_shared_tests as st;
 	use std::io;
 
@@ -151,6 +207,73 @@ mod tests {
 		st::test_delete_prefix(&db)
 	}
 
+	#[test]
+	fn typed_column() -> io::Result<()> {
+		let db = create(1);
+		st::test_typed_column(&db)
+	}
+
+	#[test]
+	fn write_with_report() -> io::Result<()> {
+		let db = create(1);
+		st::test_write_with_report(&db)
+	}
+
+	#[test]
+	fn write_with_report_rejects_long_keys() -> io::Result<()> {
+		let db = create_with_max_key_len(1, 4);
+		let mut batch = db.transaction();
+		batch.put(0, b"key", b"value");
+		batch.delete(0, b"four");
+		batch.put(0, b"longer", b"value");
+		let err = db.write_with_report(batch.clone()).unwrap_err();
+		let invalid = err.get_ref().and_then(|err| err.downcast_ref::<InvalidOp>()).unwrap();
+		assert_eq!(invalid, &InvalidOp { index: 2, reason: InvalidOpReason::KeyTooLong { len: 6, max: 4 } });
+		assert_eq!(db.get(0, b"key")?, None);
+
+		// `write` enforces the limit as well.
+		assert_eq!(db.write(batch).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+		assert_eq!(db.get(0, b"key")?, None);
+		Ok(())
+	}
+
+	#[test]
+	fn add_and_remove_columns() -> io::Result<()> {
+		let db = create(1);
+		db.add_column();
+		assert_eq!(db.num_columns(), 2);
+		let mut batch = db.transaction();
+		batch.put(1, b"key", b"value");
+		db.write_with_report(batch)?;
+		assert_eq!(db.get(1, b"key")?.as_deref(), Some(&b"value"[..]));
+
+		db.remove_last_column()?;
+		assert_eq!(db.num_columns(), 1);
+		assert!(db.get(1, b"key").is_err());
+		let mut batch = db.transaction();
+		batch.put(1, b"key", b"value");
+		assert_eq!(db.write_with_report(batch).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+		let mut batch = db.transaction();
+		batch.put(0, b"key", b"value");
+		batch.put(1, b"key", b"value");
+		let err = db.write(batch).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		let invalid = err.get_ref().and_then(|err| err.downcast_ref::<InvalidOp>()).unwrap();
+		assert_eq!(invalid, &InvalidOp { index: 1, reason: InvalidOpReason::NoSuchColumn(1) });
+		// Nothing of the rejected transaction is written.
+		assert_eq!(db.get(0, b"key")?, None);
+
+		// A column added again is empty.
+		db.add_column();
+		assert_eq!(db.get(1, b"key")?, None);
+
+		db.remove_last_column()?;
+		db.remove_last_column()?;
+		assert_eq!(db.num_columns(), 0);
+		assert!(db.remove_last_column().is_err());
+		Ok(())
+	}
+
 	#[test]
 	fn iter() -> io::Result<()> {
 		let db = create(1);
@@ -163,9 +286,27 @@ mod tests {
 		st::test_iter_with_prefix(&db)
 	}
 
+	#[test]
+	fn iter_from() -> io::Result<()> {
+		let db = create(1);
+		st::test_iter_from(&db)
+	}
+
+	#[test]
+	fn get_by_prefix() -> io::Result<()> {
+		let db = create(1);
+		st::test_get_by_prefix(&db)
+	}
+
 	#[test]
 	fn complex() -> io::Result<()> {
 		let db = create(1);
 		st::test_complex(&db)
 	}
+
+	#[test]
+	fn num_columns() -> io::Result<()> {
+		let db = create(st::NUM_COLUMNS_NUM_COLUMNS);
+		st::test_num_columns(&db)
+	}
 }
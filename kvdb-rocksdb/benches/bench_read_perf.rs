@@ -125,7 +125,7 @@ fn get(c: &mut Criterion) {
 				for _ in 0..iterations {
 					// This has no measurable impact on performance (~30ns)
 					let needle = needles.choose(&mut rand::thread_rng()).expect("needles is not empty");
-					black_box(db.get_by_prefix(0, &needle.as_bytes()[..8]).unwrap());
+					black_box(db.get_by_prefix(0, &needle.as_bytes()[..8]).unwrap().unwrap());
 				}
 				elapsed = start.elapsed();
 			});
@@ -82,6 +82,11 @@ pub trait IterationHandler {
 	/// https://github.com/facebook/rocksdb/blob/master/include/rocksdb/options.h#L1169).
 	/// The `Iterator` iterates over keys which start with the provided `prefix`.
 	fn iter_with_prefix(&self, col: u32, prefix: &[u8], read_opts: ReadOptions) -> Self::Iterator;
+	/// Create an `Iterator` over a `ColumnFamily` corresponding to the passed index. Takes
+	/// `ReadOptions` to allow configuration of the new iterator (see
+	/// https://github.com/facebook/rocksdb/blob/master/include/rocksdb/options.h#L1169).
+	/// The `Iterator` starts at the first key greater than or equal to `start`.
+	fn iter_from(&self, col: u32, start: &[u8], read_opts: ReadOptions) -> Self::Iterator;
 }
 
 impl<'a, T> ReadGuardedIterator<'a, <&'a T as IterationHandler>::Iterator, T>
@@ -105,6 +110,12 @@ where
 		Self { inner: Self::new_inner(read_lock, |db| db.iter_with_prefix(col, prefix, read_opts)) }
 	}
 
+	/// Creates a new `ReadGuardedIterator` that maps `RwLock<RocksDB>` to `RwLock<DBIterator>`,
+	/// where `DBIterator` starts at the first key greater than or equal to `start`.
+	pub fn new_from(read_lock: RwLockReadGuard<'a, Option<T>>, col: u32, start: &[u8], read_opts: ReadOptions) -> Self {
+		Self { inner: Self::new_inner(read_lock, |db| db.iter_from(col, start, read_opts)) }
+	}
+
 	fn new_inner(
 		rlock: RwLockReadGuard<'a, Option<T>>,
 		f: impl FnOnce(&'a T) -> <&'a T as IterationHandler>::Iterator,
@@ -126,4 +137,8 @@ impl<'a> IterationHandler for &'a DBAndColumns {
 	fn iter_with_prefix(&self, col: u32, prefix: &[u8], read_opts: ReadOptions) -> Self::Iterator {
 		self.db.iterator_cf_opt(self.cf(col as usize), read_opts, IteratorMode::From(prefix, Direction::Forward))
 	}
+
+	fn iter_from(&self, col: u32, start: &[u8], read_opts: ReadOptions) -> Self::Iterator {
+		self.db.iterator_cf_opt(self.cf(col as usize), read_opts, IteratorMode::From(start, Direction::Forward))
+	}
 }
@@ -9,12 +9,22 @@
 mod iter;
 mod stats;
 
-use std::{cmp, collections::HashMap, convert::identity, error, fs, io, mem, path::Path, result};
+use std::{
+	cmp,
+	collections::HashMap,
+	convert::{identity, TryInto},
+	error, fs, io, mem,
+	path::Path,
+	result,
+	sync::Arc,
+	time::{SystemTime, UNIX_EPOCH},
+};
 
 use parity_util_mem::MallocSizeOf;
 use parking_lot::RwLock;
 use rocksdb::{
-	BlockBasedOptions, ColumnFamily, ColumnFamilyDescriptor, Error, Options, ReadOptions, WriteBatch, WriteOptions, DB,
+	BlockBasedOptions, ColumnFamily, ColumnFamilyDescriptor, CompactionDecision, Error, IteratorMode, MergeOperands,
+	Options, ReadOptions, WriteBatch, WriteOptions as RocksWriteOptions, DB,
 };
 
 use crate::iter::KeyValuePair;
@@ -50,6 +60,82 @@ pub const DB_DEFAULT_COLUMN_MEMORY_BUDGET_MB: MiB = 128;
 /// The default memory budget in MiB.
 pub const DB_DEFAULT_MEMORY_BUDGET_MB: MiB = 512;
 
+/// Size of the write timestamp appended to values stored in columns with a TTL.
+const TTL_TIMESTAMP_LEN: usize = 8;
+
+fn unix_now() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Append the current time to a value written to a column with a TTL.
+fn append_ttl_timestamp(value: &[u8]) -> Vec<u8> {
+	let mut stamped = Vec::with_capacity(value.len() + TTL_TIMESTAMP_LEN);
+	stamped.extend_from_slice(value);
+	stamped.extend_from_slice(&unix_now().to_be_bytes());
+	stamped
+}
+
+/// Strip the write timestamp from a value read from a column with a TTL.
+fn strip_ttl_timestamp(value: &[u8]) -> &[u8] {
+	&value[..value.len().saturating_sub(TTL_TIMESTAMP_LEN)]
+}
+
+/// Whether a value read from a column with a TTL was written more than `ttl` seconds ago.
+fn is_ttl_expired(value: &[u8], ttl: u64) -> bool {
+	let written = match value.len().checked_sub(TTL_TIMESTAMP_LEN) {
+		Some(start) => u64::from_be_bytes(value[start..].try_into().expect("slice has TTL_TIMESTAMP_LEN bytes; qed")),
+		None => return false,
+	};
+	unix_now() >= written.saturating_add(ttl)
+}
+
+/// Key in RocksDB's `default` column family, which is otherwise unused, marking a column
+/// as storing its values with write timestamps.
+fn ttl_marker_key(col: u32) -> Vec<u8> {
+	format!("ttl-col{}", col).into_bytes()
+}
+
+/// Record the TTL configuration of a new, empty column.
+fn set_ttl_marker(db: &DB, col: u32, has_ttl: bool) -> io::Result<()> {
+	let key = ttl_marker_key(col);
+	let result = if has_ttl { db.put(&key, b"") } else { db.delete(&key) };
+	result.map_err(other_io_err)
+}
+
+/// Check that the columns gaining or losing a TTL are empty, as their values would otherwise
+/// be read with the wrong format, and record the TTL configuration unless `read_only`.
+fn check_ttl_markers(db: &DB, config: &DatabaseConfig, column_names: &[String], read_only: bool) -> io::Result<()> {
+	for col in 0..config.columns {
+		let has_ttl = config.ttl_for_col(col).is_some();
+		if db.get(ttl_marker_key(col)).map_err(other_io_err)?.is_some() == has_ttl {
+			continue;
+		}
+		let cf = db
+			.cf_handle(&column_names[col as usize])
+			.ok_or_else(|| other_io_err(format!("no column family for column {}", col)))?;
+		if db.iterator_cf(cf, IteratorMode::Start).next().is_some() {
+			return Err(other_io_err(if has_ttl {
+				format!("column {} holds values written without a TTL and can not be given one", col)
+			} else {
+				format!("column {} holds values written with a TTL and can not be opened without one", col)
+			}));
+		}
+		if !read_only {
+			set_ttl_marker(db, col, has_ttl)?;
+		}
+	}
+	Ok(())
+}
+
+/// An associative merge function, see `DatabaseConfig::merge_operators`.
+///
+/// It is called with the key, the existing value, if any, and the operands to apply
+/// in the order they were written, and returns the merged value. Returning `None`
+/// signals a failure, which RocksDB reports as corruption.
+pub trait MergeFn: Fn(&[u8], Option<&[u8]>, &[&[u8]]) -> Option<Vec<u8>> + Send + Sync + 'static {}
+
+impl<F> MergeFn for F where F: Fn(&[u8], Option<&[u8]>, &[&[u8]]) -> Option<Vec<u8>> + Send + Sync + 'static {}
+
 /// Compaction profile for the database settings
 /// Note, that changing these parameters may trigger
 /// the compaction process of RocksDB on startup.
@@ -140,6 +226,18 @@ impl CompactionProfile {
 	}
 }
 
+/// Options for a single write, see `Database::write_opt`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WriteOptions {
+	/// Skip the write-ahead log.
+	///
+	/// This speeds up bulk imports, but the written data lives only in memory until
+	/// `Database::flush` is called or the memtables are flushed in the background,
+	/// and is lost if the process crashes before that. Writes which use the log may
+	/// be persisted while earlier writes without it are lost.
+	pub disable_wal: bool,
+}
+
 /// Database configuration
 #[derive(Clone)]
 pub struct DatabaseConfig {
@@ -180,6 +278,27 @@ pub struct DatabaseConfig {
 	/// Limit the size (in bytes) of write ahead logs
 	/// More info: https://github.com/facebook/rocksdb/wiki/Write-Ahead-Log
 	pub max_total_wal_size: Option<u64>,
+	/// Time to live (in seconds) of the entries of each column.
+	/// Columns without a TTL keep their entries forever.
+	///
+	/// Entries written more than the TTL ago become eligible for removal,
+	/// but are only dropped when RocksDB compacts the files holding them.
+	/// Expiry is therefore best-effort: expired entries may still be returned
+	/// by reads until a compaction has happened.
+	///
+	/// Values in columns with a TTL are stored with their write timestamp appended,
+	/// so a column can only gain or lose its TTL while it is empty. Opening a database
+	/// with a TTL configuration which does not match its non-empty columns fails.
+	/// RocksDB's own TTL mode also stores such timestamps, but applies a single TTL
+	/// to every column of the database, so expired entries are dropped by a
+	/// compaction filter of each column with a TTL instead.
+	pub ttl_seconds: HashMap<u32, u64>,
+	/// Merge operator of each column, used by `DBTransaction::merge`.
+	///
+	/// The merge function must be associative, as RocksDB combines operands
+	/// in any grouping, both on reads and during compaction.
+	/// A column can not have both a merge operator and a TTL.
+	pub merge_operators: HashMap<u32, Arc<dyn MergeFn>>,
 }
 
 impl DatabaseConfig {
@@ -205,6 +324,11 @@ impl DatabaseConfig {
 		self.memory_budget.get(&col).unwrap_or(&DB_DEFAULT_COLUMN_MEMORY_BUDGET_MB) * MB
 	}
 
+	/// Returns the TTL of the specified column in seconds, if any.
+	fn ttl_for_col(&self, col: u32) -> Option<u64> {
+		self.ttl_seconds.get(&col).copied()
+	}
+
 	// Get column family configuration with the given block based options.
 	fn column_config(&self, block_opts: &BlockBasedOptions, col: u32) -> Options {
 		let column_mem_budget = self.memory_budget_for_col(col);
@@ -215,6 +339,25 @@ impl DatabaseConfig {
 		opts.optimize_level_style_compaction(column_mem_budget);
 		opts.set_target_file_size_base(self.compaction.initial_file_size);
 		opts.set_compression_per_level(&[]);
+		if let Some(merge) = self.merge_operators.get(&col) {
+			let merge = merge.clone();
+			opts.set_merge_operator_associative(
+				"kvdb-merge",
+				move |key: &[u8], existing: Option<&[u8]>, operands: &mut MergeOperands| {
+					let operands: Vec<&[u8]> = operands.collect();
+					merge(key, existing, &operands)
+				},
+			);
+		}
+		if let Some(ttl) = self.ttl_for_col(col) {
+			opts.set_compaction_filter("ttl", move |_level: u32, _key: &[u8], value: &[u8]| {
+				if is_ttl_expired(value, ttl) {
+					CompactionDecision::Remove
+				} else {
+					CompactionDecision::Keep
+				}
+			});
+		}
 
 		opts
 	}
@@ -231,6 +374,8 @@ impl Default for DatabaseConfig {
 			enable_statistics: false,
 			secondary: None,
 			max_total_wal_size: None,
+			ttl_seconds: HashMap::new(),
+			merge_operators: HashMap::new(),
 		}
 	}
 }
@@ -285,7 +430,7 @@ pub struct Database {
 	#[ignore_malloc_size_of = "insignificant"]
 	opts: Options,
 	#[ignore_malloc_size_of = "insignificant"]
-	write_opts: WriteOptions,
+	write_opts: RocksWriteOptions,
 	#[ignore_malloc_size_of = "insignificant"]
 	read_opts: ReadOptions,
 	#[ignore_malloc_size_of = "insignificant"]
@@ -378,6 +523,9 @@ impl Database {
 	/// The number of `config.columns` must not be zero.
 	pub fn open(config: &DatabaseConfig, path: &str) -> io::Result<Database> {
 		assert!(config.columns > 0, "the number of columns must not be zero");
+		if let Some(col) = config.merge_operators.keys().find(|col| config.ttl_seconds.contains_key(col)) {
+			return Err(other_io_err(format!("column {} can not have both a merge operator and a TTL", col)));
+		}
 
 		let opts = generate_options(config);
 		let block_opts = generate_block_based_options(config)?;
@@ -391,7 +539,7 @@ impl Database {
 		}
 
 		let column_names: Vec<_> = (0..config.columns).map(|c| format!("col{}", c)).collect();
-		let write_opts = WriteOptions::default();
+		let write_opts = RocksWriteOptions::default();
 		let read_opts = generate_read_options();
 
 		let db = if let Some(secondary_path) = &config.secondary {
@@ -400,6 +548,7 @@ impl Database {
 			let column_names: Vec<&str> = column_names.iter().map(|s| s.as_str()).collect();
 			Self::open_primary(&opts, path, config, column_names.as_slice(), &block_opts)?
 		};
+		check_ttl_markers(&db, config, &column_names, config.secondary.is_some())?;
 
 		Ok(Database {
 			db: RwLock::new(Some(DBAndColumns { db, column_names })),
@@ -413,6 +562,21 @@ impl Database {
 		})
 	}
 
+	/// Open an existing database with all of its columns, discovering their number from
+	/// the database itself rather than from `config.columns`, which is ignored.
+	///
+	/// Fails if there is no database at `path` or if it has no columns.
+	pub fn open_with_existing_columns(config: &DatabaseConfig, path: &str) -> io::Result<Database> {
+		let opts = generate_options(config);
+		let names = DB::list_cf(&opts, path).map_err(other_io_err)?;
+		// Columns are named `col0`, `col1` and so on, next to rocksdb's `default` column family.
+		let columns = (0..).take_while(|col| names.contains(&format!("col{}", col))).count() as u32;
+		if columns == 0 {
+			return Err(other_io_err(format!("database at {} has no columns", path)));
+		}
+		Self::open(&DatabaseConfig { columns, ..config.clone() }, path)
+	}
+
 	/// Internal api to open a database in primary mode.
 	fn open_primary(
 		opts: &Options,
@@ -489,8 +653,22 @@ impl Database {
 
 	/// Commit transaction to database.
 	pub fn write(&self, tr: DBTransaction) -> io::Result<()> {
+		self.write_opt(tr, WriteOptions::default())
+	}
+
+	/// Commit transaction to database with the given options.
+	pub fn write_opt(&self, tr: DBTransaction, options: WriteOptions) -> io::Result<()> {
 		match *self.db.read() {
 			Some(ref cfs) => {
+				// RocksDB accepts merges without an operator, but then fails to read the key.
+				let unmergeable = tr.ops.iter().find_map(|op| match op {
+					DBOp::Merge { col, .. } if !self.config.merge_operators.contains_key(col) => Some(*col),
+					_ => None,
+				});
+				if let Some(col) = unmergeable {
+					return Err(other_io_err(format!("No merge operator configured for column {}", col)));
+				}
+
 				let mut batch = WriteBatch::default();
 				let ops = tr.ops;
 
@@ -503,15 +681,23 @@ impl Database {
 					let cf = cfs.cf(op.col() as usize);
 
 					match op {
-						DBOp::Insert { col: _, key, value } => {
+						DBOp::Insert { col, key, value } => {
 							stats_total_bytes += key.len() + value.len();
-							batch.put_cf(cf, &key, &value);
+							if self.config.ttl_for_col(col).is_some() {
+								batch.put_cf(cf, &key, &append_ttl_timestamp(&value));
+							} else {
+								batch.put_cf(cf, &key, &value);
+							}
 						}
 						DBOp::Delete { col: _, key } => {
 							// We count deletes as writes.
 							stats_total_bytes += key.len();
 							batch.delete_cf(cf, &key);
 						}
+						DBOp::Merge { col: _, key, operand } => {
+							stats_total_bytes += key.len() + operand.len();
+							batch.merge_cf(cf, &key, &operand);
+						}
 						DBOp::DeletePrefix { col, prefix } => {
 							let end_prefix = kvdb::end_prefix(&prefix[..]);
 							let no_end = end_prefix.is_none();
@@ -533,7 +719,29 @@ impl Database {
 				}
 				self.stats.tally_bytes_written(stats_total_bytes as u64);
 
-				check_for_corruption(&self.path, cfs.db.write_opt(batch, &self.write_opts))
+				let result = if options.disable_wal {
+					let mut write_opts = RocksWriteOptions::default();
+					write_opts.disable_wal(true);
+					cfs.db.write_opt(batch, &write_opts)
+				} else {
+					cfs.db.write_opt(batch, &self.write_opts)
+				};
+				check_for_corruption(&self.path, result)
+			}
+			None => Err(other_io_err("Database is closed")),
+		}
+	}
+
+	/// Flush the memtables of all columns to disk.
+	///
+	/// Writes made with `WriteOptions::disable_wal` are durable once this returns.
+	pub fn flush(&self) -> io::Result<()> {
+		match *self.db.read() {
+			Some(ref cfs) => {
+				for col in 0..cfs.column_names.len() {
+					cfs.db.flush_cf(cfs.cf(col)).map_err(other_io_err)?;
+				}
+				Ok(())
 			}
 			None => Err(other_io_err("Database is closed")),
 		}
@@ -547,10 +755,11 @@ impl Database {
 					return Err(other_io_err("column index is out of bounds"));
 				}
 				self.stats.tally_reads(1);
+				let has_ttl = self.config.ttl_for_col(col).is_some();
 				let value = cfs
 					.db
 					.get_pinned_cf_opt(cfs.cf(col as usize), key, &self.read_opts)
-					.map(|r| r.map(|v| v.to_vec()))
+					.map(|r| r.map(|v| if has_ttl { strip_ttl_timestamp(&v).to_vec() } else { v.to_vec() }))
 					.map_err(other_io_err);
 
 				match value {
@@ -565,9 +774,15 @@ impl Database {
 		}
 	}
 
-	/// Get value by partial key. Prefix size should match configured prefix size.
-	pub fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> Option<Box<[u8]>> {
-		self.iter_with_prefix(col, prefix).next().map(|(_, v)| v)
+	/// Get the value of the first key, in sorted order, starting with `prefix`, seeking to it
+	/// with a prefix iterator.
+	pub fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<Option<DBValue>> {
+		if let Some(ref cfs) = *self.db.read() {
+			if cfs.column_names.get(col as usize).is_none() {
+				return Err(other_io_err("column index is out of bounds"));
+			}
+		}
+		Ok(self.iter_with_prefix(col, prefix).next().map(|(_, v)| v.into_vec()))
 	}
 
 	/// Iterator over the data in the given database column index.
@@ -582,7 +797,7 @@ impl Database {
 		} else {
 			None
 		};
-		optional.into_iter().flat_map(identity)
+		self.strip_ttl_timestamps(col, optional.into_iter().flat_map(identity))
 	}
 
 	/// Iterator over data in the `col` database column index matching the given prefix.
@@ -601,7 +816,33 @@ impl Database {
 		} else {
 			None
 		};
-		optional.into_iter().flat_map(identity)
+		self.strip_ttl_timestamps(col, optional.into_iter().flat_map(identity))
+	}
+
+	/// Iterator over data in the `col` database column index, starting at the first key
+	/// greater than or equal to `start`.
+	/// Will hold a lock until the iterator is dropped
+	/// preventing the database from being closed.
+	pub fn iter_from<'a>(&'a self, col: u32, start: &[u8]) -> impl Iterator<Item = KeyValuePair> + 'a {
+		let read_lock = self.db.read();
+		let optional = if read_lock.is_some() {
+			let read_opts = generate_read_options();
+			let guarded = iter::ReadGuardedIterator::new_from(read_lock, col, start, read_opts);
+			Some(guarded)
+		} else {
+			None
+		};
+		self.strip_ttl_timestamps(col, optional.into_iter().flat_map(identity))
+	}
+
+	/// Strip the write timestamps from the values of a column with a TTL.
+	fn strip_ttl_timestamps<'a>(
+		&self,
+		col: u32,
+		iter: impl Iterator<Item = KeyValuePair> + 'a,
+	) -> impl Iterator<Item = KeyValuePair> + 'a {
+		let has_ttl = self.config.ttl_for_col(col).is_some();
+		iter.map(move |(key, value)| if has_ttl { (key, strip_ttl_timestamp(&value).into()) } else { (key, value) })
 	}
 
 	/// Close the database
@@ -674,6 +915,7 @@ impl Database {
 			Some(DBAndColumns { ref mut db, ref mut column_names }) => {
 				if let Some(name) = column_names.pop() {
 					db.drop_cf(&name).map_err(other_io_err)?;
+					set_ttl_marker(db, column_names.len() as u32, false)?;
 				}
 				Ok(())
 			}
@@ -689,6 +931,7 @@ impl Database {
 				let name = format!("col{}", col);
 				let col_config = self.config.column_config(&self.block_opts, col as u32);
 				let _ = db.create_cf(&name, &col_config).map_err(other_io_err)?;
+				set_ttl_marker(db, col, self.config.ttl_for_col(col).is_some())?;
 				column_names.push(name);
 				Ok(())
 			}
@@ -740,7 +983,7 @@ impl KeyValueDB for Database {
 		Database::get(self, col, key)
 	}
 
-	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> Option<Box<[u8]>> {
+	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<Option<DBValue>> {
 		Database::get_by_prefix(self, col, prefix)
 	}
 
@@ -758,10 +1001,19 @@ impl KeyValueDB for Database {
 		Box::new(unboxed.into_iter())
 	}
 
+	fn iter_from<'a>(&'a self, col: u32, start: &'a [u8]) -> Box<dyn Iterator<Item = KeyValuePair> + 'a> {
+		let unboxed = Database::iter_from(self, col, start);
+		Box::new(unboxed.into_iter())
+	}
+
 	fn restore(&self, new_db: &str) -> io::Result<()> {
 		Database::restore(self, new_db)
 	}
 
+	fn num_columns(&self) -> u32 {
+		Database::num_columns(self)
+	}
+
 	fn io_stats(&self, kind: kvdb::IoStatsKind) -> kvdb::IoStats {
 		let rocksdb_stats = self.get_statistics();
 		let cache_hit_count = rocksdb_stats.get("block.cache.hit").map(|s| s.count).unwrap_or(0u64);
@@ -827,6 +1079,18 @@ mod tests {
 		st::test_delete_prefix(&db)
 	}
 
+	#[test]
+	fn typed_column() -> io::Result<()> {
+		let db = create(1)?;
+		st::test_typed_column(&db)
+	}
+
+	#[test]
+	fn write_with_report() -> io::Result<()> {
+		let db = create(1)?;
+		st::test_write_with_report(&db)
+	}
+
 	#[test]
 	fn iter() -> io::Result<()> {
 		let db = create(1)?;
@@ -839,12 +1103,30 @@ mod tests {
 		st::test_iter_with_prefix(&db)
 	}
 
+	#[test]
+	fn iter_from() -> io::Result<()> {
+		let db = create(1)?;
+		st::test_iter_from(&db)
+	}
+
+	#[test]
+	fn get_by_prefix() -> io::Result<()> {
+		let db = create(1)?;
+		st::test_get_by_prefix(&db)
+	}
+
 	#[test]
 	fn complex() -> io::Result<()> {
 		let db = create(1)?;
 		st::test_complex(&db)
 	}
 
+	#[test]
+	fn num_columns() -> io::Result<()> {
+		let db = create(st::NUM_COLUMNS_NUM_COLUMNS)?;
+		st::test_num_columns(&db)
+	}
+
 	#[test]
 	fn stats() -> io::Result<()> {
 		let db = create(st::IO_STATS_NUM_COLUMNS)?;
@@ -906,6 +1188,8 @@ mod tests {
 			enable_statistics: false,
 			secondary: None,
 			max_total_wal_size: None,
+			ttl_seconds: HashMap::new(),
+			merge_operators: HashMap::new(),
 		};
 
 		let db = Database::open(&config, tempdir.path().to_str().unwrap()).unwrap();
@@ -978,6 +1262,35 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn open_with_existing_columns() {
+		let tempdir = TempfileBuilder::new().prefix("existing_columns").tempdir().unwrap();
+		let path = tempdir.path().to_str().unwrap();
+
+		assert!(Database::open_with_existing_columns(&DatabaseConfig::default(), path).is_err());
+
+		{
+			let db = Database::open(&DatabaseConfig::with_columns(4), path).unwrap();
+			let mut batch = db.transaction();
+			for col in 0..4 {
+				batch.put(col, b"key", &[col as u8]);
+			}
+			db.write(batch).unwrap();
+		}
+
+		let db = Database::open_with_existing_columns(&DatabaseConfig::default(), path).unwrap();
+		assert_eq!(db.num_columns(), 4);
+		for col in 0..4 {
+			assert_eq!(db.get(col, b"key").unwrap().unwrap(), vec![col as u8]);
+		}
+		assert!(db.get(4, b"key").is_err());
+		db.add_column().unwrap();
+		drop(db);
+
+		let db = Database::open_with_existing_columns(&DatabaseConfig::with_columns(1), path).unwrap();
+		assert_eq!(db.num_columns(), 5);
+	}
+
 	#[test]
 	fn remove_columns() {
 		let config_1 = DatabaseConfig::default();
@@ -1003,6 +1316,168 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn ttl_column_expires_on_compaction() -> io::Result<()> {
+		let tempdir = TempfileBuilder::new().prefix("").tempdir()?;
+		let mut config = DatabaseConfig::with_columns(2);
+		config.ttl_seconds.insert(1, 1);
+		let db = Database::open(&config, tempdir.path().to_str().expect("tempdir path is valid unicode"))?;
+
+		let mut transaction = db.transaction();
+		transaction.put(0, b"key", b"forever");
+		transaction.put(1, b"key", b"ephemeral");
+		db.write(transaction)?;
+
+		assert_eq!(&*db.get(1, b"key")?.unwrap(), b"ephemeral");
+		assert_eq!(db.iter(1).map(|(_, v)| v.into_vec()).collect::<Vec<_>>(), vec![b"ephemeral".to_vec()]);
+		assert_eq!(db.get_by_prefix(1, b"k")?.as_deref(), Some(&b"ephemeral"[..]));
+
+		std::thread::sleep(std::time::Duration::from_secs(2));
+		{
+			let cfs = db.db.read();
+			let cfs = cfs.as_ref().expect("database is open");
+			for col in 0..2 {
+				cfs.db.compact_range_cf(cfs.cf(col), None::<&[u8]>, None::<&[u8]>);
+			}
+		}
+
+		assert_eq!(&*db.get(0, b"key")?.unwrap(), b"forever");
+		assert_eq!(db.get(1, b"key")?, None);
+		Ok(())
+	}
+
+	#[test]
+	fn ttl_change_of_non_empty_column_is_rejected() -> io::Result<()> {
+		let tempdir = TempfileBuilder::new().prefix("").tempdir()?;
+		let path = tempdir.path().to_str().expect("tempdir path is valid unicode");
+		let plain = DatabaseConfig::with_columns(2);
+		let mut with_ttl = DatabaseConfig::with_columns(2);
+		with_ttl.ttl_seconds.insert(1, 60);
+
+		// An empty column can gain a TTL.
+		drop(Database::open(&plain, path)?);
+		let db = Database::open(&with_ttl, path)?;
+		let mut transaction = db.transaction();
+		transaction.put(1, b"key", b"value");
+		db.write(transaction)?;
+		drop(db);
+
+		// A column holding values with a TTL can not lose it.
+		assert!(Database::open(&plain, path).is_err());
+		let db = Database::open(&with_ttl, path)?;
+		assert_eq!(&*db.get(1, b"key")?.unwrap(), b"value");
+
+		// Nor can a column holding values without a TTL gain one.
+		let mut transaction = db.transaction();
+		transaction.put(0, b"key", b"value");
+		db.write(transaction)?;
+		drop(db);
+		let mut both = with_ttl.clone();
+		both.ttl_seconds.insert(0, 60);
+		assert!(Database::open(&both, path).is_err());
+		Ok(())
+	}
+
+	fn add_u64(_key: &[u8], existing: Option<&[u8]>, operands: &[&[u8]]) -> Option<Vec<u8>> {
+		let mut sum = match existing {
+			Some(existing) => u64::from_le_bytes(existing.try_into().ok()?),
+			None => 0,
+		};
+		for operand in operands {
+			sum = sum.wrapping_add(u64::from_le_bytes((*operand).try_into().ok()?));
+		}
+		Some(sum.to_le_bytes().to_vec())
+	}
+
+	#[test]
+	fn merge_operator_sums_operands() -> io::Result<()> {
+		let tempdir = TempfileBuilder::new().prefix("").tempdir()?;
+		let path = tempdir.path().to_str().expect("tempdir path is valid unicode");
+		let mut config = DatabaseConfig::with_columns(2);
+		config.merge_operators.insert(1, Arc::new(add_u64));
+
+		{
+			let db = Database::open(&config, path)?;
+			for i in 1..=10u64 {
+				let mut transaction = db.transaction();
+				transaction.merge(1, b"counter", &i.to_le_bytes());
+				db.write(transaction)?;
+			}
+			assert_eq!(db.get(1, b"counter")?, Some(55u64.to_le_bytes().to_vec()));
+
+			// Merges apply on top of a written value, and several merges in one transaction.
+			let mut transaction = db.transaction();
+			transaction.put(1, b"other", &100u64.to_le_bytes());
+			transaction.merge(1, b"other", &1u64.to_le_bytes());
+			transaction.merge(1, b"other", &2u64.to_le_bytes());
+			db.write(transaction)?;
+			assert_eq!(db.get(1, b"other")?, Some(103u64.to_le_bytes().to_vec()));
+		}
+
+		// The merged values survive reopening, and merging continues on top of them.
+		let db = Database::open(&config, path)?;
+		let mut transaction = db.transaction();
+		transaction.merge(1, b"counter", &45u64.to_le_bytes());
+		db.write(transaction)?;
+		assert_eq!(db.get(1, b"counter")?, Some(100u64.to_le_bytes().to_vec()));
+		Ok(())
+	}
+
+	#[test]
+	fn write_without_wal_persists_after_flush() -> io::Result<()> {
+		let tempdir = TempfileBuilder::new().prefix("").tempdir()?;
+		let path = tempdir.path().to_str().expect("tempdir path is valid unicode");
+		let config = DatabaseConfig::with_columns(2);
+
+		{
+			let db = Database::open(&config, path)?;
+			for i in 0..100u32 {
+				let mut transaction = db.transaction();
+				transaction.put(i % 2, &i.to_be_bytes(), b"value");
+				db.write_opt(transaction, WriteOptions { disable_wal: true })?;
+			}
+			assert_eq!(db.get(1, &1u32.to_be_bytes())?.as_deref(), Some(&b"value"[..]));
+			db.flush()?;
+		}
+
+		let db = Database::open(&config, path)?;
+		for i in 0..100u32 {
+			assert_eq!(db.get(i % 2, &i.to_be_bytes())?.as_deref(), Some(&b"value"[..]));
+		}
+		assert_eq!(db.iter(0).count(), 50);
+		Ok(())
+	}
+
+	#[test]
+	fn merge_without_operator_is_rejected() -> io::Result<()> {
+		let tempdir = TempfileBuilder::new().prefix("").tempdir()?;
+		let mut config = DatabaseConfig::with_columns(2);
+		config.merge_operators.insert(1, Arc::new(add_u64));
+		let db = Database::open(&config, tempdir.path().to_str().expect("tempdir path is valid unicode"))?;
+
+		let mut transaction = db.transaction();
+		transaction.put(0, b"counter", &1u64.to_le_bytes());
+		transaction.merge(0, b"counter", &2u64.to_le_bytes());
+		assert!(db.write(transaction).is_err());
+
+		// Nothing of the rejected transaction is written, and the key can still be read.
+		assert_eq!(db.get(0, b"counter")?, None);
+		let mut transaction = db.transaction();
+		transaction.put(0, b"counter", &1u64.to_le_bytes());
+		db.write(transaction)?;
+		assert_eq!(db.get(0, b"counter")?, Some(1u64.to_le_bytes().to_vec()));
+		Ok(())
+	}
+
+	#[test]
+	fn merge_operator_and_ttl_are_exclusive() {
+		let tempdir = TempfileBuilder::new().prefix("").tempdir().unwrap();
+		let mut config = DatabaseConfig::with_columns(1);
+		config.merge_operators.insert(0, Arc::new(add_u64));
+		config.ttl_seconds.insert(0, 60);
+		assert!(Database::open(&config, tempdir.path().to_str().unwrap()).is_err());
+	}
+
 	#[test]
 	fn test_num_keys() {
 		let tempdir = TempfileBuilder::new().prefix("").tempdir().unwrap();
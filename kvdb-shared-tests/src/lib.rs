@@ -8,7 +8,7 @@
 
 //! Shared tests for kvdb functionality, to be executed against actual implementations.
 
-use kvdb::{IoStatsKind, KeyValueDB};
+use kvdb::{InvalidOp, InvalidOpReason, IoStatsKind, KeyValueDB, TypedColumn, WriteReport};
 use std::io;
 
 /// A test for `KeyValueDB::get`.
@@ -61,6 +61,30 @@ pub fn test_write_clears_buffered_ops(db: &dyn KeyValueDB) -> io::Result<()> {
 	Ok(())
 }
 
+/// A test for `KeyValueDB::write_with_report`.
+/// Assumes the `db` has only 1 column.
+pub fn test_write_with_report(db: &dyn KeyValueDB) -> io::Result<()> {
+	let mut batch = db.transaction();
+	batch.put(0, b"foo", b"bar");
+	batch.put(0, b"baz", b"qux");
+	batch.delete(0, b"baz");
+	batch.delete_prefix(0, b"nothing");
+	let report = db.write_with_report(batch)?;
+	assert_eq!(report, WriteReport { puts: 2, deletes: 1, prefix_deletes: 1, merges: 0 });
+	assert_eq!(db.get(0, b"foo")?.unwrap(), b"bar");
+	assert_eq!(db.get(0, b"baz")?, None);
+
+	let mut batch = db.transaction();
+	batch.put(0, b"written", b"no");
+	batch.put(1, b"key", b"value");
+	let err = db.write_with_report(batch).unwrap_err();
+	assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+	let invalid = err.get_ref().and_then(|err| err.downcast_ref::<InvalidOp>()).unwrap();
+	assert_eq!(invalid, &InvalidOp { index: 1, reason: InvalidOpReason::NoSuchColumn(1) });
+	assert_eq!(db.get(0, b"written")?, None);
+	Ok(())
+}
+
 /// A test for `KeyValueDB::iter`.
 pub fn test_iter(db: &dyn KeyValueDB) -> io::Result<()> {
 	let key1 = b"key1";
@@ -126,6 +150,81 @@ pub fn test_iter_with_prefix(db: &dyn KeyValueDB) -> io::Result<()> {
 	Ok(())
 }
 
+/// A test for `KeyValueDB::iter_from`.
+pub fn test_iter_from(db: &dyn KeyValueDB) -> io::Result<()> {
+	let keys: [&[u8]; 6] = [b"a", b"ab", b"abc", b"b", b"ba", b"c"];
+
+	let mut batch = db.transaction();
+	for key in keys.iter() {
+		batch.put(0, key, key);
+	}
+	db.write(batch)?;
+
+	let collect_from = |start: &[u8]| -> Vec<Vec<u8>> { db.iter_from(0, start).map(|(k, _)| k.into_vec()).collect() };
+	let expected_from = |index: usize| -> Vec<Vec<u8>> { keys[index..].iter().map(|key| key.to_vec()).collect() };
+
+	// from the start
+	assert_eq!(collect_from(b""), expected_from(0));
+	// existing key in the middle
+	assert_eq!(collect_from(b"abc"), expected_from(2));
+	// between two keys
+	assert_eq!(collect_from(b"abd"), expected_from(3));
+	// resuming after the last key seen
+	assert_eq!(collect_from(b"b\0"), expected_from(4));
+	// past the end
+	assert_eq!(collect_from(b"d"), Vec::<Vec<u8>>::new());
+
+	// values are returned alongside the keys
+	let (key, value) = db.iter_from(0, b"ba").next().expect("key exists");
+	assert_eq!(&*key, b"ba");
+	assert_eq!(&*value, b"ba");
+
+	// resuming a chunked scan visits every key once, in order
+	let mut seen = Vec::new();
+	let mut cursor = Vec::new();
+	loop {
+		let chunk: Vec<_> = db.iter_from(0, &cursor).take(2).map(|(k, _)| k.into_vec()).collect();
+		match chunk.last() {
+			Some(last) => {
+				cursor = last.clone();
+				cursor.push(0);
+			}
+			None => break,
+		}
+		seen.extend(chunk);
+	}
+	assert_eq!(seen, expected_from(0));
+	Ok(())
+}
+
+/// A test for `KeyValueDB::get_by_prefix`.
+pub fn test_get_by_prefix(db: &dyn KeyValueDB) -> io::Result<()> {
+	let mut batch = db.transaction();
+	batch.put(0, b"cat", b"meow");
+	batch.put(0, b"dog", b"woof");
+	batch.put(0, b"dogfish", b"blub");
+	batch.put(0, b"doge", b"wow");
+	db.write(batch)?;
+
+	// a single key with the prefix
+	assert_eq!(db.get_by_prefix(0, b"ca")?, Some(b"meow".to_vec()));
+	// the whole key as the prefix
+	assert_eq!(db.get_by_prefix(0, b"cat")?, Some(b"meow".to_vec()));
+	// no key with the prefix, between and past the existing keys
+	assert_eq!(db.get_by_prefix(0, b"cow")?, None);
+	assert_eq!(db.get_by_prefix(0, b"eel")?, None);
+	assert!(!db.has_prefix(0, b"cow")?);
+	// several keys with the prefix, the first in sorted order is returned
+	assert_eq!(db.get_by_prefix(0, b"dog")?, Some(b"woof".to_vec()));
+	assert_eq!(db.get_by_prefix(0, b"dogf")?, Some(b"blub".to_vec()));
+	assert_eq!(db.get_by_prefix(0, b"do")?, Some(b"woof".to_vec()));
+	assert_eq!(db.get_by_prefix(0, b"")?, Some(b"meow".to_vec()));
+	assert!(db.has_prefix(0, b"do")?);
+	// the column must exist
+	assert!(db.get_by_prefix(1, b"dog").is_err());
+	Ok(())
+}
+
 /// The number of columns required to run `test_io_stats`.
 pub const IO_STATS_NUM_COLUMNS: u32 = 3;
 
@@ -174,6 +273,25 @@ pub fn test_io_stats(db: &dyn KeyValueDB) -> io::Result<()> {
 	Ok(())
 }
 
+/// The number of columns required to run `test_num_columns`.
+pub const NUM_COLUMNS_NUM_COLUMNS: u32 = 5;
+
+/// A test for `KeyValueDB::num_columns`.
+/// Assumes that the `db` has exactly 5 columns.
+pub fn test_num_columns(db: &dyn KeyValueDB) -> io::Result<()> {
+	assert_eq!(db.num_columns(), NUM_COLUMNS_NUM_COLUMNS);
+
+	// Every reported column is usable, and the next one does not exist.
+	for col in 0..db.num_columns() {
+		let mut transaction = db.transaction();
+		transaction.put(col, b"key", &[col as u8]);
+		db.write(transaction)?;
+		assert_eq!(db.get(col, b"key")?.unwrap(), vec![col as u8]);
+	}
+	assert!(db.get(db.num_columns(), b"key").is_err());
+	Ok(())
+}
+
 /// The number of columns required to run `test_delete_prefix`.
 pub const DELETE_PREFIX_NUM_COLUMNS: u32 = 7;
 
@@ -236,6 +354,75 @@ pub fn test_delete_prefix(db: &dyn KeyValueDB) -> io::Result<()> {
 	Ok(())
 }
 
+/// A test for `TypedColumn`, round-tripping structured keys and values.
+pub fn test_typed_column(db: &dyn KeyValueDB) -> io::Result<()> {
+	#[derive(Debug, PartialEq)]
+	struct BlockKey {
+		number: u64,
+		fork: u8,
+	}
+
+	#[derive(Debug, PartialEq)]
+	struct BlockInfo {
+		parent: u64,
+		name: String,
+	}
+
+	let key_codec = (
+		|key: &BlockKey| {
+			let mut bytes = key.number.to_be_bytes().to_vec();
+			bytes.push(key.fork);
+			bytes
+		},
+		|bytes: &[u8]| {
+			if bytes.len() != 9 {
+				return Err(io::Error::new(io::ErrorKind::InvalidData, "invalid key length"));
+			}
+			let mut number = [0u8; 8];
+			number.copy_from_slice(&bytes[..8]);
+			Ok(BlockKey { number: u64::from_be_bytes(number), fork: bytes[8] })
+		},
+	);
+	let value_codec = (
+		|info: &BlockInfo| {
+			let mut bytes = info.parent.to_le_bytes().to_vec();
+			bytes.extend_from_slice(info.name.as_bytes());
+			bytes
+		},
+		|bytes: &[u8]| {
+			if bytes.len() < 8 {
+				return Err(io::Error::new(io::ErrorKind::InvalidData, "value too short"));
+			}
+			let mut parent = [0u8; 8];
+			parent.copy_from_slice(&bytes[..8]);
+			let name =
+				String::from_utf8(bytes[8..].to_vec()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+			Ok(BlockInfo { parent: u64::from_le_bytes(parent), name })
+		},
+	);
+	let column = TypedColumn::new(db, 0, key_codec, value_codec);
+
+	let key = BlockKey { number: 42, fork: 1 };
+	let info = BlockInfo { parent: 41, name: "horse".into() };
+	assert_eq!(column.get(&key)?, None);
+	column.put(&key, &info)?;
+	assert!(column.has_key(&key)?);
+	assert_eq!(column.get(&key)?, Some(info));
+	assert_eq!(db.get(0, &[0, 0, 0, 0, 0, 0, 0, 42, 1])?.unwrap()[8..], *b"horse");
+	assert_eq!(column.get(&BlockKey { number: 42, fork: 0 })?, None);
+
+	column.delete(&key)?;
+	assert!(!column.has_key(&key)?);
+	assert_eq!(column.get(&key)?, None);
+
+	// Values which fail to decode are reported as errors.
+	let mut transaction = db.transaction();
+	transaction.put(0, &[0, 0, 0, 0, 0, 0, 0, 7, 0], b"short");
+	db.write(transaction)?;
+	assert_eq!(column.get(&BlockKey { number: 7, fork: 0 }).unwrap_err().kind(), io::ErrorKind::InvalidData);
+	Ok(())
+}
+
 /// A complex test.
 pub fn test_complex(db: &dyn KeyValueDB) -> io::Result<()> {
 	let key1 = b"02c69be41d0b7e40352fc85be1cd65eb03d40ef8427a0ca4596b1ead9a00e9fc";
@@ -283,8 +470,8 @@ pub fn test_complex(db: &dyn KeyValueDB) -> io::Result<()> {
 	assert!(db.get(0, key1)?.is_none());
 	assert_eq!(&*db.get(0, key3)?.unwrap(), b"elephant");
 
-	assert_eq!(&*db.get_by_prefix(0, key3).unwrap(), b"elephant");
-	assert_eq!(&*db.get_by_prefix(0, key2).unwrap(), b"dog");
+	assert_eq!(&*db.get_by_prefix(0, key3)?.unwrap(), b"elephant");
+	assert_eq!(&*db.get_by_prefix(0, key2)?.unwrap(), b"dog");
 
 	let mut transaction = db.transaction();
 	transaction.put(0, key1, b"horse");
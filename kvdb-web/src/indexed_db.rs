@@ -18,7 +18,7 @@ use futures::prelude::*;
 use kvdb::{DBOp, DBTransaction};
 
 use log::{debug, warn};
-use std::ops::Deref;
+use std::{io, ops::Deref};
 
 use crate::error::Error;
 
@@ -114,14 +114,20 @@ fn try_create_missing_stores(req: &IdbOpenDbRequest, columns: u32, version: Opti
 }
 
 /// Commit a transaction to the IndexedDB.
-pub fn idb_commit_transaction(idb: &IdbDatabase, txn: &DBTransaction, columns: u32) -> impl Future<Output = ()> {
+/// Queues the operations of `txn` in an IndexedDB transaction, which is aborted if any of
+/// them is rejected. The returned future resolves once the transaction is committed.
+pub fn idb_commit_transaction(
+	idb: &IdbDatabase,
+	txn: &DBTransaction,
+	columns: u32,
+) -> io::Result<impl Future<Output = ()>> {
 	let store_names_js = store_names_js(columns);
 
 	// Create a transaction
 	let mode = IdbTransactionMode::Readwrite;
 	let idb_txn = idb
 		.transaction_with_str_sequence_and_mode(&store_names_js, mode)
-		.expect("The provided mode and store names are valid; qed");
+		.map_err(|err| other_io_err(format!("failed to create an IndexedDB transaction: {:?}", err)))?;
 
 	// Open object stores (columns)
 	let object_stores = (0..columns)
@@ -132,45 +138,49 @@ pub fn idb_commit_transaction(idb: &IdbDatabase, txn: &DBTransaction, columns: u
 		})
 		.collect::<Vec<_>>();
 
-	for op in &txn.ops {
-		match op {
-			DBOp::Insert { col, key, value } => {
-				let column = *col as usize;
-				// Convert rust bytes to js arrays
-				let key_js = Uint8Array::from(key.as_ref());
-				let val_js = Uint8Array::from(value.as_ref());
-
-				// Insert key/value pair into the object store
-				let res = object_stores[column].put_with_key(val_js.as_ref(), key_js.as_ref());
-				if let Err(err) = res {
-					warn!("error inserting key/values into col_{}: {:?}", column, err);
-				}
-			}
-			DBOp::Delete { col, key } => {
-				let column = *col as usize;
-				// Convert rust bytes to js arrays
-				let key_js = Uint8Array::from(key.as_ref());
-
-				// Delete key/value pair from the object store
-				let res = object_stores[column].delete(key_js.as_ref());
-				if let Err(err) = res {
-					warn!("error deleting key from col_{}: {:?}", column, err);
-				}
-			}
-			DBOp::DeletePrefix { col, prefix } => {
-				let column = *col as usize;
-				// Convert rust bytes to js arrays
-				let prefix_js_start = Uint8Array::from(prefix.as_ref());
-				let prefix_js_end = Uint8Array::from(prefix.as_ref());
-
-				let range = IdbKeyRange::bound(prefix_js_start.as_ref(), prefix_js_end.as_ref())
-					.expect("Starting and ending at same value is valid bound; qed");
-				let res = object_stores[column].delete(range.as_ref());
-				if let Err(err) = res {
-					warn!("error deleting prefix from col_{}: {:?}", column, err);
-				}
-			}
+	let queued = txn.ops.iter().try_for_each(|op| match op {
+		DBOp::Insert { col, key, value } => {
+			let column = *col as usize;
+			// Convert rust bytes to js arrays
+			let key_js = Uint8Array::from(key.as_ref());
+			let val_js = Uint8Array::from(value.as_ref());
+
+			// Insert key/value pair into the object store
+			object_stores[column]
+				.put_with_key(val_js.as_ref(), key_js.as_ref())
+				.map(|_| ())
+				.map_err(|err| other_io_err(format!("error inserting key/values into col_{}: {:?}", column, err)))
+		}
+		DBOp::Delete { col, key } => {
+			let column = *col as usize;
+			// Convert rust bytes to js arrays
+			let key_js = Uint8Array::from(key.as_ref());
+
+			// Delete key/value pair from the object store
+			object_stores[column]
+				.delete(key_js.as_ref())
+				.map(|_| ())
+				.map_err(|err| other_io_err(format!("error deleting key from col_{}: {:?}", column, err)))
 		}
+		DBOp::DeletePrefix { col, prefix } => {
+			let column = *col as usize;
+			// Convert rust bytes to js arrays
+			let prefix_js_start = Uint8Array::from(prefix.as_ref());
+			let prefix_js_end = Uint8Array::from(prefix.as_ref());
+
+			let range = IdbKeyRange::bound(prefix_js_start.as_ref(), prefix_js_end.as_ref())
+				.expect("Starting and ending at same value is valid bound; qed");
+			object_stores[column]
+				.delete(range.as_ref())
+				.map(|_| ())
+				.map_err(|err| other_io_err(format!("error deleting prefix from col_{}: {:?}", column, err)))
+		}
+		DBOp::Merge { .. } => unreachable!("merge operations are rejected by `Database::write`; qed"),
+	});
+	if let Err(err) = queued {
+		// Nothing of a rejected batch is written.
+		let _ = idb_txn.abort();
+		return Err(err);
 	}
 
 	let (tx, rx) = channel::oneshot::channel::<()>();
@@ -187,7 +197,11 @@ pub fn idb_commit_transaction(idb: &IdbDatabase, txn: &DBTransaction, columns: u
 	idb_txn.set_onerror(Some(on_error.as_ref().unchecked_ref()));
 	on_error.forget();
 
-	rx.map(|_| ())
+	Ok(rx.map(|_| ()))
+}
+
+fn other_io_err(message: String) -> io::Error {
+	io::Error::new(io::ErrorKind::Other, message)
 }
 
 /// Returns a cursor to a database column with the given column number.
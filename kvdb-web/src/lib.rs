@@ -16,7 +16,7 @@
 mod error;
 mod indexed_db;
 
-use kvdb::{DBTransaction, DBValue};
+use kvdb::{DBOp, DBTransaction, DBValue};
 use kvdb_memorydb::{self as in_memory, InMemory};
 use send_wrapper::SendWrapper;
 use std::io;
@@ -98,13 +98,18 @@ impl KeyValueDB for Database {
 		self.in_memory.get(col, key)
 	}
 
-	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> Option<Box<[u8]>> {
+	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<Option<DBValue>> {
 		self.in_memory.get_by_prefix(col, prefix)
 	}
 
 	fn write(&self, transaction: DBTransaction) -> io::Result<()> {
-		let _ = indexed_db::idb_commit_transaction(&*self.indexed_db, &transaction, self.columns);
-		self.in_memory.write(transaction)
+		if transaction.ops.iter().any(|op| matches!(op, DBOp::Merge { .. })) {
+			return Err(io::Error::new(io::ErrorKind::Other, "Merge is not supported yet"));
+		}
+		// The in-memory database rejects invalid batches, which must not reach IndexedDB.
+		self.in_memory.write(transaction.clone())?;
+		// The commit completes in the background, only failures to queue the batch are reported.
+		indexed_db::idb_commit_transaction(&*self.indexed_db, &transaction, self.columns).map(drop)
 	}
 
 	// NOTE: clones the whole db
@@ -121,8 +126,16 @@ impl KeyValueDB for Database {
 		self.in_memory.iter_with_prefix(col, prefix)
 	}
 
+	fn iter_from<'a>(&'a self, col: u32, start: &'a [u8]) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a> {
+		self.in_memory.iter_from(col, start)
+	}
+
 	// NOTE: not supported
 	fn restore(&self, _new_db: &str) -> std::io::Result<()> {
 		Err(io::Error::new(io::ErrorKind::Other, "Not supported yet"))
 	}
+
+	fn num_columns(&self) -> u32 {
+		self.columns
+	}
 }
@@ -57,12 +57,30 @@ async fn iter_with_prefix() {
 	st::test_iter_with_prefix(&db).unwrap()
 }
 
+#[wasm_bindgen_test]
+async fn iter_from() {
+	let db = open_db(1, "iter_from").await;
+	st::test_iter_from(&db).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn get_by_prefix() {
+	let db = open_db(1, "get_by_prefix").await;
+	st::test_get_by_prefix(&db).unwrap()
+}
+
 #[wasm_bindgen_test]
 async fn complex() {
 	let db = open_db(1, "complex").await;
 	st::test_complex(&db).unwrap()
 }
 
+#[wasm_bindgen_test]
+async fn num_columns() {
+	let db = open_db(st::NUM_COLUMNS_NUM_COLUMNS, "num_columns").await;
+	st::test_num_columns(&db).unwrap()
+}
+
 #[wasm_bindgen_test]
 async fn reopen_the_database_with_more_columns() {
 	let _ = console_log::init_with_level(log::Level::Trace);
@@ -12,6 +12,8 @@ use smallvec::SmallVec;
 use std::io;
 
 mod io_stats;
+mod typed;
+mod write_report;
 
 /// Required length of prefixes.
 pub const PREFIX_LEN: usize = 12;
@@ -22,6 +24,8 @@ pub type DBValue = Vec<u8>;
 pub type DBKey = SmallVec<[u8; 32]>;
 
 pub use io_stats::{IoStats, Kind as IoStatsKind};
+pub use typed::{Codec, TypedColumn};
+pub use write_report::{validate_transaction, InvalidOp, InvalidOpReason, WriteReport};
 
 /// Write transaction. Batches a sequence of put/delete operations for efficiency.
 #[derive(Default, Clone, PartialEq)]
@@ -36,6 +40,7 @@ pub enum DBOp {
 	Insert { col: u32, key: DBKey, value: DBValue },
 	Delete { col: u32, key: DBKey },
 	DeletePrefix { col: u32, prefix: DBKey },
+	Merge { col: u32, key: DBKey, operand: DBValue },
 }
 
 impl DBOp {
@@ -45,6 +50,7 @@ impl DBOp {
 			DBOp::Insert { ref key, .. } => key,
 			DBOp::Delete { ref key, .. } => key,
 			DBOp::DeletePrefix { ref prefix, .. } => prefix,
+			DBOp::Merge { ref key, .. } => key,
 		}
 	}
 
@@ -54,6 +60,7 @@ impl DBOp {
 			DBOp::Insert { col, .. } => col,
 			DBOp::Delete { col, .. } => col,
 			DBOp::DeletePrefix { col, .. } => col,
+			DBOp::Merge { col, .. } => col,
 		}
 	}
 }
@@ -90,6 +97,12 @@ impl DBTransaction {
 	pub fn delete_prefix(&mut self, col: u32, prefix: &[u8]) {
 		self.ops.push(DBOp::DeletePrefix { col, prefix: DBKey::from_slice(prefix) });
 	}
+
+	/// Merge an operand into the value of a key, using the merge operator of the column.
+	/// Only supported by databases which allow configuring merge operators.
+	pub fn merge(&mut self, col: u32, key: &[u8], operand: &[u8]) {
+		self.ops.push(DBOp::Merge { col, key: DBKey::from_slice(key), operand: operand.to_vec() });
+	}
 }
 
 /// Generic key-value database.
@@ -110,12 +123,29 @@ pub trait KeyValueDB: Sync + Send + parity_util_mem::MallocSizeOf {
 	/// Get a value by key.
 	fn get(&self, col: u32, key: &[u8]) -> io::Result<Option<DBValue>>;
 
-	/// Get the first value matching the given prefix.
-	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> Option<Box<[u8]>>;
+	/// Get the value of the first key, in sorted order, starting with the given prefix.
+	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<Option<DBValue>>;
 
 	/// Write a transaction of changes to the backing store.
 	fn write(&self, transaction: DBTransaction) -> io::Result<()>;
 
+	/// Write a transaction like `write`, reporting the numbers of operations applied.
+	///
+	/// The operations are validated first: if one refers to a column which does not exist,
+	/// or has a key longer than `max_key_len`, nothing is written and the returned error
+	/// holds an `InvalidOp` with its index.
+	fn write_with_report(&self, transaction: DBTransaction) -> io::Result<WriteReport> {
+		validate_transaction(&transaction, self.num_columns(), self.max_key_len())?;
+		let report = WriteReport::new(&transaction);
+		self.write(transaction)?;
+		Ok(report)
+	}
+
+	/// The maximum length of keys accepted by the database, if it limits them.
+	fn max_key_len(&self) -> Option<usize> {
+		None
+	}
+
 	/// Iterate over the data for a given column.
 	fn iter<'a>(&'a self, col: u32) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a>;
 
@@ -127,9 +157,22 @@ pub trait KeyValueDB: Sync + Send + parity_util_mem::MallocSizeOf {
 		prefix: &'a [u8],
 	) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a>;
 
+	/// Iterate over the data for a given column in key order, starting at the first key
+	/// which is greater than or equal to `start`.
+	///
+	/// This allows resuming a scan after the last key seen, e.g. by passing that key with
+	/// a zero byte appended. The default implementation skips the keys before `start`
+	/// from `iter`, so implementations which can seek should override it.
+	fn iter_from<'a>(&'a self, col: u32, start: &'a [u8]) -> Box<dyn Iterator<Item = (Box<[u8]>, Box<[u8]>)> + 'a> {
+		Box::new(self.iter(col).skip_while(move |(key, _)| &**key < start))
+	}
+
 	/// Attempt to replace this database with a new one located at the given path.
 	fn restore(&self, new_db: &str) -> io::Result<()>;
 
+	/// The number of columns in the database, which are numbered from `0`.
+	fn num_columns(&self) -> u32;
+
 	/// Query statistics.
 	///
 	/// Not all kvdb implementations are able or expected to implement this, so by
@@ -146,8 +189,8 @@ pub trait KeyValueDB: Sync + Send + parity_util_mem::MallocSizeOf {
 	}
 
 	/// Check for the existence of a value by prefix.
-	fn has_prefix(&self, col: u32, prefix: &[u8]) -> bool {
-		self.get_by_prefix(col, prefix).is_some()
+	fn has_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<bool> {
+		self.get_by_prefix(col, prefix).map(|opt| opt.is_some())
 	}
 }
 
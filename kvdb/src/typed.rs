@@ -0,0 +1,86 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Typed access to a single column of a key-value database.
+
+use crate::KeyValueDB;
+use std::{io, marker::PhantomData};
+
+/// Converts values of type `T` to and from their database representation.
+///
+/// Implemented for pairs of closures `(encode, decode)`.
+pub trait Codec<T> {
+	/// Encodes `value` into bytes.
+	fn encode(&self, value: &T) -> Vec<u8>;
+	/// Decodes a value from `bytes`.
+	fn decode(&self, bytes: &[u8]) -> io::Result<T>;
+}
+
+impl<T, E, D> Codec<T> for (E, D)
+where
+	E: Fn(&T) -> Vec<u8>,
+	D: Fn(&[u8]) -> io::Result<T>,
+{
+	fn encode(&self, value: &T) -> Vec<u8> {
+		(self.0)(value)
+	}
+
+	fn decode(&self, bytes: &[u8]) -> io::Result<T> {
+		(self.1)(bytes)
+	}
+}
+
+/// A column of a `KeyValueDB` with keys of type `K` and values of type `V`.
+///
+/// Keys and values are encoded with `KC` and `VC` respectively. Each `put` and `delete`
+/// is written to the database as a transaction of its own.
+pub struct TypedColumn<'a, K, V, KC, VC> {
+	db: &'a dyn KeyValueDB,
+	col: u32,
+	key_codec: KC,
+	value_codec: VC,
+	_marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<'a, K, V, KC, VC> TypedColumn<'a, K, V, KC, VC>
+where
+	KC: Codec<K>,
+	VC: Codec<V>,
+{
+	/// Creates a typed view of column `col` of `db`.
+	pub fn new(db: &'a dyn KeyValueDB, col: u32, key_codec: KC, value_codec: VC) -> Self {
+		TypedColumn { db, col, key_codec, value_codec, _marker: PhantomData }
+	}
+
+	/// Get the value stored under `key`, if any.
+	pub fn get(&self, key: &K) -> io::Result<Option<V>> {
+		match self.db.get(self.col, &self.key_codec.encode(key))? {
+			Some(value) => self.value_codec.decode(&value).map(Some),
+			None => Ok(None),
+		}
+	}
+
+	/// Check for the existence of a value for `key`.
+	pub fn has_key(&self, key: &K) -> io::Result<bool> {
+		self.db.has_key(self.col, &self.key_codec.encode(key))
+	}
+
+	/// Store `value` under `key`.
+	pub fn put(&self, key: &K, value: &V) -> io::Result<()> {
+		let mut transaction = self.db.transaction();
+		transaction.put_vec(self.col, &self.key_codec.encode(key), self.value_codec.encode(value));
+		self.db.write(transaction)
+	}
+
+	/// Delete the value stored under `key`.
+	pub fn delete(&self, key: &K) -> io::Result<()> {
+		let mut transaction = self.db.transaction();
+		transaction.delete(self.col, &self.key_codec.encode(key));
+		self.db.write(transaction)
+	}
+}
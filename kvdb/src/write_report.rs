@@ -0,0 +1,108 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Reporting on the operations of written transactions.
+
+use crate::{DBOp, DBTransaction};
+use std::{error, fmt, io};
+
+/// The numbers of operations of each kind applied by `KeyValueDB::write_with_report`.
+///
+/// Transactions are written atomically, so either all of their operations are applied,
+/// or none.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WriteReport {
+	/// Number of applied `DBOp::Insert`s.
+	pub puts: usize,
+	/// Number of applied `DBOp::Delete`s.
+	pub deletes: usize,
+	/// Number of applied `DBOp::DeletePrefix`es.
+	pub prefix_deletes: usize,
+	/// Number of applied `DBOp::Merge`s.
+	pub merges: usize,
+}
+
+impl WriteReport {
+	/// Counts the operations of `transaction`.
+	pub fn new(transaction: &DBTransaction) -> Self {
+		let mut report = WriteReport::default();
+		for op in &transaction.ops {
+			match op {
+				DBOp::Insert { .. } => report.puts += 1,
+				DBOp::Delete { .. } => report.deletes += 1,
+				DBOp::DeletePrefix { .. } => report.prefix_deletes += 1,
+				DBOp::Merge { .. } => report.merges += 1,
+			}
+		}
+		report
+	}
+}
+
+/// Why an operation was rejected before writing its transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidOpReason {
+	/// The column of the operation does not exist.
+	NoSuchColumn(u32),
+	/// The key or prefix of the operation is longer than the database allows.
+	KeyTooLong { len: usize, max: usize },
+}
+
+/// An operation of a transaction which was rejected before writing it.
+///
+/// Returned by `KeyValueDB::write_with_report` as the inner error of an `io::Error` of
+/// kind `InvalidInput`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidOp {
+	/// Index of the operation in `DBTransaction::ops`.
+	pub index: usize,
+	/// Why the operation was rejected.
+	pub reason: InvalidOpReason,
+}
+
+impl fmt::Display for InvalidOp {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.reason {
+			InvalidOpReason::NoSuchColumn(col) => write!(f, "Operation {}: no such column family: {}", self.index, col),
+			InvalidOpReason::KeyTooLong { len, max } => {
+				write!(f, "Operation {}: key of {} bytes exceeds the maximum of {}", self.index, len, max)
+			}
+		}
+	}
+}
+
+impl error::Error for InvalidOp {}
+
+impl From<InvalidOp> for io::Error {
+	fn from(err: InvalidOp) -> Self {
+		io::Error::new(io::ErrorKind::InvalidInput, err)
+	}
+}
+
+/// Fails with the first operation of `transaction` which cannot be written to a database
+/// with `num_columns` columns and keys of at most `max_key_len` bytes.
+///
+/// This is the validation of `KeyValueDB::write_with_report`, for databases which also
+/// validate transactions written with `KeyValueDB::write`.
+pub fn validate_transaction(
+	transaction: &DBTransaction,
+	num_columns: u32,
+	max_key_len: Option<usize>,
+) -> Result<(), InvalidOp> {
+	for (index, op) in transaction.ops.iter().enumerate() {
+		if op.col() >= num_columns {
+			return Err(InvalidOp { index, reason: InvalidOpReason::NoSuchColumn(op.col()) });
+		}
+		match max_key_len {
+			Some(max) if op.key().len() > max => {
+				return Err(InvalidOp { index, reason: InvalidOpReason::KeyTooLong { len: op.key().len(), max } })
+			}
+			_ => (),
+		}
+	}
+	Ok(())
+}
@@ -0,0 +1,77 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Borsh serialization support for uint and fixed hash.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[doc(hidden)]
+pub use borsh;
+
+use borsh::maybestd::io;
+
+/// Splits off the first `len` bytes of `buf`, advancing it past them.
+///
+/// Fails without consuming anything if `buf` is shorter than `len`.
+#[doc(hidden)]
+pub fn take_bytes<'a>(buf: &mut &'a [u8], len: usize) -> io::Result<&'a [u8]> {
+	if buf.len() < len {
+		return Err(io::Error::new(io::ErrorKind::InvalidInput, "Unexpected length of input"));
+	}
+	let (bytes, rest) = buf.split_at(len);
+	*buf = rest;
+	Ok(bytes)
+}
+
+/// Add Borsh serialization support to an integer created by `construct_uint!`.
+///
+/// The integer is encoded as `$name::BYTES` little-endian bytes.
+#[macro_export]
+macro_rules! impl_uint_borsh {
+	($name: ident, $len: expr) => {
+		impl $crate::borsh::BorshSerialize for $name {
+			fn serialize<W: $crate::borsh::maybestd::io::Write>(
+				&self,
+				writer: &mut W,
+			) -> $crate::borsh::maybestd::io::Result<()> {
+				let mut bytes = [0u8; $name::BYTES];
+				self.to_little_endian(&mut bytes);
+				writer.write_all(&bytes)
+			}
+		}
+
+		impl $crate::borsh::BorshDeserialize for $name {
+			fn deserialize(buf: &mut &[u8]) -> $crate::borsh::maybestd::io::Result<Self> {
+				$crate::take_bytes(buf, $name::BYTES).map($name::from_little_endian)
+			}
+		}
+	};
+}
+
+/// Add Borsh serialization support to a fixed-sized hash type created by `construct_fixed_hash!`.
+///
+/// The hash is encoded as its raw `$len` bytes, the same as a `[u8; $len]`.
+#[macro_export]
+macro_rules! impl_fixed_hash_borsh {
+	($name: ident, $len: expr) => {
+		impl $crate::borsh::BorshSerialize for $name {
+			fn serialize<W: $crate::borsh::maybestd::io::Write>(
+				&self,
+				writer: &mut W,
+			) -> $crate::borsh::maybestd::io::Result<()> {
+				writer.write_all(&self.0)
+			}
+		}
+
+		impl $crate::borsh::BorshDeserialize for $name {
+			fn deserialize(buf: &mut &[u8]) -> $crate::borsh::maybestd::io::Result<Self> {
+				$crate::take_bytes(buf, $len).map($name::from_slice)
+			}
+		}
+	};
+}
@@ -10,16 +10,126 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
 #[doc(hidden)]
 pub use parity_scale_codec as codec;
 
+use alloc::vec::Vec;
+use codec::{Compact, Decode, Encode, EncodeAsRef, Error, Input, Output};
+use core::{cmp, mem};
+
+/// Most bytes reserved up front by `decode_fixed_width_vec` when the length of the input is unknown.
+const MAX_PREALLOCATION: usize = 4 * 1024;
+
+/// Decodes a `Vec<T>` like its `Decode` impl, for types such as integers and hashes whose encoding
+/// takes exactly `T::encoded_fixed_size()` bytes.
+///
+/// The length prefix is checked against the remaining input before reserving room for the items,
+/// so that a bogus length in untrusted input fails instead of allocating a huge buffer. If the
+/// input doesn't know its remaining length, or `T` doesn't have a fixed encoded size, at most a
+/// few kilobytes are reserved up front.
+///
+/// ```
+/// use impl_codec::{codec::Encode, decode_fixed_width_vec};
+/// let encoded = vec![[1u8; 32], [2u8; 32]].encode();
+/// let decoded: Vec<[u8; 32]> = decode_fixed_width_vec(&mut &encoded[..]).unwrap();
+/// assert_eq!(decoded, vec![[1u8; 32], [2u8; 32]]);
+/// assert!(decode_fixed_width_vec::<[u8; 32], _>(&mut &encoded[..encoded.len() - 1]).is_err());
+/// ```
+pub fn decode_fixed_width_vec<T: Decode, I: Input>(input: &mut I) -> Result<Vec<T>, Error> {
+	let len = Compact::<u32>::decode(input)?.0 as usize;
+	let capacity = match (T::encoded_fixed_size(), input.remaining_len()?) {
+		(Some(item_len), Some(remaining)) => {
+			if len.checked_mul(item_len).is_none_or(|needed| needed > remaining) {
+				return Err("Vec length exceeds the remaining input".into());
+			}
+			len
+		}
+		_ => cmp::min(len, MAX_PREALLOCATION / cmp::max(mem::size_of::<T>(), 1)),
+	};
+	let mut items = Vec::with_capacity(capacity);
+	for _ in 0..len {
+		items.push(T::decode(input)?);
+	}
+	Ok(items)
+}
+
+/// Conversion of an integer created by `construct_uint!` from and to `u128`, used for compact encoding.
+#[doc(hidden)]
+pub trait CompactUintValue: Sized {
+	fn to_u128(&self) -> Option<u128>;
+	fn from_u128(value: u128) -> Option<Self>;
+}
+
+/// Compact encoding of an integer created by `construct_uint!`.
+///
+/// This is the `HasCompact::Type` of integers with `impl_uint_codec!`, so they can be used with
+/// `#[codec(compact)]`. Values are encoded exactly as `Compact<u128>`, so only values up to
+/// `u128::MAX` can be encoded; encoding a larger value panics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactUint<T>(pub T);
+
+/// Borrowed compact encoding of an integer created by `construct_uint!`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactUintRef<'a, T>(pub &'a T);
+
+impl<'a, T: CompactUintValue> CompactUintRef<'a, T> {
+	fn value(&self) -> u128 {
+		self.0.to_u128().expect("value exceeds the maximum of compact encoding")
+	}
+}
+
+impl<'a, T> From<&'a T> for CompactUintRef<'a, T> {
+	fn from(value: &'a T) -> Self {
+		CompactUintRef(value)
+	}
+}
+
+impl<'a, T: CompactUintValue> Encode for CompactUintRef<'a, T> {
+	fn size_hint(&self) -> usize {
+		Compact(self.value()).size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		Compact(self.value()).encode_to(dest)
+	}
+}
+
+impl<T> From<T> for CompactUint<T> {
+	fn from(value: T) -> Self {
+		CompactUint(value)
+	}
+}
+
+impl<T: CompactUintValue> Encode for CompactUint<T> {
+	fn size_hint(&self) -> usize {
+		CompactUintRef(&self.0).size_hint()
+	}
+
+	fn encode_to<W: Output + ?Sized>(&self, dest: &mut W) {
+		CompactUintRef(&self.0).encode_to(dest)
+	}
+}
+
+impl<'a, T: 'a + CompactUintValue> EncodeAsRef<'a, T> for CompactUint<T> {
+	type RefType = CompactUintRef<'a, T>;
+}
+
+impl<T: CompactUintValue> Decode for CompactUint<T> {
+	fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+		let value = Compact::<u128>::decode(input)?.0;
+		T::from_u128(value).map(CompactUint).ok_or_else(|| "compact value out of range".into())
+	}
+}
+
 /// Add Parity Codec serialization support to an integer created by `construct_uint!`.
 #[macro_export]
 macro_rules! impl_uint_codec {
 	($name: ident, $len: expr) => {
 		impl $crate::codec::Encode for $name {
 			fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
-				let mut bytes = [0u8; $len * 8];
+				let mut bytes = [0u8; $name::BYTES];
 				self.to_little_endian(&mut bytes);
 				bytes.using_encoded(f)
 			}
@@ -29,7 +139,44 @@ macro_rules! impl_uint_codec {
 
 		impl $crate::codec::Decode for $name {
 			fn decode<I: $crate::codec::Input>(input: &mut I) -> core::result::Result<Self, $crate::codec::Error> {
-				<[u8; $len * 8] as $crate::codec::Decode>::decode(input).map(|b| $name::from_little_endian(&b))
+				<[u8; $name::BYTES] as $crate::codec::Decode>::decode(input).map(|b| $name::from_little_endian(&b))
+			}
+
+			fn encoded_fixed_size() -> core::option::Option<usize> {
+				Some($name::BYTES)
+			}
+		}
+
+		impl $crate::CompactUintValue for $name {
+			fn to_u128(&self) -> core::option::Option<u128> {
+				let mut bytes = [0u8; $name::BYTES];
+				self.to_little_endian(&mut bytes);
+				let len = core::cmp::min($name::BYTES, 16);
+				if bytes[len..].iter().any(|&b| b != 0) {
+					return None;
+				}
+				let mut low = [0u8; 16];
+				low[..len].copy_from_slice(&bytes[..len]);
+				Some(u128::from_le_bytes(low))
+			}
+
+			fn from_u128(value: u128) -> core::option::Option<Self> {
+				let bytes = value.to_le_bytes();
+				let len = core::cmp::min($name::BYTES, 16);
+				if bytes[len..].iter().any(|&b| b != 0) {
+					return None;
+				}
+				Some($name::from_little_endian(&bytes[..len]))
+			}
+		}
+
+		impl $crate::codec::HasCompact for $name {
+			type Type = $crate::CompactUint<$name>;
+		}
+
+		impl From<$crate::CompactUint<$name>> for $name {
+			fn from(value: $crate::CompactUint<$name>) -> Self {
+				value.0
 			}
 		}
 	};
@@ -51,6 +198,10 @@ macro_rules! impl_fixed_hash_codec {
 			fn decode<I: $crate::codec::Input>(input: &mut I) -> core::result::Result<Self, $crate::codec::Error> {
 				<[u8; $len] as $crate::codec::Decode>::decode(input).map($name)
 			}
+
+			fn encoded_fixed_size() -> core::option::Option<usize> {
+				Some($len)
+			}
 		}
 	};
 }
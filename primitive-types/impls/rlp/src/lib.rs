@@ -22,11 +22,15 @@ macro_rules! impl_uint_rlp {
 	($name: ident, $size: expr) => {
 		impl $crate::rlp::Encodable for $name {
 			fn rlp_append(&self, s: &mut $crate::rlp::RlpStream) {
-				let leading_empty_bytes = $size * 8 - (self.bits() + 7) / 8;
-				let mut buffer = [0u8; $size * 8];
+				let leading_empty_bytes = $name::BYTES - (self.bits() + 7) / 8;
+				let mut buffer = [0u8; $name::BYTES];
 				self.to_big_endian(&mut buffer);
 				s.encoder().encode_value(&buffer[leading_empty_bytes..]);
 			}
+
+			fn rlp_len_hint(&self) -> Option<usize> {
+				Some($crate::rlp::encoded_len_bound($name::BYTES))
+			}
 		}
 
 		impl $crate::rlp::Decodable for $name {
@@ -34,7 +38,7 @@ macro_rules! impl_uint_rlp {
 				rlp.decoder().decode_value(|bytes| {
 					if !bytes.is_empty() && bytes[0] == 0 {
 						Err($crate::rlp::DecoderError::RlpInvalidIndirection)
-					} else if bytes.len() <= $size * 8 {
+					} else if bytes.len() <= $name::BYTES {
 						Ok($name::from(bytes))
 					} else {
 						Err($crate::rlp::DecoderError::RlpIsTooBig)
@@ -53,6 +57,10 @@ macro_rules! impl_fixed_hash_rlp {
 			fn rlp_append(&self, s: &mut $crate::rlp::RlpStream) {
 				s.encoder().encode_value(self.as_ref());
 			}
+
+			fn rlp_len_hint(&self) -> Option<usize> {
+				Some($crate::rlp::encoded_len_bound($size))
+			}
 		}
 
 		impl $crate::rlp::Decodable for $name {
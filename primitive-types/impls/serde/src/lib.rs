@@ -31,8 +31,8 @@ macro_rules! impl_uint_serde {
 			where
 				S: $crate::serde::Serializer,
 			{
-				let mut slice = [0u8; 2 + 2 * $len * 8];
-				let mut bytes = [0u8; $len * 8];
+				let mut slice = [0u8; 2 + 2 * $name::BYTES];
+				let mut bytes = [0u8; $name::BYTES];
 				self.to_big_endian(&mut bytes);
 				$crate::serialize::serialize_uint(&mut slice, &bytes, serializer)
 			}
@@ -43,7 +43,7 @@ macro_rules! impl_uint_serde {
 			where
 				D: $crate::serde::Deserializer<'de>,
 			{
-				let mut bytes = [0u8; $len * 8];
+				let mut bytes = [0u8; $name::BYTES];
 				let wrote = $crate::serialize::deserialize_check_len(
 					deserializer,
 					$crate::serialize::ExpectedLen::Between(0, &mut bytes),
@@ -55,6 +55,9 @@ macro_rules! impl_uint_serde {
 }
 
 /// Add Serde serialization support to a fixed-sized hash type created by `construct_fixed_hash!`.
+///
+/// Hashes are serialized as `0x`-prefixed hex and deserialized from hex of the exact length,
+/// with or without the prefix. See `serialize::bare` for serializing without the prefix.
 #[macro_export]
 macro_rules! impl_fixed_hash_serde {
 	($name: ident, $len: expr) => {
@@ -74,10 +77,7 @@ macro_rules! impl_fixed_hash_serde {
 				D: $crate::serde::Deserializer<'de>,
 			{
 				let mut bytes = [0u8; $len];
-				$crate::serialize::deserialize_check_len(
-					deserializer,
-					$crate::serialize::ExpectedLen::Exact(&mut bytes),
-				)?;
+				$crate::serialize::deserialize_exact(deserializer, &mut bytes)?;
 				Ok($name(bytes))
 			}
 		}
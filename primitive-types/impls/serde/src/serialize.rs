@@ -100,22 +100,22 @@ pub fn from_hex(v: &str) -> Result<Vec<u8>, FromHexError> {
 	}
 
 	let mut bytes = vec![0u8; (v.len() - 1) / 2];
-	from_hex_raw(v, &mut bytes)?;
+	from_hex_raw(&v[2..], 2, &mut bytes)?;
 	Ok(bytes)
 }
 
-/// Decode given 0x-prefixed hex string into provided slice.
-/// Used internally by `from_hex` and `deserialize_check_len`.
+/// Decode given hex digits, with any prefix already stripped, into provided slice.
+/// Used internally by `from_hex`, `deserialize_check_len` and `deserialize_exact`.
 ///
-/// The method will panic if:
-/// 1. `v` is shorter than 2 characters (you need to check 0x prefix outside).
-/// 2. `bytes` have incorrect length (make sure to allocate enough beforehand).
-fn from_hex_raw<'a>(v: &str, bytes: &mut [u8]) -> Result<usize, FromHexError> {
-	let bytes_len = v.len() - 2;
-	let mut modulus = bytes_len % 2;
+/// `offset` is the length of the stripped prefix, so that errors point into the original string.
+///
+/// The method will panic if `bytes` have incorrect length (make sure to allocate enough beforehand).
+fn from_hex_raw(digits: &str, offset: usize, bytes: &mut [u8]) -> Result<usize, FromHexError> {
+	let mut modulus = digits.len() % 2;
 	let mut buf = 0;
 	let mut pos = 0;
-	for (index, byte) in v.bytes().enumerate().skip(2) {
+	for (index, byte) in digits.bytes().enumerate() {
+		let index = index + offset;
 		buf <<= 4;
 
 		match byte {
@@ -262,7 +262,7 @@ where
 				ExpectedLen::Between(_, slice) => slice,
 			};
 
-			from_hex_raw(v, bytes).map_err(E::custom)
+			from_hex_raw(&v[2..], 2, bytes).map_err(E::custom)
 		}
 
 		fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
@@ -273,14 +273,174 @@ where
 	deserializer.deserialize_str(Visitor { len })
 }
 
+/// Deserialize a hex string of exactly `bytes.len()` bytes into `bytes`.
+///
+/// Unlike `deserialize_check_len`, the `0x` prefix is optional.
+pub fn deserialize_exact<'a, 'de, D>(deserializer: D, bytes: &'a mut [u8]) -> Result<(), D::Error>
+where
+	D: Deserializer<'de>,
+{
+	struct Visitor<'a> {
+		bytes: &'a mut [u8],
+	}
+
+	impl<'a, 'b> de::Visitor<'b> for Visitor<'a> {
+		type Value = ();
+
+		fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+			write!(formatter, "a hex string of {} bytes, optionally 0x-prefixed", self.bytes.len())
+		}
+
+		fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+			let (digits, offset) = match v.strip_prefix("0x") {
+				Some(digits) => (digits, 2),
+				None => (v, 0),
+			};
+
+			let expected = self.bytes.len();
+			if digits.len() != 2 * expected {
+				return Err(E::custom(if digits.len() % 2 == 0 {
+					format!("invalid length: expected {} bytes, got {} bytes", expected, digits.len() / 2)
+				} else {
+					format!("invalid length: expected {} bytes, got {} hex digits", expected, digits.len())
+				}));
+			}
+
+			from_hex_raw(digits, offset, self.bytes).map(|_| ()).map_err(E::custom)
+		}
+
+		fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+			self.visit_str(&v)
+		}
+	}
+
+	deserializer.deserialize_str(Visitor { bytes })
+}
+
+/// Serde adapter for fixed-size byte types, such as hashes, writing hex without the `0x` prefix.
+///
+/// Use it with `#[serde(with = "impl_serde::serialize::bare")]`.
+/// Deserialization accepts the input with or without the prefix.
+pub mod bare {
+	use super::*;
+
+	/// Serializes the bytes as hex without the `0x` prefix.
+	pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		T: AsRef<[u8]>,
+		S: Serializer,
+	{
+		let bytes = value.as_ref();
+		if bytes.is_empty() {
+			return serializer.serialize_str("");
+		}
+		let mut slice = vec![0u8; (bytes.len() + 1) * 2];
+		serializer.serialize_str(&to_hex_raw(&mut slice, bytes, false)[2..])
+	}
+
+	/// Deserializes hex of exactly the size of `T`, with or without the `0x` prefix.
+	pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+	where
+		T: Default + AsMut<[u8]>,
+		D: Deserializer<'de>,
+	{
+		let mut value = T::default();
+		deserialize_exact(deserializer, value.as_mut())?;
+		Ok(value)
+	}
+}
+
+/// Serde adapter for integers that also accepts plain unsigned JSON numbers.
+///
+/// Use it with `#[serde(with = "impl_serde::serialize::lenient_uint")]`.
+/// Serialization is unchanged, while deserialization accepts either a `0x`-prefixed
+/// hex string or a non-negative integer. Floats and negative numbers are rejected.
+/// It relies on `deserialize_any`, so it only works with self-describing formats.
+pub mod lenient_uint {
+	use super::*;
+	use core::marker::PhantomData;
+	use serde::de::IntoDeserializer;
+
+	/// Serializes the value with its own `Serialize` implementation.
+	pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		T: serde::Serialize,
+		S: Serializer,
+	{
+		value.serialize(serializer)
+	}
+
+	/// Deserializes a hex string or an unsigned integer.
+	pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+	where
+		T: serde::Deserialize<'de> + From<u64>,
+		D: Deserializer<'de>,
+	{
+		struct Visitor<T>(PhantomData<T>);
+
+		impl<'de, T> de::Visitor<'de> for Visitor<T>
+		where
+			T: serde::Deserialize<'de> + From<u64>,
+		{
+			type Value = T;
+
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				write!(formatter, "a 0x-prefixed hex string or an unsigned integer")
+			}
+
+			fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+				T::deserialize(v.into_deserializer())
+			}
+
+			fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+				self.visit_str(&v)
+			}
+
+			fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+				Ok(T::from(v))
+			}
+
+			fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+				if v < 0 {
+					return Err(E::invalid_value(de::Unexpected::Signed(v), &self));
+				}
+				Ok(T::from(v as u64))
+			}
+		}
+
+		deserializer.deserialize_any(Visitor(PhantomData))
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use alloc::string::ToString;
 	use serde_derive::{Deserialize, Serialize};
 
 	#[derive(Serialize, Deserialize)]
 	struct Bytes(#[serde(with = "super")] Vec<u8>);
 
+	#[derive(Debug)]
+	struct Fixed([u8; 4]);
+
+	impl serde::Serialize for Fixed {
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+			serialize(&self.0, serializer)
+		}
+	}
+
+	impl<'de> serde::Deserialize<'de> for Fixed {
+		fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+			let mut bytes = [0u8; 4];
+			deserialize_exact(deserializer, &mut bytes)?;
+			Ok(Fixed(bytes))
+		}
+	}
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct Bare(#[serde(with = "super::bare")] [u8; 4]);
+
 	#[test]
 	fn should_not_fail_on_short_string() {
 		let a: Bytes = serde_json::from_str("\"0x\"").unwrap();
@@ -324,6 +484,63 @@ mod tests {
 		assert!(deserialized.0.is_empty())
 	}
 
+	#[test]
+	fn should_roundtrip_fixed_with_and_without_prefix() {
+		let fixed = Fixed([0x12, 0x34, 0xab, 0xcd]);
+		let data = serde_json::to_string(&fixed).unwrap();
+		assert_eq!(data, "\"0x1234abcd\"");
+		assert_eq!(serde_json::from_str::<Fixed>(&data).unwrap().0, fixed.0);
+		assert_eq!(serde_json::from_str::<Fixed>("\"1234ABCD\"").unwrap().0, fixed.0);
+	}
+
+	#[test]
+	fn should_roundtrip_bare() {
+		let bare = Bare([0x12, 0x34, 0xab, 0xcd]);
+		let data = serde_json::to_string(&bare).unwrap();
+		assert_eq!(data, "\"1234abcd\"");
+		assert_eq!(serde_json::from_str::<Bare>(&data).unwrap(), bare);
+		assert_eq!(serde_json::from_str::<Bare>("\"0x1234abcd\"").unwrap(), bare);
+	}
+
+	#[test]
+	fn should_report_expected_and_actual_length() {
+		let err = serde_json::from_str::<Fixed>("\"0x123456\"").unwrap_err();
+		assert_eq!(err.to_string(), "invalid length: expected 4 bytes, got 3 bytes at line 1 column 10");
+		let err = serde_json::from_str::<Bare>("\"1234567\"").unwrap_err();
+		assert_eq!(err.to_string(), "invalid length: expected 4 bytes, got 7 hex digits at line 1 column 9");
+		let err = serde_json::from_str::<Fixed>("\"0x1234abcdef\"").unwrap_err();
+		assert_eq!(err.to_string(), "invalid length: expected 4 bytes, got 5 bytes at line 1 column 14");
+	}
+
+	uint::construct_uint! {
+		struct U64(1);
+	}
+
+	crate::impl_uint_serde!(U64, 1);
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct Lenient(#[serde(with = "lenient_uint")] U64);
+
+	#[test]
+	fn lenient_uint_accepts_hex_and_integers() {
+		assert_eq!(serde_json::from_str::<Lenient>("\"0x1f\"").unwrap(), Lenient(U64::from(31)));
+		assert_eq!(serde_json::from_str::<Lenient>("42").unwrap(), Lenient(U64::from(42)));
+		assert_eq!(serde_json::to_string(&Lenient(U64::from(42))).unwrap(), "\"0x2a\"");
+	}
+
+	#[test]
+	fn lenient_uint_rejects_floats_and_negatives() {
+		assert!(serde_json::from_str::<Lenient>("1.5").is_err());
+		assert!(serde_json::from_str::<Lenient>("-1").is_err());
+		assert!(serde_json::from_str::<Lenient>("\"0xzz\"").is_err());
+	}
+
+	#[test]
+	fn strict_uint_rejects_integers() {
+		assert!(serde_json::from_str::<U64>("42").is_err());
+		assert_eq!(serde_json::from_str::<U64>("\"0x2a\"").unwrap(), U64::from(42));
+	}
+
 	#[test]
 	fn should_encode_to_and_from_hex() {
 		assert_eq!(to_hex(&[0, 1, 2], true), "0x102");
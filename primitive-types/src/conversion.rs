@@ -0,0 +1,177 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Conversions between the uint types.
+//!
+//! Widening conversions are `From`, narrowing ones `TryFrom` with a `TryFromUintError`.
+//! `LossyFrom` and `LossyInto` provide overflowing and saturating conversions in any direction.
+
+use crate::{U128, U256, U512};
+use core::fmt;
+
+/// Error returned when a uint does not fit into a narrower uint type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TryFromUintError {
+	/// The number of bits required to represent the value.
+	pub required_bits: usize,
+}
+
+impl fmt::Display for TryFromUintError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "value of {} bits does not fit into the target type", self.required_bits)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromUintError {}
+
+/// Lossy conversion from another uint type.
+///
+/// Implemented for every pair of uint types, including the identity.
+pub trait LossyFrom<T>: Sized {
+	/// Converts the low bits of `value`, also returning whether any bits were lost.
+	fn overflowing_from(value: T) -> (Self, bool);
+
+	/// Converts `value`, returning the maximum value of `Self` if it does not fit.
+	fn saturating_from(value: T) -> Self;
+}
+
+/// Lossy conversion into another uint type, the counterpart of `LossyFrom`.
+pub trait LossyInto<T> {
+	/// Converts the low bits of `self`, also returning whether any bits were lost.
+	fn overflowing_into(self) -> (T, bool);
+
+	/// Converts `self`, returning the maximum value of `T` if it does not fit.
+	fn saturating_into(self) -> T;
+}
+
+impl<T> LossyFrom<T> for T {
+	fn overflowing_from(value: T) -> (Self, bool) {
+		(value, false)
+	}
+
+	fn saturating_from(value: T) -> Self {
+		value
+	}
+}
+
+impl<T, U: LossyFrom<T>> LossyInto<U> for T {
+	fn overflowing_into(self) -> (U, bool) {
+		U::overflowing_from(self)
+	}
+
+	fn saturating_into(self) -> U {
+		U::saturating_from(self)
+	}
+}
+
+/// Implements `From`, `TryFrom` and `LossyFrom` between a narrower and a wider uint type.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_uint_conversions {
+	($narrow:ident, $narrow_words:expr, $wide:ident, $wide_words:expr) => {
+		impl ::core::convert::From<$narrow> for $wide {
+			fn from(value: $narrow) -> $wide {
+				let mut ret = [0u64; $wide_words];
+				ret[..$narrow_words].copy_from_slice(&value.0);
+				$wide(ret)
+			}
+		}
+
+		impl<'a> ::core::convert::From<&'a $narrow> for $wide {
+			fn from(value: &'a $narrow) -> $wide {
+				(*value).into()
+			}
+		}
+
+		impl ::core::convert::TryFrom<$wide> for $narrow {
+			type Error = $crate::TryFromUintError;
+
+			fn try_from(value: $wide) -> ::core::result::Result<$narrow, $crate::TryFromUintError> {
+				match <$narrow as $crate::LossyFrom<$wide>>::overflowing_from(value) {
+					(ret, false) => Ok(ret),
+					(_, true) => Err($crate::TryFromUintError { required_bits: value.bits() }),
+				}
+			}
+		}
+
+		impl<'a> ::core::convert::TryFrom<&'a $wide> for $narrow {
+			type Error = $crate::TryFromUintError;
+
+			fn try_from(value: &'a $wide) -> ::core::result::Result<$narrow, $crate::TryFromUintError> {
+				::core::convert::TryFrom::try_from(*value)
+			}
+		}
+
+		impl $crate::LossyFrom<$wide> for $narrow {
+			fn overflowing_from(value: $wide) -> ($narrow, bool) {
+				let mut ret = [0u64; $narrow_words];
+				ret.copy_from_slice(&value.0[..$narrow_words]);
+				($narrow(ret), value.0[$narrow_words..].iter().any(|&word| word != 0))
+			}
+
+			fn saturating_from(value: $wide) -> $narrow {
+				match Self::overflowing_from(value) {
+					(ret, false) => ret,
+					(_, true) => $narrow::MAX,
+				}
+			}
+		}
+
+		impl $crate::LossyFrom<$narrow> for $wide {
+			fn overflowing_from(value: $narrow) -> ($wide, bool) {
+				(value.into(), false)
+			}
+
+			fn saturating_from(value: $narrow) -> $wide {
+				value.into()
+			}
+		}
+	};
+}
+
+impl_uint_conversions!(U128, 2, U256, 4);
+impl_uint_conversions!(U128, 2, U512, 8);
+impl_uint_conversions!(U256, 4, U512, 8);
+#[cfg(feature = "large-uints")]
+mod large {
+	use super::*;
+	use crate::{U1024, U2048};
+
+	impl_uint_conversions!(U128, 2, U1024, 16);
+	impl_uint_conversions!(U256, 4, U1024, 16);
+	impl_uint_conversions!(U512, 8, U1024, 16);
+	impl_uint_conversions!(U128, 2, U2048, 32);
+	impl_uint_conversions!(U256, 4, U2048, 32);
+	impl_uint_conversions!(U512, 8, U2048, 32);
+	impl_uint_conversions!(U1024, 16, U2048, 32);
+
+	impl U1024 {
+		/// Low 512 bits, discarding the rest.
+		#[inline]
+		pub fn low_u512(&self) -> U512 {
+			<U512 as LossyFrom<U1024>>::overflowing_from(*self).0
+		}
+	}
+
+	impl U2048 {
+		/// Low 1024 bits, discarding the rest.
+		#[inline]
+		pub fn low_u1024(&self) -> U1024 {
+			<U1024 as LossyFrom<U2048>>::overflowing_from(*self).0
+		}
+	}
+}
+
+impl U512 {
+	/// Low 256 bits, discarding the rest.
+	#[inline]
+	pub fn low_u256(&self) -> U256 {
+		<U256 as LossyFrom<U512>>::overflowing_from(*self).0
+	}
+}
@@ -0,0 +1,164 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Construction of fixed hash types with all integrations enabled in primitive-types.
+
+/// Constructs a fixed hash type like `fixed_hash::construct_fixed_hash!` and implements
+/// the serde, scale-codec, rlp and borsh traits for it, each when the corresponding
+/// feature of primitive-types is enabled.
+///
+/// The integrations follow the features of primitive-types rather than those of the
+/// calling crate, so the type supports exactly what `H256` does. Random generation and
+/// the other fixed-hash integrations follow the features of fixed-hash, as usual.
+///
+/// # Example
+///
+/// ```
+/// use primitive_types::construct_fixed_hash_full;
+///
+/// construct_fixed_hash_full! {
+/// 	/// A 288-bit commitment.
+/// 	pub struct H288(36);
+/// }
+///
+/// assert_eq!(H288::len_bytes(), 36);
+/// ```
+#[macro_export]
+macro_rules! construct_fixed_hash_full {
+	( $(#[$attr:meta])* $visibility:vis struct $name:ident ( $n_bytes:expr ); ) => {
+		$crate::fixed_hash::construct_fixed_hash! {
+			$(#[$attr])*
+			$visibility struct $name ( $n_bytes );
+		}
+
+		$crate::impl_fixed_hash_serde_if_enabled!($name, $n_bytes);
+		$crate::impl_fixed_hash_codec_if_enabled!($name, $n_bytes);
+		$crate::impl_fixed_hash_rlp_if_enabled!($name, $n_bytes);
+		$crate::impl_fixed_hash_borsh_if_enabled!($name, $n_bytes);
+	};
+}
+
+// When the `impl-serde` dependency is disabled.
+//
+// # Note
+//
+// Feature guarded macro definitions instead of feature guarded impl blocks
+// to work around the problems of introducing `serde` crate feature in
+// a user crate.
+#[cfg(not(feature = "impl-serde"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_fixed_hash_serde_if_enabled {
+	($name:ident, $n_bytes:expr) => {};
+}
+
+// When the `impl-serde` dependency is enabled.
+//
+// # Note
+//
+// Feature guarded macro definitions instead of feature guarded impl blocks
+// to work around the problems of introducing `serde` crate feature in
+// a user crate.
+#[cfg(feature = "impl-serde")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_fixed_hash_serde_if_enabled {
+	($name:ident, $n_bytes:expr) => {
+		$crate::impl_serde::impl_fixed_hash_serde!($name, $n_bytes);
+	};
+}
+
+// When the `impl-codec` dependency is disabled.
+//
+// # Note
+//
+// Feature guarded macro definitions instead of feature guarded impl blocks
+// to work around the problems of introducing `codec` crate feature in
+// a user crate.
+#[cfg(not(feature = "impl-codec"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_fixed_hash_codec_if_enabled {
+	($name:ident, $n_bytes:expr) => {};
+}
+
+// When the `impl-codec` dependency is enabled.
+//
+// # Note
+//
+// Feature guarded macro definitions instead of feature guarded impl blocks
+// to work around the problems of introducing `codec` crate feature in
+// a user crate.
+#[cfg(feature = "impl-codec")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_fixed_hash_codec_if_enabled {
+	($name:ident, $n_bytes:expr) => {
+		$crate::impl_codec::impl_fixed_hash_codec!($name, $n_bytes);
+	};
+}
+
+// When the `impl-rlp` dependency is disabled.
+//
+// # Note
+//
+// Feature guarded macro definitions instead of feature guarded impl blocks
+// to work around the problems of introducing `rlp` crate feature in
+// a user crate.
+#[cfg(not(feature = "impl-rlp"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_fixed_hash_rlp_if_enabled {
+	($name:ident, $n_bytes:expr) => {};
+}
+
+// When the `impl-rlp` dependency is enabled.
+//
+// # Note
+//
+// Feature guarded macro definitions instead of feature guarded impl blocks
+// to work around the problems of introducing `rlp` crate feature in
+// a user crate.
+#[cfg(feature = "impl-rlp")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_fixed_hash_rlp_if_enabled {
+	($name:ident, $n_bytes:expr) => {
+		$crate::impl_rlp::impl_fixed_hash_rlp!($name, $n_bytes);
+	};
+}
+
+// When the `impl-borsh` dependency is disabled.
+//
+// # Note
+//
+// Feature guarded macro definitions instead of feature guarded impl blocks
+// to work around the problems of introducing `borsh` crate feature in
+// a user crate.
+#[cfg(not(feature = "impl-borsh"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_fixed_hash_borsh_if_enabled {
+	($name:ident, $n_bytes:expr) => {};
+}
+
+// When the `impl-borsh` dependency is enabled.
+//
+// # Note
+//
+// Feature guarded macro definitions instead of feature guarded impl blocks
+// to work around the problems of introducing `borsh` crate feature in
+// a user crate.
+#[cfg(feature = "impl-borsh")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_fixed_hash_borsh_if_enabled {
+	($name:ident, $n_bytes:expr) => {
+		$crate::impl_borsh::impl_fixed_hash_borsh!($name, $n_bytes);
+	};
+}
@@ -1,6 +1,71 @@
 use super::U256;
+use std::{error, fmt};
+
+/// How `U256::from_f64_rounding` rounds the fractional part of a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+	/// Round towards zero.
+	Floor,
+	/// Round away from zero.
+	Ceil,
+	/// Round to the nearest integer, and halfway cases away from zero.
+	Nearest,
+}
+
+/// Error converting a `f64` to a `U256` with `U256::from_f64_rounding`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromFloatError {
+	/// The value is NaN.
+	NaN,
+	/// The value is infinite.
+	Infinite,
+	/// The value is negative.
+	Negative,
+	/// The rounded value exceeds `U256::MAX`.
+	Overflow,
+}
+
+impl fmt::Display for FromFloatError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let msg = match self {
+			FromFloatError::NaN => "value is NaN",
+			FromFloatError::Infinite => "value is infinite",
+			FromFloatError::Negative => "value is negative",
+			FromFloatError::Overflow => "value does not fit into U256",
+		};
+		f.write_str(msg)
+	}
+}
+
+impl error::Error for FromFloatError {}
 
 impl U256 {
+	/// Conversion from a `f64` to a `U256`, rounding the fractional part as given by `rounding`.
+	///
+	/// Unlike `from_f64_lossy`, this rejects NaN, infinite and negative values, and values
+	/// which exceed `U256::MAX` after rounding. Negative zero converts to zero.
+	pub fn from_f64_rounding(value: f64, rounding: RoundingMode) -> Result<U256, FromFloatError> {
+		if value.is_nan() {
+			return Err(FromFloatError::NaN);
+		}
+		if value.is_infinite() {
+			return Err(FromFloatError::Infinite);
+		}
+		if value < 0.0 {
+			return Err(FromFloatError::Negative);
+		}
+		let rounded = match rounding {
+			RoundingMode::Floor => value.floor(),
+			RoundingMode::Ceil => value.ceil(),
+			RoundingMode::Nearest => value.round(),
+		};
+		if rounded >= 2.0f64.powi(256) {
+			return Err(FromFloatError::Overflow);
+		}
+		// `rounded` is an integer below 2^256, so this conversion is exact.
+		Ok(U256::from_f64_lossy(rounded))
+	}
+
 	/// Lossy saturating conversion from a `f64` to a `U256`. Like for floating point to
 	/// primitive integer type conversions, this truncates fractional parts.
 	///
@@ -9,27 +9,41 @@
 //! Primitive types shared by Substrate and Parity Ethereum.
 //!
 //! Those are uint types `U128`, `U256` and `U512`, and fixed hash types `H160`,
-//! `H256` and `H512`, with optional serde serialization, parity-scale-codec and
-//! rlp encoding.
+//! `H256` and `H512`, with optional serde serialization, parity-scale-codec,
+//! rlp and borsh encoding. The `large-uints` feature adds `U1024`, `U2048`
+//! and `H1024`, and the `bls-hashes` feature adds the BLS12-381 sized `H384`
+//! and `H768`.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod conversion;
+mod fixed_hash_full;
 #[cfg(feature = "fp-conversion")]
 mod fp_conversion;
 
-use core::convert::TryFrom;
-use fixed_hash::{construct_fixed_hash, impl_fixed_hash_conversions};
+#[doc(hidden)]
+pub use fixed_hash;
+#[cfg(feature = "impl-borsh")]
+#[doc(hidden)]
+pub use impl_borsh;
+#[cfg(feature = "impl-codec")]
+#[doc(hidden)]
+pub use impl_codec;
+#[cfg(feature = "impl-rlp")]
+#[doc(hidden)]
+pub use impl_rlp;
+#[cfg(feature = "impl-serde")]
+#[doc(hidden)]
+pub use impl_serde;
+
+pub use conversion::{LossyFrom, LossyInto, TryFromUintError};
+use fixed_hash::{construct_fixed_hash, impl_fixed_hash_conversions, impl_fixed_hash_halves};
+#[cfg(feature = "fp-conversion")]
+pub use fp_conversion::{FromFloatError, RoundingMode};
 #[cfg(feature = "scale-info")]
 use scale_info_crate::TypeInfo;
 use uint::{construct_uint, uint_full_mul_reg};
 
-/// Error type for conversion.
-#[derive(Debug, PartialEq, Eq)]
-pub enum Error {
-	/// Overflow encountered.
-	Overflow,
-}
-
 construct_uint! {
 	/// 128-bit unsigned integer.
 	#[cfg_attr(feature = "scale-info", derive(TypeInfo))]
@@ -45,6 +59,18 @@ construct_uint! {
 	#[cfg_attr(feature = "scale-info", derive(TypeInfo))]
 	pub struct U512(8);
 }
+#[cfg(feature = "large-uints")]
+construct_uint! {
+	/// 1024-bit unsigned integer.
+	#[cfg_attr(feature = "scale-info", derive(TypeInfo))]
+	pub struct U1024(16);
+}
+#[cfg(feature = "large-uints")]
+construct_uint! {
+	/// 2048-bit unsigned integer.
+	#[cfg_attr(feature = "scale-info", derive(TypeInfo))]
+	pub struct U2048(32);
+}
 
 construct_fixed_hash! {
 	/// Fixed-size uninterpreted hash type with 16 bytes (128 bits) size.
@@ -67,6 +93,28 @@ construct_fixed_hash! {
 	#[cfg_attr(feature = "scale-info", derive(TypeInfo))]
 	pub struct H512(64);
 }
+#[cfg(feature = "bls-hashes")]
+construct_fixed_hash! {
+	/// Fixed-size uninterpreted hash type with 48 bytes (384 bits) size.
+	///
+	/// Fits a compressed BLS12-381 G1 point, e.g. a public key.
+	#[cfg_attr(feature = "scale-info", derive(TypeInfo))]
+	pub struct H384(48);
+}
+#[cfg(feature = "bls-hashes")]
+construct_fixed_hash! {
+	/// Fixed-size uninterpreted hash type with 96 bytes (768 bits) size.
+	///
+	/// Fits a compressed BLS12-381 G2 point, e.g. a signature.
+	#[cfg_attr(feature = "scale-info", derive(TypeInfo))]
+	pub struct H768(96);
+}
+#[cfg(feature = "large-uints")]
+construct_fixed_hash! {
+	/// Fixed-size uninterpreted hash type with 128 bytes (1024 bits) size.
+	#[cfg_attr(feature = "scale-info", derive(TypeInfo))]
+	pub struct H1024(128);
+}
 
 #[cfg(feature = "num-traits")]
 mod num_traits {
@@ -76,6 +124,10 @@ mod num_traits {
 	impl_uint_num_traits!(U128, 2);
 	impl_uint_num_traits!(U256, 4);
 	impl_uint_num_traits!(U512, 8);
+	#[cfg(feature = "large-uints")]
+	impl_uint_num_traits!(U1024, 16);
+	#[cfg(feature = "large-uints")]
+	impl_uint_num_traits!(U2048, 32);
 }
 
 #[cfg(feature = "impl-serde")]
@@ -86,11 +138,21 @@ mod serde {
 	impl_uint_serde!(U128, 2);
 	impl_uint_serde!(U256, 4);
 	impl_uint_serde!(U512, 8);
+	#[cfg(feature = "large-uints")]
+	impl_uint_serde!(U1024, 16);
+	#[cfg(feature = "large-uints")]
+	impl_uint_serde!(U2048, 32);
 
 	impl_fixed_hash_serde!(H128, 16);
 	impl_fixed_hash_serde!(H160, 20);
 	impl_fixed_hash_serde!(H256, 32);
 	impl_fixed_hash_serde!(H512, 64);
+	#[cfg(feature = "bls-hashes")]
+	impl_fixed_hash_serde!(H384, 48);
+	#[cfg(feature = "bls-hashes")]
+	impl_fixed_hash_serde!(H768, 96);
+	#[cfg(feature = "large-uints")]
+	impl_fixed_hash_serde!(H1024, 128);
 }
 
 #[cfg(feature = "impl-codec")]
@@ -101,11 +163,21 @@ mod codec {
 	impl_uint_codec!(U128, 2);
 	impl_uint_codec!(U256, 4);
 	impl_uint_codec!(U512, 8);
+	#[cfg(feature = "large-uints")]
+	impl_uint_codec!(U1024, 16);
+	#[cfg(feature = "large-uints")]
+	impl_uint_codec!(U2048, 32);
 
 	impl_fixed_hash_codec!(H128, 16);
 	impl_fixed_hash_codec!(H160, 20);
 	impl_fixed_hash_codec!(H256, 32);
 	impl_fixed_hash_codec!(H512, 64);
+	#[cfg(feature = "bls-hashes")]
+	impl_fixed_hash_codec!(H384, 48);
+	#[cfg(feature = "bls-hashes")]
+	impl_fixed_hash_codec!(H768, 96);
+	#[cfg(feature = "large-uints")]
+	impl_fixed_hash_codec!(H1024, 128);
 }
 
 #[cfg(feature = "impl-rlp")]
@@ -116,14 +188,55 @@ mod rlp {
 	impl_uint_rlp!(U128, 2);
 	impl_uint_rlp!(U256, 4);
 	impl_uint_rlp!(U512, 8);
+	#[cfg(feature = "large-uints")]
+	impl_uint_rlp!(U1024, 16);
+	#[cfg(feature = "large-uints")]
+	impl_uint_rlp!(U2048, 32);
 
 	impl_fixed_hash_rlp!(H128, 16);
 	impl_fixed_hash_rlp!(H160, 20);
 	impl_fixed_hash_rlp!(H256, 32);
 	impl_fixed_hash_rlp!(H512, 64);
+	#[cfg(feature = "bls-hashes")]
+	impl_fixed_hash_rlp!(H384, 48);
+	#[cfg(feature = "bls-hashes")]
+	impl_fixed_hash_rlp!(H768, 96);
+	#[cfg(feature = "large-uints")]
+	impl_fixed_hash_rlp!(H1024, 128);
+}
+
+#[cfg(feature = "impl-borsh")]
+mod borsh {
+	use super::*;
+	use impl_borsh::{impl_fixed_hash_borsh, impl_uint_borsh};
+
+	impl_uint_borsh!(U128, 2);
+	impl_uint_borsh!(U256, 4);
+	impl_uint_borsh!(U512, 8);
+	#[cfg(feature = "large-uints")]
+	impl_uint_borsh!(U1024, 16);
+	#[cfg(feature = "large-uints")]
+	impl_uint_borsh!(U2048, 32);
+
+	impl_fixed_hash_borsh!(H128, 16);
+	impl_fixed_hash_borsh!(H160, 20);
+	impl_fixed_hash_borsh!(H256, 32);
+	impl_fixed_hash_borsh!(H512, 64);
+	#[cfg(feature = "bls-hashes")]
+	impl_fixed_hash_borsh!(H384, 48);
+	#[cfg(feature = "bls-hashes")]
+	impl_fixed_hash_borsh!(H768, 96);
+	#[cfg(feature = "large-uints")]
+	impl_fixed_hash_borsh!(H1024, 128);
 }
 
 impl_fixed_hash_conversions!(H256, H160);
+impl_fixed_hash_halves!(H256, H128);
+impl_fixed_hash_halves!(H512, H256);
+#[cfg(feature = "bls-hashes")]
+impl_fixed_hash_halves!(H768, H384);
+#[cfg(feature = "large-uints")]
+impl_fixed_hash_halves!(H1024, H512);
 
 impl U128 {
 	/// Multiplies two 128-bit integers to produce full 256-bit integer.
@@ -143,110 +256,22 @@ impl U256 {
 	}
 }
 
-impl From<U256> for U512 {
-	fn from(value: U256) -> U512 {
-		let U256(ref arr) = value;
-		let mut ret = [0; 8];
-		ret[0] = arr[0];
-		ret[1] = arr[1];
-		ret[2] = arr[2];
-		ret[3] = arr[3];
-		U512(ret)
-	}
-}
-
-impl TryFrom<U256> for U128 {
-	type Error = Error;
-
-	fn try_from(value: U256) -> Result<U128, Error> {
-		let U256(ref arr) = value;
-		if arr[2] | arr[3] != 0 {
-			return Err(Error::Overflow);
-		}
-		let mut ret = [0; 2];
-		ret[0] = arr[0];
-		ret[1] = arr[1];
-		Ok(U128(ret))
-	}
-}
-
-impl TryFrom<U512> for U256 {
-	type Error = Error;
-
-	fn try_from(value: U512) -> Result<U256, Error> {
-		let U512(ref arr) = value;
-		if arr[4] | arr[5] | arr[6] | arr[7] != 0 {
-			return Err(Error::Overflow);
-		}
-		let mut ret = [0; 4];
-		ret[0] = arr[0];
-		ret[1] = arr[1];
-		ret[2] = arr[2];
-		ret[3] = arr[3];
-		Ok(U256(ret))
-	}
-}
-
-impl TryFrom<U512> for U128 {
-	type Error = Error;
-
-	fn try_from(value: U512) -> Result<U128, Error> {
-		let U512(ref arr) = value;
-		if arr[2] | arr[3] | arr[4] | arr[5] | arr[6] | arr[7] != 0 {
-			return Err(Error::Overflow);
-		}
-		let mut ret = [0; 2];
-		ret[0] = arr[0];
-		ret[1] = arr[1];
-		Ok(U128(ret))
-	}
-}
-
-impl From<U128> for U512 {
-	fn from(value: U128) -> U512 {
-		let U128(ref arr) = value;
-		let mut ret = [0; 8];
-		ret[0] = arr[0];
-		ret[1] = arr[1];
-		U512(ret)
-	}
-}
-
-impl From<U128> for U256 {
-	fn from(value: U128) -> U256 {
-		let U128(ref arr) = value;
-		let mut ret = [0; 4];
-		ret[0] = arr[0];
-		ret[1] = arr[1];
-		U256(ret)
-	}
-}
-
-impl<'a> From<&'a U256> for U512 {
-	fn from(value: &'a U256) -> U512 {
-		let U256(ref arr) = *value;
-		let mut ret = [0; 8];
-		ret[0] = arr[0];
-		ret[1] = arr[1];
-		ret[2] = arr[2];
-		ret[3] = arr[3];
-		U512(ret)
+#[cfg(feature = "large-uints")]
+impl U512 {
+	/// Multiplies two 512-bit integers to produce full 1024-bit integer.
+	/// Overflow is not possible.
+	#[inline(always)]
+	pub fn full_mul(self, other: U512) -> U1024 {
+		U1024(uint_full_mul_reg!(U512, 8, self, other))
 	}
 }
 
-impl<'a> TryFrom<&'a U512> for U256 {
-	type Error = Error;
-
-	fn try_from(value: &'a U512) -> Result<U256, Error> {
-		let U512(ref arr) = *value;
-		if arr[4] | arr[5] | arr[6] | arr[7] != 0 {
-			return Err(Error::Overflow);
-		}
-		let mut ret = [0; 4];
-		ret[0] = arr[0];
-		ret[1] = arr[1];
-		ret[2] = arr[2];
-		ret[3] = arr[3];
-		Ok(U256(ret))
+#[cfg(feature = "large-uints")]
+impl U1024 {
+	/// Multiplies two 1024-bit integers to produce full 2048-bit integer.
+	/// Overflow is not possible.
+	#[inline(always)]
+	pub fn full_mul(self, other: U1024) -> U2048 {
+		U2048(uint_full_mul_reg!(U1024, 16, self, other))
 	}
 }
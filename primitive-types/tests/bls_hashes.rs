@@ -0,0 +1,83 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for bls-hashes feature of primitive-types.
+
+use core::convert::TryFrom;
+use primitive_types::{H384, H768};
+
+#[test]
+fn sizes() {
+	assert_eq!(H384::len_bytes(), 48);
+	assert_eq!(H768::len_bytes(), 96);
+}
+
+#[test]
+fn byte_conversions() {
+	let bytes = [0x5a; 48];
+	let hash = H384::from(bytes);
+	assert_eq!(hash, H384::repeat_byte(0x5a));
+	assert_eq!(<[u8; 48]>::from(hash), bytes);
+	assert_eq!(H384::from_slice(&bytes), hash);
+	assert_eq!(H384::try_from(&bytes[..]).ok(), Some(hash));
+	assert!(H384::try_from(&bytes[1..]).is_err());
+
+	let bytes = [0xa5; 96];
+	let hash = H768::from(bytes);
+	assert_eq!(hash.as_fixed_bytes(), &bytes);
+	assert!(H768::try_from(&[0u8; 95][..]).is_err());
+}
+
+#[test]
+fn hash_halves() {
+	let high = H384::repeat_byte(0x01);
+	let low = H384::repeat_byte(0x02);
+	let hash = H768::from_halves(high, low);
+	assert_eq!(&hash[..48], high.as_bytes());
+	assert_eq!(&hash[48..], low.as_bytes());
+	assert_eq!(hash.split(), (high, low));
+}
+
+#[test]
+fn hex_formatting() {
+	let hash = H384::from_low_u64_be(0xabcd);
+	assert_eq!(format!("{:x}", hash), format!("{:0>96}", "abcd"));
+	assert_eq!(format!("{:?}", H768::zero()), format!("0x{}", "0".repeat(192)));
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn codec_round_trip() {
+	use impl_codec::codec::{Decode, Encode};
+
+	let hash = H384::repeat_byte(0x11);
+	let encoded = hash.encode();
+	assert_eq!(encoded, vec![0x11; 48]);
+	assert_eq!(H384::decode(&mut &encoded[..]).ok(), Some(hash));
+
+	let hash = H768::repeat_byte(0x22);
+	let encoded = hash.encode();
+	assert_eq!(encoded, vec![0x22; 96]);
+	assert_eq!(H768::decode(&mut &encoded[..]).ok(), Some(hash));
+	assert!(H768::decode(&mut &encoded[..95]).is_err());
+}
+
+#[cfg(feature = "rlp")]
+#[test]
+fn rlp_round_trip() {
+	let hash = H384::repeat_byte(0x33);
+	let encoded = impl_rlp::rlp::encode(&hash);
+	assert_eq!(encoded[0], 0x80 + 48);
+	assert_eq!(impl_rlp::rlp::decode::<H384>(&encoded), Ok(hash));
+
+	let hash = H768::repeat_byte(0x44);
+	let encoded = impl_rlp::rlp::encode(&hash);
+	assert_eq!(&encoded[..2], &[0xb8, 96]);
+	assert_eq!(impl_rlp::rlp::decode::<H768>(&encoded), Ok(hash));
+	assert!(impl_rlp::rlp::decode::<H384>(&encoded).is_err());
+}
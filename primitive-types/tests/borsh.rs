@@ -0,0 +1,98 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for borsh feature of primitive-types.
+
+use impl_borsh::borsh::{BorshDeserialize, BorshSerialize};
+use primitive_types::{H160, H256, U128, U256, U512};
+
+#[test]
+fn uint_borsh_roundtrip() {
+	let values = [U256::zero(), U256::one(), U256::from(0xdead_beef_u64) << 100, U256::MAX];
+	for value in values.iter() {
+		let encoded = value.try_to_vec().unwrap();
+		assert_eq!(encoded.len(), 32);
+		assert_eq!(U256::try_from_slice(&encoded).unwrap(), *value);
+	}
+
+	let value = U128::from(u128::max_value() - 7);
+	assert_eq!(U128::try_from_slice(&value.try_to_vec().unwrap()).unwrap(), value);
+
+	let value = U512::MAX >> 3;
+	assert_eq!(U512::try_from_slice(&value.try_to_vec().unwrap()).unwrap(), value);
+}
+
+#[test]
+fn uint_borsh_is_little_endian() {
+	let value = U256([1, 2, 3, 4]);
+	let mut expected = [0u8; 32];
+	value.to_little_endian(&mut expected);
+
+	assert_eq!(value.try_to_vec().unwrap(), expected.to_vec());
+	// Same layout as the canonical borsh encoding of the underlying limbs.
+	assert_eq!(value.try_to_vec().unwrap(), value.0.try_to_vec().unwrap());
+}
+
+#[test]
+fn fixed_hash_borsh_roundtrip() {
+	let hash = H256::repeat_byte(0x42);
+	assert_eq!(H256::try_from_slice(&hash.try_to_vec().unwrap()).unwrap(), hash);
+
+	let hash = H160::from_low_u64_be(0x1234);
+	assert_eq!(H160::try_from_slice(&hash.try_to_vec().unwrap()).unwrap(), hash);
+}
+
+#[test]
+fn fixed_hash_borsh_matches_byte_array() {
+	let mut bytes = [0u8; 32];
+	for (i, b) in bytes.iter_mut().enumerate() {
+		*b = i as u8;
+	}
+	let hash = H256(bytes);
+
+	assert_eq!(hash.try_to_vec().unwrap(), bytes.try_to_vec().unwrap());
+	assert_eq!(hash.try_to_vec().unwrap(), bytes.to_vec());
+}
+
+#[test]
+fn borsh_rejects_truncated_input() {
+	let encoded = U256::MAX.try_to_vec().unwrap();
+	assert!(U256::try_from_slice(&encoded[..31]).is_err());
+	assert!(U256::try_from_slice(&[]).is_err());
+
+	let encoded = H256::repeat_byte(1).try_to_vec().unwrap();
+	assert!(H256::try_from_slice(&encoded[..20]).is_err());
+}
+
+#[test]
+fn borsh_rejects_trailing_input() {
+	let mut encoded = H160::zero().try_to_vec().unwrap();
+	encoded.push(0);
+	assert!(H160::try_from_slice(&encoded).is_err());
+}
+
+mod narrow {
+	use impl_borsh::impl_uint_borsh;
+
+	uint::construct_uint! {
+		pub struct U160(3, 160);
+	}
+
+	impl_uint_borsh!(U160, 3);
+}
+
+#[test]
+fn narrow_uint_borsh_uses_exact_width() {
+	use narrow::U160;
+
+	let value = U160::MAX - 1;
+	let encoded = value.try_to_vec().unwrap();
+	assert_eq!(encoded.len(), 20);
+	assert_eq!(U160::try_from_slice(&encoded).unwrap(), value);
+	assert!(U160::try_from_slice(&[0u8; 19]).is_err());
+}
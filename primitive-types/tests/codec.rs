@@ -0,0 +1,93 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for codec feature of primitive-types.
+
+use impl_codec::codec::{Compact, Decode, Encode, EncodeAsRef, Error, HasCompact, Input};
+use impl_codec::decode_fixed_width_vec;
+use primitive_types::{H256, U128, U256};
+
+uint::construct_uint! {
+	/// A 200 bit integer, which is encoded in 25 bytes.
+	struct U200(4, 200);
+}
+
+impl_codec::impl_uint_codec!(U200, 4);
+
+type CompactU256 = <U256 as HasCompact>::Type;
+
+fn encode_compact(value: &U256) -> Vec<u8> {
+	<CompactU256 as EncodeAsRef<'_, U256>>::RefType::from(value).encode()
+}
+
+#[test]
+fn uint_compact_matches_compact_u128() {
+	let values = [0u128, 1, 63, 64, 16383, 16384, (1 << 30) - 1, 1 << 30, u64::max_value() as u128, u128::max_value()];
+	for &value in values.iter() {
+		let expected = Compact(value).encode();
+		assert_eq!(encode_compact(&U256::from(value)), expected);
+		assert_eq!(CompactU256::from(U256::from(value)).encode(), expected);
+		assert_eq!(CompactU256::from(U256::from(value)).size_hint(), expected.len());
+
+		let decoded: U256 = CompactU256::decode(&mut &expected[..]).unwrap().into();
+		assert_eq!(decoded, U256::from(value));
+		let decoded: U128 = <U128 as HasCompact>::Type::decode(&mut &expected[..]).unwrap().into();
+		assert_eq!(decoded, U128::from(value));
+	}
+}
+
+#[test]
+#[should_panic(expected = "value exceeds the maximum of compact encoding")]
+fn uint_compact_encode_out_of_range() {
+	encode_compact(&(U256::from(u128::max_value()) + 1));
+}
+
+/// Input which doesn't tell its remaining length.
+struct UnknownLen<'a>(&'a [u8]);
+
+impl Input for UnknownLen<'_> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, Error> {
+		Ok(None)
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), Error> {
+		Input::read(&mut self.0, into)
+	}
+}
+
+#[test]
+fn uint_vec_decode_checks_length() {
+	let values = vec![U256::from(1), U256::max_value(), U256::zero()];
+	let encoded = values.encode();
+	assert_eq!(decode_fixed_width_vec::<U256, _>(&mut &encoded[..]).unwrap(), values);
+	assert_eq!(decode_fixed_width_vec::<U256, _>(&mut UnknownLen(&encoded)).unwrap(), values);
+	assert!(decode_fixed_width_vec::<U256, _>(&mut &encoded[..encoded.len() - 1]).is_err());
+
+	// A bogus length of u32::MAX items, i.e. 128 GiB of U256s, fails without reserving room
+	// for them, which would abort the test.
+	let mut bogus = Compact(u32::max_value()).encode();
+	bogus.extend_from_slice(&[0u8; 64]);
+	assert!(decode_fixed_width_vec::<U256, _>(&mut &bogus[..]).is_err());
+	assert!(decode_fixed_width_vec::<U256, _>(&mut UnknownLen(&bogus)).is_err());
+}
+
+#[test]
+fn vec_decode_uses_encoded_width() {
+	assert_eq!(U200::encoded_fixed_size(), Some(25));
+	assert_eq!(H256::encoded_fixed_size(), Some(32));
+
+	let values = vec![U200::from(1), U200::MAX, U200::zero()];
+	let encoded = values.encode();
+	assert_eq!(encoded.len(), 1 + 3 * 25);
+	assert_eq!(decode_fixed_width_vec::<U200, _>(&mut &encoded[..]).unwrap(), values);
+	assert!(decode_fixed_width_vec::<U200, _>(&mut &encoded[..encoded.len() - 1]).is_err());
+
+	let values = vec![Some(1u8), None];
+	let encoded = values.encode();
+	assert_eq!(decode_fixed_width_vec::<Option<u8>, _>(&mut &encoded[..]).unwrap(), values);
+}
@@ -0,0 +1,69 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for `construct_fixed_hash_full!` of primitive-types.
+
+use primitive_types::construct_fixed_hash_full;
+
+construct_fixed_hash_full! {
+	/// A hash size not provided by primitive-types.
+	pub struct H288(36);
+}
+
+fn sample() -> H288 {
+	let mut hash = H288::repeat_byte(0x5a);
+	hash.as_bytes_mut()[0] = 0x01;
+	hash.as_bytes_mut()[35] = 0xff;
+	hash
+}
+
+#[test]
+fn fixed_hash_api() {
+	assert_eq!(H288::len_bytes(), 36);
+	assert_eq!(H288::from([0x5a; 36]), H288::repeat_byte(0x5a));
+	assert_eq!(format!("{}", sample()), "0x015a…5aff");
+}
+
+#[cfg(feature = "impl-serde")]
+#[test]
+fn serde_round_trip() {
+	let json = serde_json::to_string(&sample()).unwrap();
+	assert_eq!(json, format!("\"0x01{}ff\"", "5a".repeat(34)));
+	assert_eq!(serde_json::from_str::<H288>(&json).unwrap(), sample());
+	assert!(serde_json::from_str::<H288>("\"0x01\"").is_err());
+}
+
+#[cfg(feature = "impl-codec")]
+#[test]
+fn codec_round_trip() {
+	use impl_codec::codec::{Decode, Encode};
+
+	let encoded = sample().encode();
+	assert_eq!(encoded, sample().as_bytes());
+	assert_eq!(H288::decode(&mut &encoded[..]).ok(), Some(sample()));
+	assert!(H288::decode(&mut &encoded[..35]).is_err());
+}
+
+#[cfg(feature = "impl-rlp")]
+#[test]
+fn rlp_round_trip() {
+	let encoded = impl_rlp::rlp::encode(&sample());
+	assert_eq!(encoded[0], 0x80 + 36);
+	assert_eq!(&encoded[1..], sample().as_bytes());
+	assert_eq!(impl_rlp::rlp::decode::<H288>(&encoded), Ok(sample()));
+}
+
+#[cfg(feature = "impl-borsh")]
+#[test]
+fn borsh_round_trip() {
+	use impl_borsh::borsh::{BorshDeserialize, BorshSerialize};
+
+	let encoded = sample().try_to_vec().unwrap();
+	assert_eq!(encoded, sample().as_bytes());
+	assert_eq!(H288::try_from_slice(&encoded).unwrap(), sample());
+}
@@ -74,3 +74,26 @@ fn convert_f64_to_u256_non_normal() {
 fn f64_to_u256_truncation() {
 	assert_eq!(U256::from_f64_lossy(10.5), 10.into());
 }
+
+#[test]
+fn convert_f64_with_rounding() {
+	use primitive_types::{FromFloatError, RoundingMode};
+
+	assert_eq!(U256::from_f64_rounding(2.9, RoundingMode::Floor), Ok(U256::from(2)));
+	assert_eq!(U256::from_f64_rounding(2.9, RoundingMode::Ceil), Ok(U256::from(3)));
+	assert_eq!(U256::from_f64_rounding(2.9, RoundingMode::Nearest), Ok(U256::from(3)));
+	assert_eq!(U256::from_f64_rounding(2.1, RoundingMode::Nearest), Ok(U256::from(2)));
+	assert_eq!(U256::from_f64_rounding(2.5, RoundingMode::Nearest), Ok(U256::from(3)));
+	assert_eq!(U256::from_f64_rounding(3.0, RoundingMode::Ceil), Ok(U256::from(3)));
+	assert_eq!(U256::from_f64_rounding(0.2, RoundingMode::Ceil), Ok(U256::from(1)));
+	assert_eq!(U256::from_f64_rounding(-0.0, RoundingMode::Floor), Ok(U256::zero()));
+	assert_eq!(U256::from_f64_rounding(1e20, RoundingMode::Floor), Ok(U256::from(100_000_000_000_000_000_000u128)));
+	assert_eq!(U256::from_f64_rounding(2.0f64.powi(255), RoundingMode::Ceil), Ok(U256::one() << 255));
+
+	assert_eq!(U256::from_f64_rounding(2.0f64.powi(256), RoundingMode::Floor), Err(FromFloatError::Overflow));
+	assert_eq!(U256::from_f64_rounding(1e100, RoundingMode::Nearest), Err(FromFloatError::Overflow));
+	assert_eq!(U256::from_f64_rounding(-0.5, RoundingMode::Ceil), Err(FromFloatError::Negative));
+	assert_eq!(U256::from_f64_rounding(f64::NAN, RoundingMode::Floor), Err(FromFloatError::NaN));
+	assert_eq!(U256::from_f64_rounding(f64::INFINITY, RoundingMode::Floor), Err(FromFloatError::Infinite));
+	assert_eq!(U256::from_f64_rounding(f64::NEG_INFINITY, RoundingMode::Floor), Err(FromFloatError::Infinite));
+}
@@ -0,0 +1,91 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for large-uints feature of primitive-types.
+
+use core::convert::TryFrom;
+use primitive_types::{TryFromUintError, H1024, H512, U1024, U2048, U256, U512};
+
+#[test]
+fn sizes() {
+	assert_eq!(U1024::BITS, 1024);
+	assert_eq!(U2048::BYTES, 256);
+	assert_eq!(U1024::MAX.bits(), 1024);
+	assert_eq!(H1024::len_bytes(), 128);
+}
+
+#[test]
+fn full_mul() {
+	assert_eq!(U512::MAX.full_mul(U512::MAX), U1024::from(U512::MAX) * U1024::from(U512::MAX));
+	assert_eq!(U512::from(3).full_mul(U512::from(5)), U1024::from(15));
+
+	let product = U1024::MAX.full_mul(U1024::MAX);
+	// (2^1024 - 1)^2 = 2^2048 - 2^1025 + 1
+	assert_eq!(product, (U2048::MAX - (U2048::one() << 1025)) + 2);
+	assert_eq!(U1024::from(U512::MAX).full_mul(U1024::from(2)), U2048::from(U512::MAX) << 1);
+}
+
+#[test]
+fn conversions() {
+	assert_eq!(U1024::from(U256::MAX).bits(), 256);
+	assert_eq!(U2048::from(U1024::MAX).bits(), 1024);
+	assert_eq!(U512::try_from(U1024::from(U512::MAX)), Ok(U512::MAX));
+	assert_eq!(U512::try_from(U1024::MAX), Err(TryFromUintError { required_bits: 1024 }));
+	assert_eq!(U1024::try_from(U2048::from(U1024::MAX) + 1), Err(TryFromUintError { required_bits: 1025 }));
+	assert_eq!(U1024::MAX.low_u512(), U512::MAX);
+	assert_eq!((U2048::from(U1024::MAX) + 2).low_u1024(), U1024::one());
+}
+
+#[test]
+fn arithmetic_and_parsing() {
+	let value = U2048::from_dec_str("179769313486231590772930519078902473361797697894230657273430081157732675805500963132708477322407536021120113879871393357658789768814416622492847430639474124377767893424865485276302219601246094119453082952085005768838150682342462881473913110540827237163350510684586298239947245938479716304835356329624224137216").unwrap();
+	assert_eq!(value, U2048::one() << 1024);
+	assert_eq!(value.to_string(), "179769313486231590772930519078902473361797697894230657273430081157732675805500963132708477322407536021120113879871393357658789768814416622492847430639474124377767893424865485276302219601246094119453082952085005768838150682342462881473913110540827237163350510684586298239947245938479716304835356329624224137216");
+	assert_eq!(value / U2048::from(U1024::MAX), U2048::one());
+	assert_eq!(value % U2048::from(U1024::MAX), U2048::one());
+	assert_eq!((value - 1).integer_sqrt(), (U2048::one() << 512) - 1);
+}
+
+#[test]
+fn hash_halves() {
+	let hash = H1024::repeat_byte(0xab);
+	let (high, low) = hash.split();
+	assert_eq!(high, H512::repeat_byte(0xab));
+	assert_eq!(H1024::from_halves(high, low), hash);
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn codec_roundtrip() {
+	use impl_codec::codec::{Decode, Encode};
+
+	for value in [U1024::zero(), U1024::from(U512::MAX), U1024::MAX >> 3].iter() {
+		let encoded = value.encode();
+		assert_eq!(encoded.len(), 128);
+		assert_eq!(U1024::decode(&mut &encoded[..]).unwrap(), *value);
+	}
+	for value in [U2048::zero(), U2048::MAX >> 5].iter() {
+		assert_eq!(U2048::decode(&mut &value.encode()[..]).unwrap(), *value);
+	}
+	let hash = H1024::repeat_byte(0x5a);
+	assert_eq!(H1024::decode(&mut &hash.encode()[..]).unwrap(), hash);
+}
+
+#[cfg(feature = "rlp")]
+#[test]
+fn rlp_roundtrip() {
+	use impl_rlp::rlp;
+
+	for value in [U1024::zero(), U1024::MAX >> 3, U1024::MAX].iter() {
+		assert_eq!(rlp::decode::<U1024>(&rlp::encode(value)), Ok(*value));
+	}
+	assert_eq!(rlp::decode::<U2048>(&rlp::encode(&U2048::MAX)), Ok(U2048::MAX));
+	assert_eq!(rlp::decode::<U1024>(&rlp::encode(&U2048::MAX)), Err(rlp::DecoderError::RlpIsTooBig));
+	let hash = H1024::repeat_byte(0x5a);
+	assert_eq!(rlp::decode::<H1024>(&rlp::encode(&hash)), Ok(hash));
+}
@@ -0,0 +1,72 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tests for conversions between the uint types.
+
+use core::convert::TryFrom;
+use primitive_types::{LossyFrom, LossyInto, TryFromUintError, U128, U256, U512};
+
+macro_rules! test_pair {
+	($test_name:ident, $narrow:ident, $wide:ident) => {
+		#[test]
+		fn $test_name() {
+			let narrow_bits = $narrow::MAX.bits();
+
+			// In range, including the boundary.
+			for value in [$narrow::zero(), $narrow::from(42), $narrow::MAX].iter() {
+				let wide = $wide::from(*value);
+				assert_eq!($wide::from(value), wide);
+				assert_eq!(wide.bits(), value.bits());
+				assert_eq!($narrow::try_from(wide), Ok(*value));
+				assert_eq!($narrow::try_from(&wide), Ok(*value));
+				assert_eq!(LossyInto::<$narrow>::overflowing_into(wide), (*value, false));
+				assert_eq!(LossyInto::<$narrow>::saturating_into(wide), *value);
+				assert_eq!(<$wide as LossyFrom<$narrow>>::overflowing_from(*value), (wide, false));
+				assert_eq!(<$wide as LossyFrom<$narrow>>::saturating_from(*value), wide);
+			}
+
+			// Just out of range.
+			let wide = $wide::from($narrow::MAX) + 1;
+			assert_eq!($narrow::try_from(wide), Err(TryFromUintError { required_bits: narrow_bits + 1 }));
+			assert_eq!(LossyInto::<$narrow>::overflowing_into(wide), ($narrow::zero(), true));
+			assert_eq!(LossyInto::<$narrow>::saturating_into(wide), $narrow::MAX);
+
+			// Far out of range.
+			assert_eq!($narrow::try_from($wide::MAX), Err(TryFromUintError { required_bits: $wide::MAX.bits() }));
+			assert_eq!(LossyInto::<$narrow>::overflowing_into($wide::MAX), ($narrow::MAX, true));
+			assert_eq!(LossyInto::<$narrow>::saturating_into($wide::MAX), $narrow::MAX);
+			let high_and_low = ($wide::one() << narrow_bits) | $wide::from(7);
+			assert_eq!(LossyInto::<$narrow>::overflowing_into(high_and_low), ($narrow::from(7), true));
+		}
+	};
+}
+
+test_pair!(u128_u256, U128, U256);
+test_pair!(u128_u512, U128, U512);
+test_pair!(u256_u512, U256, U512);
+
+#[test]
+fn identity_conversions() {
+	assert_eq!(LossyInto::<U256>::overflowing_into(U256::MAX), (U256::MAX, false));
+	assert_eq!(LossyInto::<U256>::saturating_into(U256::MAX), U256::MAX);
+}
+
+#[test]
+fn low_u256() {
+	assert_eq!(U512::MAX.low_u256(), U256::MAX);
+	assert_eq!((U512::from(U256::MAX) + 2).low_u256(), U256::one());
+	assert_eq!(U512::from(42).low_u256(), U256::from(42));
+}
+
+#[test]
+fn error_display() {
+	assert_eq!(
+		TryFromUintError { required_bits: 129 }.to_string(),
+		"value of 129 bits does not fit into the target type"
+	);
+}
@@ -0,0 +1,150 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::bound::option_value_type;
+use syn::{
+	punctuated::Punctuated, token::Comma, Attribute, Error, Field, Fields, Ident, Lit, Meta, Result, WherePredicate,
+};
+
+const UNSUPPORTED: &str =
+	"only #[rlp(default)], #[rlp(skip)], #[rlp(trailing)] and #[rlp(flatten)] attributes are supported";
+
+/// How a field is encoded and decoded, chosen with an `#[rlp(...)]` attribute.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FieldAttr {
+	/// No attribute: the field is encoded and required when decoding.
+	Required,
+	/// `#[rlp(default)]`: the field is encoded, and decoded as `Default::default()` when
+	/// missing at the end of the list.
+	Default,
+	/// `#[rlp(skip)]`: the field is not encoded, and decoded as `Default::default()`.
+	Skip,
+	/// `#[rlp(trailing)]` on an `Option`: the field is encoded only when set, and decoded as
+	/// `None` when missing at the end of the list.
+	Trailing,
+	/// `#[rlp(flatten)]`: the fields of the field's struct are encoded as items of the list,
+	/// instead of a nested list.
+	Flatten,
+}
+
+impl FieldAttr {
+	/// Reads the `#[rlp(...)]` attribute of `field`.
+	pub fn of(field: &Field) -> Result<Self> {
+		let mut result = Self::Required;
+		for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("rlp")) {
+			if result != Self::Required {
+				return Err(Error::new_spanned(attr, "a field can have only one #[rlp(...)] attribute"));
+			}
+			let ident: Ident = attr.parse_args().map_err(|_| Error::new_spanned(attr, UNSUPPORTED))?;
+			result = if ident == "default" {
+				Self::Default
+			} else if ident == "skip" {
+				Self::Skip
+			} else if ident == "trailing" {
+				if option_value_type(&field.ty).is_none() {
+					return Err(Error::new_spanned(&field.ty, "#[rlp(trailing)] fields must be of type Option<T>"));
+				}
+				Self::Trailing
+			} else if ident == "flatten" {
+				Self::Flatten
+			} else {
+				return Err(Error::new_spanned(attr, UNSUPPORTED));
+			};
+		}
+		Ok(result)
+	}
+
+	/// Reads the `#[rlp(...)]` attributes of `fields`, checking that no required field follows
+	/// an `#[rlp(default)]` one and that only skipped fields follow `#[rlp(trailing)]` ones,
+	/// since only the last items can be missing from a list.
+	pub fn of_fields<'a>(fields: impl IntoIterator<Item = &'a Field>) -> Result<Vec<Self>> {
+		let mut default_attribute_encountered = false;
+		let mut trailing_attribute_encountered = false;
+		let mut attrs = Vec::new();
+		for field in fields {
+			let attr = Self::of(field)?;
+			match attr {
+				Self::Required | Self::Flatten | Self::Default if trailing_attribute_encountered => {
+					return Err(Error::new_spanned(field, "#[rlp(trailing)] fields must come after all other fields"));
+				}
+				Self::Required | Self::Flatten if default_attribute_encountered => {
+					return Err(Error::new_spanned(field, "#[rlp(default)] fields must come after all other fields"));
+				}
+				Self::Default => default_attribute_encountered = true,
+				Self::Trailing => trailing_attribute_encountered = true,
+				Self::Required | Self::Flatten | Self::Skip => {}
+			}
+			attrs.push(attr);
+		}
+		Ok(attrs)
+	}
+}
+
+/// The `#[rlp(...)]` attributes of a struct.
+#[derive(Default)]
+pub struct ContainerAttrs {
+	/// The predicates of `#[rlp(bound = "...")]`, which replace the bounds inferred from the
+	/// field types.
+	pub bound: Option<Punctuated<WherePredicate, Comma>>,
+	/// `#[rlp(transparent)]`, encoding a single-field struct exactly as its field.
+	pub transparent: Option<Attribute>,
+}
+
+impl ContainerAttrs {
+	/// Reads the `#[rlp(...)]` attributes of a struct.
+	pub fn of(attrs: &[Attribute]) -> Result<Self> {
+		let mut result = Self::default();
+		for attr in attrs.iter().filter(|attr| attr.path.is_ident("rlp")) {
+			let unsupported = || {
+				Error::new_spanned(
+					attr,
+					"only #[rlp(bound = \"...\")] and #[rlp(transparent)] are supported on structs",
+				)
+			};
+			match attr.parse_args().map_err(|_| unsupported())? {
+				Meta::Path(path) if path.is_ident("transparent") => {
+					if result.transparent.is_some() {
+						return Err(Error::new_spanned(attr, "duplicate #[rlp(transparent)] attribute"));
+					}
+					result.transparent = Some(attr.clone());
+				}
+				Meta::NameValue(meta) if meta.path.is_ident("bound") => {
+					if result.bound.is_some() {
+						return Err(Error::new_spanned(
+							attr,
+							"a struct can have only one #[rlp(bound = \"...\")] attribute",
+						));
+					}
+					result.bound = match meta.lit {
+						Lit::Str(lit) => Some(lit.parse_with(Punctuated::parse_terminated)?),
+						lit => return Err(Error::new_spanned(lit, "expected where predicates in a string")),
+					};
+				}
+				_ => return Err(unsupported()),
+			}
+		}
+		Ok(result)
+	}
+
+	/// The only field of an `#[rlp(transparent)]` struct, failing if it has other fields or an
+	/// `#[rlp(...)]` attribute.
+	pub fn transparent_field<'a>(&self, fields: &'a Fields, attrs: &[FieldAttr]) -> Result<Option<&'a Field>> {
+		let Some(transparent) = &self.transparent else {
+			return Ok(None);
+		};
+		let mut iter = fields.iter().zip(attrs);
+		match (iter.next(), iter.next()) {
+			(Some((field, FieldAttr::Required)), None) => Ok(Some(field)),
+			(Some((field, _)), None) => Err(Error::new_spanned(
+				field,
+				"the field of an #[rlp(transparent)] struct can't have an #[rlp(...)] attribute",
+			)),
+			_ => Err(Error::new_spanned(transparent, "#[rlp(transparent)] requires a struct with exactly one field")),
+		}
+	}
+}
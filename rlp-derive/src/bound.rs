@@ -0,0 +1,89 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{
+	parse_quote, punctuated::Punctuated, token::Comma, GenericArgument, GenericParam, Generics, Ident, PathArguments,
+	Type, WherePredicate,
+};
+
+/// The item type of a `Vec` field, which is encoded with `append_list` and decoded with `list_at`.
+pub fn vec_item_type(ty: &Type) -> Option<&Type> {
+	generic_argument(ty, "Vec")
+}
+
+/// The value type of an `Option` field.
+pub fn option_value_type(ty: &Type) -> Option<&Type> {
+	generic_argument(ty, "Option")
+}
+
+fn generic_argument<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+	let segment = match ty {
+		Type::Path(path) if path.qself.is_none() => path.path.segments.last()?,
+		_ => return None,
+	};
+	if segment.ident != name {
+		return None;
+	}
+	match &segment.arguments {
+		PathArguments::AngleBracketed(angle) => match angle.args.first()? {
+			GenericArgument::Type(ty) => Some(ty),
+			_ => None,
+		},
+		_ => None,
+	}
+}
+
+/// Adds the `bounds` of the field types which mention a type parameter to the where clause of
+/// `generics`, or the predicates of an `#[rlp(bound = "...")]` attribute instead.
+///
+/// Bounding field types rather than type parameters keeps fields like `PhantomData<T>`
+/// from requiring bounds on `T`.
+pub fn with_bounds(
+	generics: &Generics,
+	bounds: Vec<(&Type, TokenStream)>,
+	custom: Option<Punctuated<WherePredicate, Comma>>,
+) -> Generics {
+	let params: Vec<Ident> = generics.type_params().map(|param| param.ident.clone()).collect();
+	let mut generics = generics.clone();
+	let where_clause = generics.make_where_clause();
+	match custom {
+		Some(predicates) => where_clause.predicates.extend(predicates),
+		None => {
+			for (ty, bound) in bounds {
+				if mentions(ty.to_token_stream(), &params) {
+					where_clause.predicates.push(parse_quote! { #ty: #bound });
+				}
+			}
+		}
+	}
+	generics
+}
+
+/// Whether `ty` mentions a parameter of `generics`, and so can only be named inside the impl.
+pub fn is_generic(ty: &Type, generics: &Generics) -> bool {
+	let params: Vec<Ident> = generics
+		.params
+		.iter()
+		.map(|param| match param {
+			GenericParam::Type(param) => param.ident.clone(),
+			GenericParam::Lifetime(param) => param.lifetime.ident.clone(),
+			GenericParam::Const(param) => param.ident.clone(),
+		})
+		.collect();
+	mentions(ty.to_token_stream(), &params)
+}
+
+fn mentions(tokens: TokenStream, params: &[Ident]) -> bool {
+	tokens.into_iter().any(|token| match token {
+		TokenTree::Ident(ident) => params.contains(&ident),
+		TokenTree::Group(group) => mentions(group.stream(), params),
+		_ => false,
+	})
+}
@@ -6,47 +6,104 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use crate::{
+	attr::{ContainerAttrs, FieldAttr},
+	bound::{is_generic, option_value_type, vec_item_type, with_bounds},
+};
 use proc_macro2::TokenStream;
-use quote::quote;
-
-struct ParseQuotes {
-	single: TokenStream,
-	list: TokenStream,
-	takes_index: bool,
-}
-
-fn decodable_parse_quotes() -> ParseQuotes {
-	ParseQuotes { single: quote! { rlp.val_at }, list: quote! { rlp.list_at }, takes_index: true }
-}
-
-fn decodable_wrapper_parse_quotes() -> ParseQuotes {
-	ParseQuotes { single: quote! { rlp.as_val }, list: quote! { rlp.as_list }, takes_index: false }
-}
+use quote::{format_ident, quote, quote_spanned};
+use syn::{punctuated::Punctuated, spanned::Spanned, token::Comma, WherePredicate};
 
 pub fn impl_decodable(ast: &syn::DeriveInput) -> TokenStream {
-	let body = if let syn::Data::Struct(s) = &ast.data {
-		s
-	} else {
+	let syn::Data::Struct(body) = &ast.data else {
 		panic!("#[derive(RlpDecodable)] is only defined for structs.");
 	};
 
-	let mut default_attribute_encountered = false;
-	let stmts: Vec<_> = body
-		.fields
-		.iter()
-		.enumerate()
-		.map(|(i, field)| decodable_field(i, field, decodable_parse_quotes(), &mut default_attribute_encountered))
-		.collect();
+	let (attrs, container) = match (FieldAttr::of_fields(&body.fields), ContainerAttrs::of(&ast.attrs)) {
+		(Ok(attrs), Ok(container)) => (attrs, container),
+		(Err(err), _) | (_, Err(err)) => return err.to_compile_error(),
+	};
+	match container.transparent_field(&body.fields, &attrs) {
+		Ok(Some(field)) => return decodable_wrapper(ast, field, container.bound),
+		Ok(None) => {}
+		Err(err) => return err.to_compile_error(),
+	}
+
+	// Each field is decoded into a local, from the item at `index` which is then advanced
+	// past the items decoded, as flattened and missing fields take a varying number of items.
+	// Flattened fields with optional fields of their own must come last, which is checked where
+	// their type can be named: outside of the impl unless it is generic.
+	let mut stmts = Vec::new();
+	let mut members = Vec::new();
+	let mut locals = Vec::new();
+	let mut checks = Vec::new();
+	let last_encoded = attrs.iter().rposition(|attr| *attr != FieldAttr::Skip);
+	for (i, (field, attr)) in body.fields.iter().zip(attrs.iter().copied()).enumerate() {
+		let local = format_ident!("field_{}", i);
+		if attr == FieldAttr::Flatten && Some(i) != last_encoded {
+			let check = flattened_not_last(&field.ty);
+			if is_generic(&field.ty, &ast.generics) {
+				stmts.push(quote! { const { #check } });
+			} else {
+				checks.push(quote! { const _: () = #check; });
+			}
+		}
+		stmts.push(decodable_field(&local, &field.ty, attr));
+		members.push(field_member(i, field));
+		locals.push(local);
+	}
+	let has_optional_fields = body.fields.iter().zip(attrs.iter()).map(|(field, attr)| {
+		let ty = &field.ty;
+		match attr {
+			FieldAttr::Default | FieldAttr::Trailing => quote! { true },
+			FieldAttr::Flatten => quote! { <#ty as rlp::DecodableFields>::HAS_OPTIONAL_FIELDS },
+			FieldAttr::Required | FieldAttr::Skip => quote! { false },
+		}
+	});
 	let name = &ast.ident;
+	let bounds = body.fields.iter().zip(attrs.iter()).flat_map(|(field, attr)| field_bounds(field, *attr)).collect();
+	let generics = with_bounds(&ast.generics, bounds, container.bound);
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+	// The list must not be longer than all fields, otherwise trailing fields would be dropped.
+	let decode = if attrs.contains(&FieldAttr::Trailing) {
+		quote! {
+			let (result, len) = <Self as rlp::DecodableFields>::rlp_decode_fields(rlp, 0)?;
+			if rlp.item_count()? > len {
+				return Err(rlp::DecoderError::RlpIncorrectListLen);
+			}
+			Ok(result)
+		}
+	} else {
+		quote! {
+			<Self as rlp::DecodableFields>::rlp_decode_fields(rlp, 0).map(|(result, _)| result)
+		}
+	};
+	let unused_rlp = if attrs.iter().all(|attr| *attr == FieldAttr::Skip) {
+		quote! { let _ = rlp; }
+	} else {
+		quote! {}
+	};
 
 	let impl_block = quote! {
-		impl rlp::Decodable for #name {
-			fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+		impl #impl_generics rlp::DecodableFields for #name #ty_generics #where_clause {
+			const HAS_OPTIONAL_FIELDS: bool = false #(|| #has_optional_fields)*;
+
+			fn rlp_decode_fields(rlp: &rlp::Rlp, start: usize) -> Result<(Self, usize), rlp::DecoderError> {
+				#unused_rlp
+				let index = start;
+				#(#stmts)*
 				let result = #name {
-					#(#stmts)*
+					#(#members: #locals,)*
 				};
 
-				Ok(result)
+				Ok((result, index - start))
+			}
+		}
+
+		impl #impl_generics rlp::Decodable for #name #ty_generics #where_clause {
+			fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+				#decode
 			}
 		}
 	};
@@ -54,36 +111,43 @@ pub fn impl_decodable(ast: &syn::DeriveInput) -> TokenStream {
 	quote! {
 		const _: () = {
 			extern crate rlp;
+			#(#checks)*
 			#impl_block
 		};
 	}
 }
 
 pub fn impl_decodable_wrapper(ast: &syn::DeriveInput) -> TokenStream {
-	let body = if let syn::Data::Struct(s) = &ast.data {
-		s
-	} else {
+	let syn::Data::Struct(body) = &ast.data else {
 		panic!("#[derive(RlpDecodableWrapper)] is only defined for structs.");
 	};
 
-	let stmt = {
-		let fields: Vec<_> = body.fields.iter().collect();
-		if fields.len() == 1 {
-			let field = fields.first().expect("fields.len() == 1; qed");
-			let mut default_attribute_encountered = false;
-			decodable_field(0, field, decodable_wrapper_parse_quotes(), &mut default_attribute_encountered)
-		} else {
-			panic!("#[derive(RlpEncodableWrapper)] is only defined for structs with one field.")
-		}
-	};
+	assert!(body.fields.len() == 1, "#[derive(RlpEncodableWrapper)] is only defined for structs with one field.");
+	let field = body.fields.iter().next().expect("fields.len() == 1; qed");
+	decodable_wrapper(ast, field, None)
+}
 
+/// Decodes a struct exactly as its only field.
+fn decodable_wrapper(
+	ast: &syn::DeriveInput,
+	field: &syn::Field,
+	custom_bound: Option<Punctuated<WherePredicate, Comma>>,
+) -> TokenStream {
+	let member = field_member(0, field);
+	let decode = if vec_item_type(&field.ty).is_some() {
+		quote! { rlp.as_list()? }
+	} else {
+		quote! { rlp.as_val()? }
+	};
 	let name = &ast.ident;
+	let generics = with_bounds(&ast.generics, field_bounds(field, FieldAttr::Required), custom_bound);
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
 	let impl_block = quote! {
-		impl rlp::Decodable for #name {
+		impl #impl_generics rlp::Decodable for #name #ty_generics #where_clause {
 			fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
 				let result = #name {
-					#stmt
+					#member: #decode,
 				};
 
 				Ok(result)
@@ -99,65 +163,66 @@ pub fn impl_decodable_wrapper(ast: &syn::DeriveInput) -> TokenStream {
 	}
 }
 
-fn decodable_field(
-	mut index: usize,
-	field: &syn::Field,
-	quotes: ParseQuotes,
-	default_attribute_encountered: &mut bool,
-) -> TokenStream {
-	let id = if let Some(ident) = &field.ident {
-		quote! { #ident }
-	} else {
-		let index = syn::Index::from(index);
-		quote! { #index }
-	};
+fn field_member(index: usize, field: &syn::Field) -> TokenStream {
+	field.ident.as_ref().map_or_else(
+		|| {
+			let index = syn::Index::from(index);
+			quote! { #index }
+		},
+		|ident| quote! { #ident },
+	)
+}
 
-	if *default_attribute_encountered {
-		index -= 1;
+/// The bounds which the types of `field` must satisfy to decode it.
+fn field_bounds(field: &syn::Field, attr: FieldAttr) -> Vec<(&syn::Type, TokenStream)> {
+	let decodable = quote! { rlp::Decodable };
+	let default = quote! { Default };
+	let decoded_type = vec_item_type(&field.ty).unwrap_or(&field.ty);
+	match attr {
+		FieldAttr::Required => vec![(decoded_type, decodable)],
+		FieldAttr::Default => vec![(decoded_type, decodable), (&field.ty, default)],
+		FieldAttr::Skip => vec![(&field.ty, default)],
+		FieldAttr::Trailing => vec![(option_value_type(&field.ty).expect("checked by FieldAttr::of; qed"), decodable)],
+		FieldAttr::Flatten => vec![(&field.ty, quote! { rlp::DecodableFields })],
 	}
-	let index = quote! { #index };
+}
 
-	let single = quotes.single;
-	let list = quotes.list;
+/// Rejects flattening `ty` before other fields if some of its fields may be missing, as they
+/// would then be read from the items of the following fields.
+fn flattened_not_last(ty: &syn::Type) -> TokenStream {
+	quote_spanned! {ty.span()=>
+		assert!(
+			!<#ty as rlp::DecodableFields>::HAS_OPTIONAL_FIELDS,
+			"#[rlp(flatten)] fields with #[rlp(default)] or #[rlp(trailing)] fields must come after all other fields",
+		)
+	}
+}
 
-	let attributes = &field.attrs;
-	let default = if let Some(attr) = attributes.iter().find(|attr| attr.path.is_ident("rlp")) {
-		if *default_attribute_encountered {
-			panic!("only 1 #[rlp(default)] attribute is allowed in a struct")
-		}
-		match attr.parse_args() {
-			Ok(proc_macro2::TokenTree::Ident(ident)) if ident == "default" => {}
-			_ => panic!("only #[rlp(default)] attribute is supported"),
-		}
-		*default_attribute_encountered = true;
-		true
+/// Decodes a field of type `ty` into `local`, advancing `index` past the items decoded.
+fn decodable_field(local: &syn::Ident, ty: &syn::Type, attr: FieldAttr) -> TokenStream {
+	let decode = if vec_item_type(ty).is_some() {
+		quote! { rlp.list_at(index)? }
 	} else {
-		false
+		quote! { rlp.val_at(index)? }
 	};
 
-	if let syn::Type::Path(path) = &field.ty {
-		let ident = &path.path.segments.first().expect("there must be at least 1 segment").ident;
-		let ident_type = ident.to_string();
-		if ident_type == "Vec" {
-			if quotes.takes_index {
-				if default {
-					quote! { #id: #list(#index).unwrap_or_default(), }
-				} else {
-					quote! { #id: #list(#index)?, }
-				}
-			} else {
-				quote! { #id: #list()?, }
-			}
-		} else if quotes.takes_index {
-			if default {
-				quote! { #id: #single(#index).unwrap_or_default(), }
-			} else {
-				quote! { #id: #single(#index)?, }
-			}
-		} else {
-			quote! { #id: #single()?, }
-		}
-	} else {
-		panic!("rlp_derive not supported");
+	match attr {
+		FieldAttr::Required => quote! {
+			let #local = #decode;
+			let index = index + 1;
+		},
+		FieldAttr::Default => quote! {
+			let (#local, index) = if rlp.item_count()? > index { (#decode, index + 1) } else { (Default::default(), index) };
+		},
+		FieldAttr::Trailing => quote! {
+			let (#local, index) = if rlp.item_count()? > index { (Some(#decode), index + 1) } else { (None, index) };
+		},
+		FieldAttr::Flatten => quote! {
+			let (#local, len) = <#ty as rlp::DecodableFields>::rlp_decode_fields(rlp, index)?;
+			let index = index + len;
+		},
+		FieldAttr::Skip => quote! {
+			let #local = Default::default();
+		},
 	}
 }
@@ -6,26 +6,101 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use crate::{
+	attr::{ContainerAttrs, FieldAttr},
+	bound::{option_value_type, vec_item_type, with_bounds},
+};
 use proc_macro2::TokenStream;
 use quote::quote;
+use syn::{punctuated::Punctuated, token::Comma, WherePredicate};
 
 pub fn impl_encodable(ast: &syn::DeriveInput) -> TokenStream {
-	let body = if let syn::Data::Struct(s) = &ast.data {
-		s
-	} else {
+	let syn::Data::Struct(body) = &ast.data else {
 		panic!("#[derive(RlpEncodable)] is only defined for structs.");
 	};
 
-	let stmts: Vec<_> = body.fields.iter().enumerate().map(|(i, field)| encodable_field(i, field)).collect();
+	let (attrs, container) = match (FieldAttr::of_fields(&body.fields), ContainerAttrs::of(&ast.attrs)) {
+		(Ok(attrs), Ok(container)) => (attrs, container),
+		(Err(err), _) | (_, Err(err)) => return err.to_compile_error(),
+	};
+	match container.transparent_field(&body.fields, &attrs) {
+		Ok(Some(field)) => return encodable_wrapper(ast, field, container.bound),
+		Ok(None) => {}
+		Err(err) => return err.to_compile_error(),
+	}
+
+	let mut stmts = Vec::new();
+	let mut plain_len = 0usize;
+	let mut flattened = Vec::new();
+	let mut trailing = Vec::new();
+	let mut bounds = Vec::new();
+	for (i, (field, attr)) in body.fields.iter().zip(attrs).enumerate() {
+		let member = field_member(i, field);
+		match attr {
+			FieldAttr::Skip => {}
+			FieldAttr::Trailing => {
+				let ty = option_value_type(&field.ty).expect("checked by FieldAttr::of; qed");
+				bounds.push((ty, quote! { rlp::Encodable }));
+				trailing.push(member);
+			}
+			FieldAttr::Flatten => {
+				stmts.push(quote! { rlp::EncodableFields::rlp_append_fields(&self.#member, stream); });
+				bounds.push((&field.ty, quote! { rlp::EncodableFields }));
+				flattened.push(member);
+			}
+			FieldAttr::Required | FieldAttr::Default => {
+				stmts.push(encodable_field(&member, &field.ty));
+				bounds.push((encoded_type(&field.ty), quote! { rlp::Encodable }));
+				plain_len += 1;
+			}
+		}
+	}
 	let name = &ast.ident;
+	let generics = with_bounds(&ast.generics, bounds, container.bound);
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-	let stmts_len = stmts.len();
-	let stmts_len = quote! { #stmts_len };
+	let fields_len = if trailing.is_empty() {
+		quote! { #plain_len #(+ rlp::EncodableFields::rlp_fields_len(&self.#flattened))* }
+	} else {
+		// Only a prefix of the trailing fields can be set, as an unset one ends the list.
+		let trailing_indices = 0..trailing.len();
+		quote! {
+			let mut trailing_len = 0usize;
+			#(
+				if self.#trailing.is_some() {
+					assert_eq!(trailing_len, #trailing_indices, "#[rlp(trailing)] field set after an unset one");
+					trailing_len += 1;
+				}
+			)*
+			#plain_len #(+ rlp::EncodableFields::rlp_fields_len(&self.#flattened))* + trailing_len
+		}
+	};
+	let unused_stream = if stmts.is_empty() && trailing.is_empty() {
+		quote! { let _ = stream; }
+	} else {
+		quote! {}
+	};
 	let impl_block = quote! {
-		impl rlp::Encodable for #name {
-			fn rlp_append(&self, stream: &mut rlp::RlpStream) {
-				stream.begin_list(#stmts_len);
+		impl #impl_generics rlp::EncodableFields for #name #ty_generics #where_clause {
+			fn rlp_fields_len(&self) -> usize {
+				#fields_len
+			}
+
+			fn rlp_append_fields(&self, stream: &mut rlp::RlpStream) {
+				#unused_stream
 				#(#stmts)*
+				#(
+					if let Some(value) = &self.#trailing {
+						stream.append(value);
+					}
+				)*
+			}
+		}
+
+		impl #impl_generics rlp::Encodable for #name #ty_generics #where_clause {
+			fn rlp_append(&self, stream: &mut rlp::RlpStream) {
+				stream.begin_list(rlp::EncodableFields::rlp_fields_len(self));
+				rlp::EncodableFields::rlp_append_fields(self, stream);
 			}
 		}
 	};
@@ -39,26 +114,28 @@ pub fn impl_encodable(ast: &syn::DeriveInput) -> TokenStream {
 }
 
 pub fn impl_encodable_wrapper(ast: &syn::DeriveInput) -> TokenStream {
-	let body = if let syn::Data::Struct(s) = &ast.data {
-		s
-	} else {
+	let syn::Data::Struct(body) = &ast.data else {
 		panic!("#[derive(RlpEncodableWrapper)] is only defined for structs.");
 	};
 
-	let stmt = {
-		let fields: Vec<_> = body.fields.iter().collect();
-		if fields.len() == 1 {
-			let field = fields.first().expect("fields.len() == 1; qed");
-			encodable_field(0, field)
-		} else {
-			panic!("#[derive(RlpEncodableWrapper)] is only defined for structs with one field.")
-		}
-	};
+	assert!(body.fields.len() == 1, "#[derive(RlpEncodableWrapper)] is only defined for structs with one field.");
+	let field = body.fields.iter().next().expect("fields.len() == 1; qed");
+	encodable_wrapper(ast, field, None)
+}
 
+/// Encodes a struct exactly as its only field.
+fn encodable_wrapper(
+	ast: &syn::DeriveInput,
+	field: &syn::Field,
+	custom_bound: Option<Punctuated<WherePredicate, Comma>>,
+) -> TokenStream {
+	let stmt = encodable_field(&field_member(0, field), &field.ty);
 	let name = &ast.ident;
+	let generics = with_bounds(&ast.generics, vec![(encoded_type(&field.ty), quote! { rlp::Encodable })], custom_bound);
+	let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
 	let impl_block = quote! {
-		impl rlp::Encodable for #name {
+		impl #impl_generics rlp::Encodable for #name #ty_generics #where_clause {
 			fn rlp_append(&self, stream: &mut rlp::RlpStream) {
 				#stmt
 			}
@@ -73,38 +150,26 @@ pub fn impl_encodable_wrapper(ast: &syn::DeriveInput) -> TokenStream {
 	}
 }
 
-fn encodable_field(index: usize, field: &syn::Field) -> TokenStream {
-	let ident = if let Some(ident) = &field.ident {
-		quote! { #ident }
-	} else {
-		let index = syn::Index::from(index);
-		quote! { #index }
-	};
+fn field_member(index: usize, field: &syn::Field) -> TokenStream {
+	field.ident.as_ref().map_or_else(
+		|| {
+			let index = syn::Index::from(index);
+			quote! { #index }
+		},
+		|ident| quote! { #ident },
+	)
+}
 
-	let id = quote! { self.#ident };
-
-	if let syn::Type::Path(path) = &field.ty {
-		let top_segment = path.path.segments.first().expect("there must be at least 1 segment");
-		let ident = &top_segment.ident;
-		if ident == "Vec" {
-			let inner_ident = {
-				if let syn::PathArguments::AngleBracketed(angle) = &top_segment.arguments {
-					if let syn::GenericArgument::Type(syn::Type::Path(path)) =
-						angle.args.first().expect("Vec has only one angle bracketed type; qed")
-					{
-						&path.path.segments.first().expect("there must be at least 1 segment").ident
-					} else {
-						panic!("rlp_derive not supported");
-					}
-				} else {
-					unreachable!("Vec has only one angle bracketed type; qed")
-				}
-			};
-			quote! { stream.append_list::<#inner_ident, _>(&#id); }
-		} else {
-			quote! { stream.append(&#id); }
-		}
-	} else {
-		panic!("rlp_derive not supported");
-	}
+/// The type which must be `Encodable` to encode a field of type `ty`.
+fn encoded_type(ty: &syn::Type) -> &syn::Type {
+	vec_item_type(ty).unwrap_or(ty)
+}
+
+fn encodable_field(member: &TokenStream, ty: &syn::Type) -> TokenStream {
+	let id = quote! { self.#member };
+
+	vec_item_type(ty).map_or_else(
+		|| quote! { stream.append(&#id); },
+		|item_type| quote! { stream.append_list::<#item_type, _>(&#id); },
+	)
 }
@@ -10,16 +10,49 @@
 //!
 //! For example of usage see `./tests/rlp.rs`.
 //!
-//! This library also supports up to 1 `#[rlp(default)]` in a struct,
-//! which is similar to [`#[serde(default)]`](https://serde.rs/field-attrs.html#default)
-//! with the caveat that we use the `Default` value if
-//! the field deserialization fails, as we don't serialize field
-//! names and there is no way to tell if it is present or not.
+//! This library also supports `#[rlp(default)]` on trailing fields of a struct,
+//! which is similar to [`#[serde(default)]`](https://serde.rs/field-attrs.html#default).
+//! As we don't serialize field names, a field is considered missing when the
+//! decoded list runs out of items before reaching it, in which case its
+//! `Default` value is used. This allows appending new fields to a struct while
+//! still decoding payloads produced before the fields were added. Fields
+//! without the attribute are always required, and every field after an
+//! `#[rlp(default)]` field must also be marked `#[rlp(default)]`.
+//!
+//! Fields marked `#[rlp(skip)]`, such as cached values derived from the
+//! others, are left out of the encoding and set to their `Default` value when
+//! decoding. They don't count towards the length of the list and can appear
+//! anywhere in the struct.
+//!
+//! Structures which grow by appending optional items to their list, like
+//! transactions gaining fields with new EIPs, can mark trailing `Option` fields
+//! with `#[rlp(trailing)]`. Such a field is only encoded when set, and decoded
+//! as `None` when the list ends before it. Only a prefix of the trailing fields
+//! may be set, encoding panics otherwise, and decoding a list with more items
+//! than fields fails with `RlpIncorrectListLen`. Trailing fields must come after
+//! all other encoded fields.
+//!
+//! Generic structs get an `Encodable` or `Decodable` bound on each field type
+//! mentioning a type parameter, so fields like `PhantomData<T>` (with
+//! `#[rlp(skip)]`) don't require anything of `T`. When these bounds don't fit,
+//! e.g. for recursive types, they can be replaced with
+//! `#[rlp(bound = "T: rlp::Encodable + rlp::Decodable")]` on the struct.
+//!
+//! A struct with a single field marked `#[rlp(transparent)]`, like a
+//! `BlockNumber(u64)` newtype, is encoded exactly as its field, as with
+//! `RlpEncodableWrapper`. A field marked `#[rlp(flatten)]` instead has the
+//! fields of its own derived struct spliced into the list, rather than nested
+//! in a list of their own, through the `rlp::EncodableFields` and
+//! `rlp::DecodableFields` traits. A flattened struct with defaulted or trailing
+//! fields must be the last encoded field, as they could otherwise be read from
+//! the items of the following fields, which fails to compile.
 
 #![warn(clippy::all, clippy::pedantic, clippy::nursery)]
 
 extern crate proc_macro;
 
+mod attr;
+mod bound;
 mod de;
 mod en;
 
@@ -29,28 +62,28 @@ use proc_macro::TokenStream;
 
 #[proc_macro_derive(RlpEncodable, attributes(rlp))]
 pub fn encodable(input: TokenStream) -> TokenStream {
-	let ast = syn::parse(input).unwrap();
+	let ast = syn::parse_macro_input!(input as syn::DeriveInput);
 	let gen = impl_encodable(&ast);
 	gen.into()
 }
 
 #[proc_macro_derive(RlpEncodableWrapper)]
 pub fn encodable_wrapper(input: TokenStream) -> TokenStream {
-	let ast = syn::parse(input).unwrap();
+	let ast = syn::parse_macro_input!(input as syn::DeriveInput);
 	let gen = impl_encodable_wrapper(&ast);
 	gen.into()
 }
 
 #[proc_macro_derive(RlpDecodable, attributes(rlp))]
 pub fn decodable(input: TokenStream) -> TokenStream {
-	let ast = syn::parse(input).unwrap();
+	let ast = syn::parse_macro_input!(input as syn::DeriveInput);
 	let gen = impl_decodable(&ast);
 	gen.into()
 }
 
 #[proc_macro_derive(RlpDecodableWrapper)]
 pub fn decodable_wrapper(input: TokenStream) -> TokenStream {
-	let ast = syn::parse(input).unwrap();
+	let ast = syn::parse_macro_input!(input as syn::DeriveInput);
 	let gen = impl_decodable_wrapper(&ast);
 	gen.into()
 }
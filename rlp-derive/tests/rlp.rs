@@ -6,8 +6,9 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use rlp::{decode, encode};
+use rlp::{decode, encode, DecoderError, RlpStream};
 use rlp_derive::{RlpDecodable, RlpDecodableWrapper, RlpEncodable, RlpEncodableWrapper};
+use std::marker::PhantomData;
 
 #[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
 struct Item {
@@ -69,3 +70,411 @@ fn test_encode_item_default() {
 	let out = encode(&item_some);
 	assert_eq!(decode(&out), Ok(item_some));
 }
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct ItemTrailingDefaults {
+	a: String,
+	b: u64,
+	#[rlp(default)]
+	c: u64,
+	#[rlp(default)]
+	d: Vec<u8>,
+}
+
+#[test]
+fn test_encode_item_trailing_defaults() {
+	let item = ItemTrailingDefaults { a: "dog".into(), b: 7, c: 42, d: vec![1, 2, 3] };
+	let out = encode(&item);
+	assert_eq!(decode(&out), Ok(item));
+}
+
+#[test]
+fn test_decode_item_trailing_defaults_from_shorter_list() {
+	let mut stream = RlpStream::new_list(3);
+	stream.append(&"dog").append(&7u64).append(&42u64);
+	let decoded: ItemTrailingDefaults = decode(&stream.out()).expect("missing trailing field is defaulted");
+	assert_eq!(decoded, ItemTrailingDefaults { a: "dog".into(), b: 7, c: 42, d: vec![] });
+
+	let mut stream = RlpStream::new_list(2);
+	stream.append(&"dog").append(&7u64);
+	let decoded: ItemTrailingDefaults = decode(&stream.out()).expect("missing trailing fields are defaulted");
+	assert_eq!(decoded, ItemTrailingDefaults { a: "dog".into(), b: 7, c: 0, d: vec![] });
+}
+
+#[test]
+fn test_decode_item_trailing_defaults_requires_other_fields() {
+	let mut stream = RlpStream::new_list(1);
+	stream.append(&"dog");
+	let decoded: Result<ItemTrailingDefaults, _> = decode(&stream.out());
+	assert_eq!(decoded, Err(DecoderError::RlpIsTooShort));
+}
+
+#[test]
+fn test_decode_item_trailing_defaults_propagates_errors() {
+	let mut stream = RlpStream::new_list(3);
+	stream.append(&"dog").append(&7u64).append(&"not a number, too long");
+	let decoded: Result<ItemTrailingDefaults, _> = decode(&stream.out());
+	assert_eq!(decoded, Err(DecoderError::RlpIsTooBig));
+}
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct ItemSkipped {
+	#[rlp(skip)]
+	hash_cache: Option<u64>,
+	a: String,
+	#[rlp(skip)]
+	len_cache: usize,
+	b: u64,
+	#[rlp(default)]
+	c: u64,
+	#[rlp(skip)]
+	c_cache: u64,
+}
+
+#[test]
+fn test_encode_item_skipped() {
+	let item = ItemSkipped { hash_cache: Some(1), a: "cat".into(), len_cache: 3, b: 7, c: 42, c_cache: 42 };
+
+	let mut stream = RlpStream::new_list(3);
+	stream.append(&"cat").append(&7u64).append(&42u64);
+	let expected = stream.out();
+	assert_eq!(encode(&item), expected);
+
+	let decoded: ItemSkipped = decode(&expected).expect("decode failure");
+	assert_eq!(decoded, ItemSkipped { hash_cache: None, a: "cat".into(), len_cache: 0, b: 7, c: 42, c_cache: 0 });
+
+	let mut stream = RlpStream::new_list(2);
+	stream.append(&"cat").append(&7u64);
+	let decoded: ItemSkipped = decode(&stream.out()).expect("missing trailing field is defaulted");
+	assert_eq!(decoded, ItemSkipped { hash_cache: None, a: "cat".into(), len_cache: 0, b: 7, c: 0, c_cache: 0 });
+
+	let mut stream = RlpStream::new_list(1);
+	stream.append(&"cat");
+	assert_eq!(decode::<ItemSkipped>(&stream.out()), Err(DecoderError::RlpIsTooShort));
+}
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct TupleSkipped(#[rlp(skip)] u32, u8, #[rlp(default)] Vec<u8>);
+
+#[test]
+fn test_encode_tuple_skipped() {
+	let item = TupleSkipped(5, 1, vec![2, 3]);
+	let out = encode(&item);
+	assert_eq!(out, vec![0xc4, 0x01, 0xc2, 0x02, 0x03]);
+	assert_eq!(decode(&out), Ok(TupleSkipped(0, 1, vec![2, 3])));
+	assert_eq!(decode(&[0xc1, 0x01]), Ok(TupleSkipped(0, 1, vec![])));
+}
+
+#[test]
+fn compile_errors() {
+	let t = trybuild::TestCases::new();
+	t.compile_fail("tests/ui/*.rs");
+}
+
+/// A transaction which optionally carries EIP-155 replay protection in trailing fields.
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct TrailingTransaction {
+	nonce: u64,
+	to: Vec<u8>,
+	#[rlp(trailing)]
+	chain_id: Option<u64>,
+	#[rlp(trailing)]
+	r: Option<Vec<u8>>,
+	#[rlp(trailing)]
+	s: Option<Vec<u8>>,
+	#[rlp(skip)]
+	hash_cache: Option<u64>,
+}
+
+fn trailing_transaction(chain_id: Option<u64>, r: Option<Vec<u8>>, s: Option<Vec<u8>>) -> TrailingTransaction {
+	TrailingTransaction { nonce: 9, to: vec![0x35; 20], chain_id, r, s, hash_cache: None }
+}
+
+#[test]
+fn test_encode_item_trailing() {
+	let legacy = trailing_transaction(None, None, None);
+	let mut stream = RlpStream::new_list(2);
+	stream.append(&9u64).append_list::<u8, _>(&[0x35u8; 20]);
+	let expected = stream.out();
+	assert_eq!(encode(&legacy), expected);
+	assert_eq!(decode(&expected), Ok(legacy));
+
+	let eip155 = trailing_transaction(Some(1), Some(vec![0xaa; 32]), Some(vec![0xbb; 32]));
+	let mut stream = RlpStream::new_list(5);
+	stream
+		.append(&9u64)
+		.append_list::<u8, _>(&[0x35u8; 20])
+		.append(&1u64)
+		.append(&vec![0xaau8; 32])
+		.append(&vec![0xbbu8; 32]);
+	let expected = stream.out();
+	assert_eq!(encode(&eip155), expected);
+	assert_eq!(decode(&expected), Ok(eip155));
+
+	let prefix = trailing_transaction(Some(1), None, None);
+	let mut stream = RlpStream::new_list(3);
+	stream.append(&9u64).append_list::<u8, _>(&[0x35u8; 20]).append(&1u64);
+	let expected = stream.out();
+	assert_eq!(encode(&prefix), expected);
+	assert_eq!(decode(&expected), Ok(prefix));
+}
+
+#[test]
+fn test_decode_item_trailing_errors() {
+	let mut stream = RlpStream::new_list(6);
+	stream.append(&9u64).append_list::<u8, _>(&[0x35u8; 20]).append(&1u64).append(&"r").append(&"s").append(&"extra");
+	assert_eq!(decode::<TrailingTransaction>(&stream.out()), Err(DecoderError::RlpIncorrectListLen));
+
+	let mut stream = RlpStream::new_list(1);
+	stream.append(&9u64);
+	assert_eq!(decode::<TrailingTransaction>(&stream.out()), Err(DecoderError::RlpIsTooShort));
+}
+
+#[test]
+#[should_panic(expected = "#[rlp(trailing)] field set after an unset one")]
+fn test_encode_item_trailing_gap() {
+	encode(&trailing_transaction(None, Some(vec![0xaa; 32]), None));
+}
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct Envelope<T> {
+	payload: T,
+	nonce: u64,
+	history: Vec<T>,
+}
+
+#[test]
+fn test_encode_generic() {
+	let envelope = Envelope { payload: "cat".to_owned(), nonce: 3, history: vec!["dog".to_owned()] };
+	let mut stream = RlpStream::new_list(3);
+	stream.append(&"cat").append(&3u64).append_list::<String, _>(&["dog".to_owned()]);
+	let expected = stream.out();
+	assert_eq!(encode(&envelope), expected);
+	assert_eq!(decode(&expected), Ok(envelope));
+
+	let envelope = Envelope { payload: Item { a: "cat".into() }, nonce: 4, history: vec![] };
+	assert_eq!(decode(&encode(&envelope)), Ok(envelope));
+}
+
+/// A marker type, which is neither `Encodable` nor `Decodable`.
+#[derive(Debug, PartialEq)]
+struct Marker;
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct Typed<T> {
+	value: u64,
+	#[rlp(skip)]
+	_marker: PhantomData<T>,
+}
+
+#[test]
+fn test_encode_phantom_data() {
+	let typed: Typed<Marker> = Typed { value: 7, _marker: PhantomData };
+	assert_eq!(encode(&typed), vec![0xc1, 0x07]);
+	assert_eq!(decode(&[0xc1, 0x07]), Ok(typed));
+}
+
+#[derive(RlpEncodable)]
+struct Borrowed<'a> {
+	data: &'a [u8],
+	name: &'a str,
+}
+
+#[test]
+fn test_encode_borrowed() {
+	let data = [1u8, 2, 3];
+	let borrowed = Borrowed { data: &data, name: "cat" };
+	let mut stream = RlpStream::new_list(2);
+	stream.append(&&data[..]).append(&"cat");
+	assert_eq!(encode(&borrowed), stream.out());
+}
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct Fixed<T, const N: usize>
+where
+	T: Copy,
+{
+	items: [T; N],
+}
+
+#[test]
+fn test_encode_const_generic() {
+	let fixed = Fixed { items: [1u16, 2, 0x300] };
+	let mut stream = RlpStream::new_list(1);
+	stream.append(&[1u16, 2, 0x300]);
+	let expected = stream.out();
+	assert_eq!(encode(&fixed), expected);
+	assert_eq!(decode(&expected), Ok(fixed));
+}
+
+/// A recursive type, for which the inferred `Tree<T>: Encodable` bound would be cyclic.
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+#[rlp(bound = "T: rlp::Encodable + rlp::Decodable")]
+struct Tree<T> {
+	value: T,
+	children: Vec<Tree<T>>,
+}
+
+#[test]
+fn test_encode_custom_bound() {
+	let tree = Tree { value: 1u8, children: vec![Tree { value: 2, children: vec![] }] };
+	assert_eq!(encode(&tree), vec![0xc5, 0x01, 0xc3, 0xc2, 0x02, 0xc0]);
+	assert_eq!(decode(&[0xc5, 0x01, 0xc3, 0xc2, 0x02, 0xc0]), Ok(tree));
+}
+
+#[derive(Debug, PartialEq, RlpEncodableWrapper, RlpDecodableWrapper)]
+struct GenericWrapper<T>(Vec<T>);
+
+#[test]
+fn test_encode_generic_wrapper() {
+	let wrapper = GenericWrapper(vec![1u64, 2]);
+	assert_eq!(encode(&wrapper), vec![0xc2, 0x01, 0x02]);
+	assert_eq!(decode(&[0xc2, 0x01, 0x02]), Ok(wrapper));
+}
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+#[rlp(transparent)]
+struct BlockNumber(u64);
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+#[rlp(transparent)]
+struct Numbers {
+	items: Vec<u64>,
+}
+
+#[test]
+fn test_encode_transparent() {
+	let number = BlockNumber(0x1234);
+	assert_eq!(encode(&number), encode(&0x1234u64));
+	assert_eq!(encode(&number), vec![0x82, 0x12, 0x34]);
+	assert_eq!(decode(&[0x82, 0x12, 0x34]), Ok(number));
+
+	let numbers = Numbers { items: vec![1, 2] };
+	let mut stream = RlpStream::new();
+	stream.append_list::<u64, _>(&[1, 2]);
+	let expected = stream.out();
+	assert_eq!(encode(&numbers), expected);
+	assert_eq!(decode(&expected), Ok(numbers));
+}
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct Signature {
+	v: u64,
+	r: Vec<u8>,
+	s: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct SignedMessage {
+	nonce: u64,
+	#[rlp(flatten)]
+	signature: Signature,
+	body: String,
+}
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct ExtendedMessage {
+	#[rlp(flatten)]
+	message: SignedMessage,
+	#[rlp(trailing)]
+	extra: Option<u64>,
+}
+
+fn signed_message() -> SignedMessage {
+	SignedMessage { nonce: 3, signature: Signature { v: 27, r: vec![0xaa; 2], s: vec![0xbb; 2] }, body: "cat".into() }
+}
+
+#[test]
+fn test_encode_flatten() {
+	let message = signed_message();
+	let mut stream = RlpStream::new_list(5);
+	stream
+		.append(&3u64)
+		.append(&27u64)
+		.append_list::<u8, _>(&[0xaa; 2])
+		.append_list::<u8, _>(&[0xbb; 2])
+		.append(&"cat");
+	let expected = stream.out();
+	assert_eq!(encode(&message), expected);
+	assert_eq!(decode(&expected), Ok(message));
+
+	let mut stream = RlpStream::new_list(4);
+	stream.append(&3u64).append(&27u64).append_list::<u8, _>(&[0xaa; 2]).append_list::<u8, _>(&[0xbb; 2]);
+	assert_eq!(decode::<SignedMessage>(&stream.out()), Err(DecoderError::RlpIsTooShort));
+}
+
+#[test]
+fn test_encode_flatten_nested() {
+	let short = ExtendedMessage { message: signed_message(), extra: None };
+	let mut stream = RlpStream::new_list(5);
+	stream
+		.append(&3u64)
+		.append(&27u64)
+		.append_list::<u8, _>(&[0xaa; 2])
+		.append_list::<u8, _>(&[0xbb; 2])
+		.append(&"cat");
+	let expected = stream.out();
+	assert_eq!(encode(&short), expected);
+	assert_eq!(decode(&expected), Ok(short));
+
+	let long = ExtendedMessage { message: signed_message(), extra: Some(9) };
+	let mut stream = RlpStream::new_list(6);
+	stream
+		.append(&3u64)
+		.append(&27u64)
+		.append_list::<u8, _>(&[0xaa; 2])
+		.append_list::<u8, _>(&[0xbb; 2])
+		.append(&"cat")
+		.append(&9u64);
+	let expected = stream.out();
+	assert_eq!(encode(&long), expected);
+	assert_eq!(decode(&expected), Ok(long));
+
+	let mut stream = RlpStream::new_list(7);
+	stream
+		.append(&3u64)
+		.append(&27u64)
+		.append_list::<u8, _>(&[0xaa; 2])
+		.append_list::<u8, _>(&[0xbb; 2])
+		.append(&"cat")
+		.append(&9u64)
+		.append(&10u64);
+	assert_eq!(decode::<ExtendedMessage>(&stream.out()), Err(DecoderError::RlpIncorrectListLen));
+}
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct OptionalInner {
+	a: u64,
+	#[rlp(trailing)]
+	b: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct OptionalOuter {
+	c: u64,
+	#[rlp(flatten)]
+	inner: OptionalInner,
+	#[rlp(skip)]
+	d: u64,
+}
+
+#[derive(Debug, PartialEq, RlpEncodable, RlpDecodable)]
+struct GenericMessage<S> {
+	#[rlp(flatten)]
+	signature: S,
+	body: String,
+}
+
+#[test]
+fn test_encode_flatten_optional_last() {
+	for value in [
+		OptionalOuter { c: 5, inner: OptionalInner { a: 1, b: None }, d: 0 },
+		OptionalOuter { c: 5, inner: OptionalInner { a: 1, b: Some(2) }, d: 0 },
+	] {
+		assert_eq!(decode(&encode(&value)), Ok(value));
+	}
+
+	let message =
+		GenericMessage { signature: Signature { v: 27, r: vec![0xaa; 2], s: vec![0xbb; 2] }, body: "cat".into() };
+	assert_eq!(decode(&encode(&message)), Ok(message));
+}
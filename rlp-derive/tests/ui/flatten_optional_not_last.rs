@@ -0,0 +1,18 @@
+use rlp_derive::RlpDecodable;
+
+#[derive(RlpDecodable)]
+struct Inner {
+	a: u64,
+	#[rlp(trailing)]
+	b: Option<u64>,
+}
+
+#[derive(RlpDecodable)]
+struct Outer {
+	#[rlp(flatten)]
+	inner: Inner,
+	#[rlp(default)]
+	c: u64,
+}
+
+fn main() {}
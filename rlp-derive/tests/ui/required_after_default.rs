@@ -0,0 +1,10 @@
+use rlp_derive::RlpDecodable;
+
+#[derive(RlpDecodable)]
+struct Item {
+	#[rlp(default)]
+	a: u64,
+	b: u64,
+}
+
+fn main() {}
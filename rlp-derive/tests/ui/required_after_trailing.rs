@@ -0,0 +1,10 @@
+use rlp_derive::RlpEncodable;
+
+#[derive(RlpEncodable)]
+struct Item {
+	#[rlp(trailing)]
+	a: Option<u64>,
+	b: u64,
+}
+
+fn main() {}
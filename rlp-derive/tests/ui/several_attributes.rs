@@ -0,0 +1,10 @@
+use rlp_derive::RlpDecodable;
+
+#[derive(RlpDecodable)]
+struct Item {
+	#[rlp(default)]
+	#[rlp(skip)]
+	a: u64,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use rlp_derive::RlpEncodable;
+
+#[derive(RlpEncodable)]
+#[rlp(bounds = "T: rlp::Encodable")]
+struct Item<T> {
+	a: T,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use rlp_derive::RlpDecodable;
+
+#[derive(RlpDecodable)]
+struct Item {
+	a: u64,
+	#[rlp(trailing)]
+	b: Vec<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use rlp_derive::RlpEncodable;
+
+#[derive(RlpEncodable)]
+#[rlp(transparent)]
+struct Item {
+	a: u64,
+	b: u64,
+}
+
+fn main() {}
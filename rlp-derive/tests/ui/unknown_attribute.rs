@@ -0,0 +1,9 @@
+use rlp_derive::RlpEncodable;
+
+#[derive(RlpEncodable)]
+struct Item {
+	#[rlp(ignore)]
+	a: u64,
+}
+
+fn main() {}
@@ -9,6 +9,60 @@
 //! benchmarking for rlp
 
 use criterion::{criterion_group, criterion_main, Criterion};
+use primitive_types::{H160, U256};
+use rlp::{Encodable, RlpStream};
+
+/// A legacy Ethereum transaction.
+struct Transaction {
+	nonce: u64,
+	gas_price: U256,
+	gas: u64,
+	to: H160,
+	value: U256,
+	data: Vec<u8>,
+	v: u64,
+	r: U256,
+	s: U256,
+}
+
+impl Transaction {
+	fn synthetic(i: u64) -> Self {
+		Transaction {
+			nonce: i,
+			gas_price: U256::from(20_000_000_000u64),
+			gas: 21_000 + i,
+			to: H160::from_low_u64_be(i),
+			value: U256::from(i) << 64usize,
+			data: vec![i as u8; (i % 100) as usize],
+			v: 37,
+			r: U256::MAX - U256::from(i),
+			s: U256::MAX / U256::from(i + 1),
+		}
+	}
+}
+
+impl Encodable for Transaction {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(9);
+		s.append(&self.nonce).append(&self.gas_price).append(&self.gas).append(&self.to).append(&self.value);
+		s.append(&self.data).append(&self.v).append(&self.r).append(&self.s);
+	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		let fields = [
+			self.nonce.rlp_len_hint()?,
+			self.gas_price.rlp_len_hint()?,
+			self.gas.rlp_len_hint()?,
+			self.to.rlp_len_hint()?,
+			self.value.rlp_len_hint()?,
+			self.data.rlp_len_hint()?,
+			self.v.rlp_len_hint()?,
+			self.r.rlp_len_hint()?,
+			self.s.rlp_len_hint()?,
+		];
+		Some(rlp::encoded_len_bound(fields.iter().sum()))
+	}
+}
 
 fn bench_encode(c: &mut Criterion) {
 	c.bench_function("encode_u64", |b| {
@@ -35,6 +89,44 @@ fn bench_encode(c: &mut Criterion) {
 			let _ = stream.out();
 		})
 	});
+	c.bench_function("encode_10k_u64_separately", |b| {
+		b.iter(|| {
+			let mut out = Vec::new();
+			for i in 0..10_000u64 {
+				out.extend_from_slice(&rlp::encode(&i));
+			}
+			out
+		})
+	});
+	c.bench_function("encode_10k_u64_into_buffer", |b| {
+		let mut out = bytes::BytesMut::new();
+		b.iter(|| {
+			out.clear();
+			for i in 0..10_000u64 {
+				rlp::Encodable::rlp_bytes_into(&i, &mut out);
+			}
+		})
+	});
+	let block: Vec<Transaction> = (0..10_000).map(Transaction::synthetic).collect();
+	c.bench_function("encode_10k_transactions_growing", |b| {
+		b.iter(|| {
+			let mut stream = RlpStream::new_list(block.len());
+			for transaction in &block {
+				stream.append(transaction);
+			}
+			stream.out()
+		})
+	});
+	c.bench_function("encode_10k_transactions_reserved", |b| {
+		b.iter(|| {
+			let len = rlp::encoded_len_bound(block.iter().filter_map(Encodable::rlp_len_hint).sum());
+			let mut stream = RlpStream::with_capacity(block.len(), len);
+			for transaction in &block {
+				stream.append(transaction);
+			}
+			stream.out()
+		})
+	});
 	c.bench_function("encode_nested_empty_lists", |b| {
 		b.iter(|| {
 			// [ [], [[]], [ [], [[]] ] ]
@@ -87,6 +179,19 @@ fn bench_decode(c: &mut Criterion) {
 			}
 		});
 	});
+	c.bench_function("decode_1000_u64_reverse", |b| {
+		let mut stream = rlp::RlpStream::new_list(1000);
+		for i in 0..1000u64 {
+			stream.append(&i);
+		}
+		let data = stream.out();
+		b.iter(|| {
+			let rlp = rlp::Rlp::new(&data);
+			for i in (0..1000).rev() {
+				let _: u64 = rlp.val_at(i).unwrap();
+			}
+		});
+	});
 	c.bench_function("decode_nested_empty_lists", |b| {
 		b.iter(|| {
 			// [ [], [[]], [ [], [[]] ] ]
@@ -0,0 +1,44 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rlp::{stream_decode::ItemReader, Rlp, RlpStream};
+
+// Decoding arbitrary input must fail with an error rather than panic.
+fn walk(rlp: &Rlp) {
+	let _ = rlp.to_string();
+	let _ = rlp.is_int();
+	let _ = rlp.as_val::<u64>();
+	let _ = rlp.as_val::<Vec<u8>>();
+	let _ = rlp.as_val::<String>();
+	let _ = rlp.as_list::<u64>();
+	if let Ok(count) = rlp.item_count() {
+		for index in (0..count).rev() {
+			if let Ok(item) = rlp.at(index) {
+				walk(&item);
+			}
+		}
+	}
+}
+
+fuzz_target!(|data: &[u8]| {
+	walk(&Rlp::new(data));
+	walk(&Rlp::new_lenient(data));
+	let _ = rlp::decode_list::<Vec<u8>>(data);
+	let _ = rlp::decode_located::<(u64, Vec<u8>)>(data);
+
+	// Input accepted as a raw item decodes as a single item of that length.
+	if RlpStream::new().append_raw_validated(data).is_ok() {
+		assert_eq!(Rlp::new(data).payload_info().unwrap().total(), Ok(data.len()));
+	}
+
+	let mut items = ItemReader::with_max_size(data, 1 << 20);
+	while let Ok(Some(_)) = items.read_item() {}
+});
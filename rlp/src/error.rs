@@ -12,6 +12,7 @@ use std::error::Error as StdError;
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 /// Error concerning the RLP decoder.
+#[non_exhaustive]
 pub enum DecoderError {
 	/// Data has additional bytes at the end of the valid RLP fragment.
 	RlpIsTooBig,
@@ -33,6 +34,10 @@ pub enum DecoderError {
 	RlpInconsistentLengthAndData,
 	/// Declared length is invalid and results in overflow
 	RlpInvalidLength,
+	/// Lists are nested deeper than the limit of the `Rlp` view.
+	DepthLimitExceeded,
+	/// A list has more items than the limit it is decoded with.
+	ListLimitExceeded,
 	/// Custom rlp decoding error.
 	Custom(&'static str),
 }
@@ -49,3 +54,26 @@ impl fmt::Display for DecoderError {
 		fmt::Debug::fmt(&self, f)
 	}
 }
+
+/// A decoding error along with the byte offset in the input of the innermost item which
+/// failed to decode, as returned by `decode_located`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LocatedError {
+	/// The offset of the first byte of the item.
+	pub offset: usize,
+	/// The error the item failed with.
+	pub error: DecoderError,
+}
+
+#[cfg(feature = "std")]
+impl StdError for LocatedError {
+	fn source(&self) -> Option<&(dyn StdError + 'static)> {
+		Some(&self.error)
+	}
+}
+
+impl fmt::Display for LocatedError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} at byte offset {}", self.error, self.offset)
+	}
+}
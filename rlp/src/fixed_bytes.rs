@@ -0,0 +1,73 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::cmp;
+
+use crate::{
+	error::DecoderError,
+	rlpin::Rlp,
+	stream::RlpStream,
+	traits::{Decodable, Encodable},
+};
+
+/// A byte array encoded as a byte string of exactly `N` bytes.
+///
+/// A plain `[u8; N]` is encoded as a list of `N` single byte items, like any other array.
+/// Decoding fails with `RlpIsTooShort` or `RlpIsTooBig` if the length differs.
+///
+/// ```
+/// use rlp::FixedBytes;
+/// let bytes = FixedBytes([0xca, 0xfe]);
+/// assert_eq!(rlp::encode(&bytes).to_vec(), vec![0x82, 0xca, 0xfe]);
+/// assert_eq!(rlp::encode(&bytes.0).to_vec(), vec![0xc4, 0x81, 0xca, 0x81, 0xfe]);
+/// assert_eq!(rlp::decode::<FixedBytes<2>>(&[0x82, 0xca, 0xfe]), Ok(bytes));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FixedBytes<const N: usize>(pub [u8; N]);
+
+impl<const N: usize> From<[u8; N]> for FixedBytes<N> {
+	fn from(bytes: [u8; N]) -> Self {
+		FixedBytes(bytes)
+	}
+}
+
+impl<const N: usize> From<FixedBytes<N>> for [u8; N] {
+	fn from(bytes: FixedBytes<N>) -> Self {
+		bytes.0
+	}
+}
+
+impl<const N: usize> AsRef<[u8]> for FixedBytes<N> {
+	fn as_ref(&self) -> &[u8] {
+		&self.0
+	}
+}
+
+impl<const N: usize> Encodable for FixedBytes<N> {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.encoder().encode_value(&self.0);
+	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		Some(crate::encoded_len_bound(N))
+	}
+}
+
+impl<const N: usize> Decodable for FixedBytes<N> {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		rlp.decoder().decode_value(|bytes| match bytes.len().cmp(&N) {
+			cmp::Ordering::Less => Err(DecoderError::RlpIsTooShort),
+			cmp::Ordering::Greater => Err(DecoderError::RlpIsTooBig),
+			cmp::Ordering::Equal => {
+				let mut result = [0u8; N];
+				result.copy_from_slice(bytes);
+				Ok(FixedBytes(result))
+			}
+		})
+	}
+}
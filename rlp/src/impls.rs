@@ -6,19 +6,29 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+#[cfg(not(feature = "std"))]
+use alloc::borrow::Cow;
+#[cfg(not(feature = "std"))]
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
 #[cfg(not(feature = "std"))]
 use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
 use bytes::{Bytes, BytesMut};
+use core::convert::TryFrom;
 use core::iter::{empty, once};
 use core::{mem, str};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 
 use crate::error::DecoderError;
 use crate::rlpin::Rlp;
 use crate::stream::RlpStream;
-use crate::traits::{Decodable, Encodable};
+use crate::traits::{Decodable, DecodableBorrowed, Encodable};
 
 pub fn decode_usize(bytes: &[u8]) -> Result<usize, DecoderError> {
 	match bytes.len() {
+		0 => Err(DecoderError::RlpIsTooShort),
 		l if l <= mem::size_of::<usize>() => {
 			if bytes[0] == 0 {
 				return Err(DecoderError::RlpInvalidIndirection);
@@ -38,6 +48,10 @@ impl<T: Encodable + ?Sized> Encodable for Box<T> {
 	fn rlp_append(&self, s: &mut RlpStream) {
 		Encodable::rlp_append(&**self, s)
 	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		Encodable::rlp_len_hint(&**self)
+	}
 }
 
 impl<T: Decodable> Decodable for Box<T> {
@@ -50,6 +64,10 @@ impl Encodable for bool {
 	fn rlp_append(&self, s: &mut RlpStream) {
 		s.encoder().encode_iter(once(if *self { 1u8 } else { 0 }));
 	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		Some(1)
+	}
 }
 
 impl Decodable for bool {
@@ -66,12 +84,20 @@ impl<'a> Encodable for &'a [u8] {
 	fn rlp_append(&self, s: &mut RlpStream) {
 		s.encoder().encode_value(self);
 	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		Some(crate::encoded_len_bound(self.len()))
+	}
 }
 
 impl Encodable for Vec<u8> {
 	fn rlp_append(&self, s: &mut RlpStream) {
 		s.encoder().encode_value(self);
 	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		Some(crate::encoded_len_bound(self.len()))
+	}
 }
 
 impl Decodable for Vec<u8> {
@@ -84,6 +110,10 @@ impl Encodable for Bytes {
 	fn rlp_append(&self, s: &mut RlpStream) {
 		s.encoder().encode_value(self);
 	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		Some(crate::encoded_len_bound(self.len()))
+	}
 }
 
 impl Decodable for Bytes {
@@ -96,6 +126,10 @@ impl Encodable for BytesMut {
 	fn rlp_append(&self, s: &mut RlpStream) {
 		s.encoder().encode_value(self);
 	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		Some(crate::encoded_len_bound(self.len()))
+	}
 }
 
 impl Decodable for BytesMut {
@@ -104,6 +138,8 @@ impl Decodable for BytesMut {
 	}
 }
 
+/// `None` is encoded as the empty list and `Some(value)` as a list holding just `value`,
+/// so the two stay distinct for every `T`. See `Nullable` for encoding `None` as null.
 impl<T> Encodable for Option<T>
 where
 	T: Encodable,
@@ -135,6 +171,158 @@ where
 	}
 }
 
+/// Arrays are encoded as a list of `N` items, like `append_list` does for slices. This
+/// includes `[u8; N]`, which is a list of single bytes rather than a byte string; use
+/// `FixedBytes` to encode it as a byte string.
+impl<T: Encodable, const N: usize> Encodable for [T; N] {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(N);
+		for item in self {
+			s.append(item);
+		}
+	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		let payload_len = self.iter().map(Encodable::rlp_len_hint).sum::<Option<usize>>()?;
+		Some(crate::encoded_len_bound(payload_len))
+	}
+}
+
+impl<T: Decodable, const N: usize> Decodable for [T; N] {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		if rlp.item_count()? != N {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+		let items = rlp.iter_limited(N).map(|item| item?.as_val()).collect::<Result<Vec<T>, DecoderError>>()?;
+		<[T; N]>::try_from(items).map_err(|_| DecoderError::RlpIncorrectListLen)
+	}
+}
+
+// Collections are encoded as a list of their items, like `append_list` does. Unlike
+// `Vec<u8>`, a `VecDeque<u8>` is therefore a list of single bytes.
+impl<T: Encodable> Encodable for VecDeque<T> {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(self.len());
+		for item in self {
+			s.append(item);
+		}
+	}
+}
+
+impl<T: Decodable> Decodable for VecDeque<T> {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		rlp.iter_limited(rlp.max_items()).map(|item| item?.as_val()).collect()
+	}
+}
+
+/// Sets are encoded as a list of their items in increasing order. Decoding requires
+/// strictly increasing items, which keeps the encoding canonical.
+impl<T: Encodable> Encodable for BTreeSet<T> {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(self.len());
+		for item in self {
+			s.append(item);
+		}
+	}
+}
+
+impl<T: Decodable + Ord> Decodable for BTreeSet<T> {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		let mut set = BTreeSet::new();
+		for item in rlp.iter_limited(rlp.max_items()) {
+			let item = item?.as_val()?;
+			if set.iter().next_back().is_some_and(|last| &item <= last) {
+				return Err(DecoderError::Custom("set items are not in strictly increasing order"));
+			}
+			set.insert(item);
+		}
+		Ok(set)
+	}
+}
+
+/// Maps are encoded as a list of `[key, value]` lists in increasing key order. Decoding
+/// requires strictly increasing keys, which keeps the encoding canonical.
+impl<K: Encodable, V: Encodable> Encodable for BTreeMap<K, V> {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(self.len());
+		for (key, value) in self {
+			s.begin_list(2).append(key).append(value);
+		}
+	}
+}
+
+impl<K: Decodable + Ord, V: Decodable> Decodable for BTreeMap<K, V> {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		let mut map = BTreeMap::new();
+		for entry in rlp.iter_limited(rlp.max_items()) {
+			let (key, value) = entry?.as_val()?;
+			if map.keys().next_back().is_some_and(|last| &key <= last) {
+				return Err(DecoderError::Custom("map keys are not in strictly increasing order"));
+			}
+			map.insert(key, value);
+		}
+		Ok(map)
+	}
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> Encodable for smallvec::SmallVec<A>
+where
+	A::Item: Encodable,
+{
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(self.len());
+		for item in self {
+			s.append(item);
+		}
+	}
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> Decodable for smallvec::SmallVec<A>
+where
+	A::Item: Decodable,
+{
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		rlp.iter_limited(rlp.max_items()).map(|item| item?.as_val()).collect()
+	}
+}
+
+// Tuples are encoded as a list of their elements, and `()` as the empty list.
+macro_rules! impl_tuple {
+	($len: expr; $($name: ident $index: tt),*) => {
+		impl<$($name: Encodable),*> Encodable for ($($name,)*) {
+			fn rlp_append(&self, s: &mut RlpStream) {
+				s.begin_list($len);
+				$(s.append(&self.$index);)*
+			}
+		}
+
+		impl<$($name: Decodable),*> Decodable for ($($name,)*) {
+			fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+				if rlp.item_count()? != $len {
+					return Err(DecoderError::RlpIncorrectListLen);
+				}
+				Ok(($(rlp.val_at::<$name>($index)?,)*))
+			}
+		}
+	};
+}
+
+impl_tuple!(0;);
+impl_tuple!(1; A 0);
+impl_tuple!(2; A 0, B 1);
+impl_tuple!(3; A 0, B 1, C 2);
+impl_tuple!(4; A 0, B 1, C 2, D 3);
+impl_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+impl_tuple!(9; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+impl_tuple!(10; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+impl_tuple!(11; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+impl_tuple!(12; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
+
 impl Encodable for u8 {
 	fn rlp_append(&self, s: &mut RlpStream) {
 		if *self != 0 {
@@ -143,6 +331,10 @@ impl Encodable for u8 {
 			s.encoder().encode_iter(empty());
 		}
 	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		Some(2)
+	}
 }
 
 impl Decodable for u8 {
@@ -164,6 +356,10 @@ macro_rules! impl_encodable_for_u {
 				let buffer = self.to_be_bytes();
 				s.encoder().encode_value(&buffer[leading_empty_bytes..]);
 			}
+
+			fn rlp_len_hint(&self) -> Option<usize> {
+				Some(1 + mem::size_of::<$name>())
+			}
 		}
 	};
 }
@@ -206,6 +402,10 @@ impl Encodable for usize {
 	fn rlp_append(&self, s: &mut RlpStream) {
 		(*self as u64).rlp_append(s);
 	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		(*self as u64).rlp_len_hint()
+	}
 }
 
 impl Decodable for usize {
@@ -218,12 +418,38 @@ impl<'a> Encodable for &'a str {
 	fn rlp_append(&self, s: &mut RlpStream) {
 		s.encoder().encode_value(self.as_bytes());
 	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		Some(crate::encoded_len_bound(self.len()))
+	}
+}
+
+impl<'a> DecodableBorrowed<'a> for &'a [u8] {
+	fn decode_borrowed(rlp: &Rlp<'a>) -> Result<Self, DecoderError> {
+		rlp.decoder().decode_value_borrowed()
+	}
+}
+
+impl<'a> DecodableBorrowed<'a> for &'a str {
+	fn decode_borrowed(rlp: &Rlp<'a>) -> Result<Self, DecoderError> {
+		str::from_utf8(rlp.decoder().decode_value_borrowed()?).map_err(|_| DecoderError::RlpExpectedToBeData)
+	}
+}
+
+impl<'a> DecodableBorrowed<'a> for Cow<'a, [u8]> {
+	fn decode_borrowed(rlp: &Rlp<'a>) -> Result<Self, DecoderError> {
+		rlp.decoder().decode_value_borrowed().map(Cow::Borrowed)
+	}
 }
 
 impl Encodable for String {
 	fn rlp_append(&self, s: &mut RlpStream) {
 		s.encoder().encode_value(self.as_bytes());
 	}
+
+	fn rlp_len_hint(&self) -> Option<usize> {
+		Some(crate::encoded_len_bound(self.len()))
+	}
 }
 
 impl Decodable for String {
@@ -237,3 +463,90 @@ impl Decodable for String {
 		})
 	}
 }
+
+#[cfg(feature = "std")]
+mod net {
+	use core::{cmp::Ordering, convert::TryFrom};
+	use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+	use super::*;
+
+	fn check_len(bytes: &[u8], len: usize) -> Result<(), DecoderError> {
+		match bytes.len().cmp(&len) {
+			Ordering::Less => Err(DecoderError::RlpIsTooShort),
+			Ordering::Greater => Err(DecoderError::RlpIsTooBig),
+			Ordering::Equal => Ok(()),
+		}
+	}
+
+	/// Encoded as its 4 octets.
+	impl Encodable for Ipv4Addr {
+		fn rlp_append(&self, s: &mut RlpStream) {
+			s.encoder().encode_value(&self.octets());
+		}
+	}
+
+	impl Decodable for Ipv4Addr {
+		fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+			rlp.decoder().decode_value(|bytes| {
+				check_len(bytes, 4)?;
+				Ok(Ipv4Addr::from(<[u8; 4]>::try_from(bytes).expect("length checked above; qed")))
+			})
+		}
+	}
+
+	/// Encoded as its 16 octets.
+	impl Encodable for Ipv6Addr {
+		fn rlp_append(&self, s: &mut RlpStream) {
+			s.encoder().encode_value(&self.octets());
+		}
+	}
+
+	impl Decodable for Ipv6Addr {
+		fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+			rlp.decoder().decode_value(|bytes| {
+				check_len(bytes, 16)?;
+				Ok(Ipv6Addr::from(<[u8; 16]>::try_from(bytes).expect("length checked above; qed")))
+			})
+		}
+	}
+
+	/// Encoded as 4 or 16 octets, depending on the version.
+	impl Encodable for IpAddr {
+		fn rlp_append(&self, s: &mut RlpStream) {
+			match self {
+				IpAddr::V4(ip) => ip.rlp_append(s),
+				IpAddr::V6(ip) => ip.rlp_append(s),
+			}
+		}
+	}
+
+	impl Decodable for IpAddr {
+		fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+			match rlp.size() {
+				4 => Ipv4Addr::decode(rlp).map(IpAddr::V4),
+				16 => Ipv6Addr::decode(rlp).map(IpAddr::V6),
+				_ if rlp.is_list() => Err(DecoderError::RlpExpectedToBeData),
+				_ => Err(DecoderError::Custom("IP address must have 4 or 16 bytes")),
+			}
+		}
+	}
+
+	/// Encoded as the list `[ip, port]`. The flow info and scope id of IPv6 sockets are not encoded.
+	impl Encodable for SocketAddr {
+		fn rlp_append(&self, s: &mut RlpStream) {
+			s.begin_list(2);
+			s.append(&self.ip());
+			s.append(&self.port());
+		}
+	}
+
+	impl Decodable for SocketAddr {
+		fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+			if rlp.item_count()? != 2 {
+				return Err(DecoderError::RlpIncorrectListLen);
+			}
+			Ok(SocketAddr::new(rlp.val_at(0)?, rlp.val_at(1)?))
+		}
+	}
+}
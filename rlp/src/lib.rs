@@ -31,6 +31,10 @@
 //! * You are working on input data.
 //! * You want to get view onto rlp-slice.
 //! * You don't want to decode whole rlp at once.
+//!
+//!### Use `stream_decode::ItemReader` when:
+//! * You decode items read from an `io::Read`, e.g. a file.
+//! * The input is too big to be held in memory at once.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -38,21 +42,30 @@
 extern crate alloc;
 
 mod error;
+mod fixed_bytes;
 mod impls;
+mod nullable;
 mod rlpin;
 mod stream;
+#[cfg(feature = "std")]
+pub mod stream_decode;
 mod traits;
 
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
-use bytes::BytesMut;
-use core::borrow::Borrow;
+use bytes::{BufMut, Bytes, BytesMut};
+use core::{
+	mem,
+	sync::atomic::{AtomicUsize, Ordering},
+};
 
 pub use self::{
-	error::DecoderError,
-	rlpin::{PayloadInfo, Prototype, Rlp, RlpIterator},
+	error::{DecoderError, LocatedError},
+	fixed_bytes::FixedBytes,
+	nullable::Nullable,
+	rlpin::{PayloadInfo, Prototype, Rlp, RlpIterator, RlpLimitedIterator, DEFAULT_MAX_DEPTH},
 	stream::RlpStream,
-	traits::{Decodable, Encodable},
+	traits::{Decodable, DecodableBorrowed, DecodableFields, Encodable, EncodableFields},
 };
 
 /// The RLP encoded empty data (used to mean "null value").
@@ -75,12 +88,74 @@ where
 	rlp.as_val()
 }
 
-pub fn decode_list<T>(bytes: &[u8]) -> Vec<T>
+/// Like `decode`, but on failure also reports the offset in `bytes` of the innermost item
+/// which failed to decode.
+///
+/// Items are located through `Rlp::as_val`, so an error returned by a `Decodable` impl
+/// without decoding any item is reported at the item it was decoding.
+///
+/// ```
+/// // A list of a number and a number with a leading zero byte.
+/// let data = vec![0xc4, 0x01, 0x82, 0x00, 0x02];
+/// let err = rlp::decode_located::<(u64, u64)>(&data).unwrap_err();
+/// assert_eq!(err.offset, 2);
+/// assert_eq!(err.error, rlp::DecoderError::RlpInvalidIndirection);
+/// ```
+pub fn decode_located<T>(bytes: &[u8]) -> Result<T, LocatedError>
+where
+	T: Decodable,
+{
+	let failed_item = AtomicUsize::new(rlpin::NO_FAILED_ITEM);
+	let rlp = Rlp::new_recording_failures(bytes, &failed_item);
+	rlp.as_val().map_err(|error| {
+		let address = failed_item.load(Ordering::Relaxed);
+		let offset = if address == rlpin::NO_FAILED_ITEM { 0 } else { address - bytes.as_ptr() as usize };
+		LocatedError { offset, error }
+	})
+}
+
+/// Shortcut function to decode a list of items.
+///
+/// ```
+/// let data = vec![0xc8, 0x83, b'c', b'a', b't', 0x83, b'd', b'o', b'g'];
+/// let animals: Vec<String> = rlp::decode_list(&data).expect("could not decode");
+/// assert_eq!(animals, vec!["cat".to_owned(), "dog".to_owned()]);
+/// ```
+pub fn decode_list<T>(bytes: &[u8]) -> Result<Vec<T>, DecoderError>
 where
 	T: Decodable,
 {
 	let rlp = Rlp::new(bytes);
-	rlp.as_list().expect("trusted rlp should be valid")
+	if !rlp.is_list() {
+		return Err(DecoderError::RlpExpectedToBeList);
+	}
+	// Walk the payload directly, so that malformed items are reported instead of ending the list.
+	let info = rlp.payload_info()?;
+	let mut payload = &bytes[info.header_len..info.total()?];
+	let mut result = Vec::new();
+	while !payload.is_empty() {
+		let item = Rlp::new(payload).payload_info()?;
+		let (item, rest) = payload.split_at(item.total()?);
+		result.push(Rlp::new(item).as_val()?);
+		payload = rest;
+	}
+	Ok(result)
+}
+
+/// Upper bound of the length of the encoding of a byte string of `payload_len` bytes, or of
+/// a list whose encoded items take `payload_len` bytes.
+///
+/// ```
+/// assert_eq!(rlp::encoded_len_bound(3), rlp::encode(&"cat").len());
+/// assert!(rlp::encoded_len_bound(1) >= rlp::encode(&"a").len());
+/// ```
+pub const fn encoded_len_bound(payload_len: usize) -> usize {
+	if payload_len < 56 {
+		1 + payload_len
+	} else {
+		let len_of_len = (mem::size_of::<usize>() * 8 - payload_len.leading_zeros() as usize).div_ceil(8);
+		1 + len_of_len + payload_len
+	}
 }
 
 /// Shortcut function to encode structure into rlp.
@@ -94,17 +169,60 @@ pub fn encode<E>(object: &E) -> BytesMut
 where
 	E: Encodable,
 {
-	let mut stream = RlpStream::new();
+	let capacity = object.rlp_len_hint().unwrap_or(1024);
+	let mut stream = RlpStream::new_with_buffer(BytesMut::with_capacity(capacity));
 	stream.append(object);
 	stream.out()
 }
 
-pub fn encode_list<E, K>(object: &[K]) -> BytesMut
+/// Shortcut function to encode structure into rlp, appending it to `out`.
+///
+/// The structure is encoded in place at the end of `out`, which is only reallocated when it
+/// lacks the capacity.
+///
+/// ```
+/// let mut out = vec![0x01];
+/// rlp::encode_into(&"cat", &mut out);
+/// assert_eq!(out, vec![0x01, 0x83, b'c', b'a', b't']);
+/// ```
+pub fn encode_into<E>(object: &E, out: &mut Vec<u8>)
 where
 	E: Encodable,
-	K: Borrow<E>,
+{
+	// Lends the allocation of `out` to the stream, which needs a `BytesMut`.
+	let mut buffer = BytesMut::from(Bytes::from(mem::take(out)));
+	object.rlp_bytes_into(&mut buffer);
+	*out = buffer.into();
+}
+
+/// Shortcut function to encode structure into rlp, writing it to `out`.
+///
+/// As list headers are inserted before their items once those are encoded, the structure is
+/// encoded into a temporary buffer, allocated on every call, and then copied to `out`. Use
+/// `encode_into` for a `Vec<u8>`, or `Encodable::rlp_bytes_into` to reuse a `BytesMut`, to
+/// encode without it.
+pub fn encode_to_buf<E, B>(object: &E, out: &mut B)
+where
+	E: Encodable,
+	B: BufMut,
+{
+	let mut stream = RlpStream::new_with_buffer(BytesMut::new());
+	stream.append(object);
+	out.put_slice(&stream.out());
+}
+
+/// Shortcut function to encode a slice of items into an rlp list.
+///
+/// ```
+/// let animals = vec!["cat", "dog"];
+/// let out = rlp::encode_list(&animals);
+/// assert_eq!(out, vec![0xc8, 0x83, b'c', b'a', b't', 0x83, b'd', b'o', b'g']);
+/// ```
+pub fn encode_list<T>(object: &[T]) -> BytesMut
+where
+	T: Encodable,
 {
 	let mut stream = RlpStream::new();
-	stream.append_list(object);
+	stream.append_list::<T, T>(object);
 	stream.out()
 }
@@ -0,0 +1,67 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::{
+	error::DecoderError,
+	rlpin::Rlp,
+	stream::RlpStream,
+	traits::{Decodable, Encodable},
+};
+
+/// An optional value encoded as the value itself, or as null (the empty string `0x80`)
+/// when absent. This is the convention of e.g. the `to` field of Ethereum transactions.
+///
+/// Unlike `Option<T>`, which wraps a present value in a single-element list, this is only
+/// unambiguous for types which never encode as the empty string: `Nullable(Some(vec![]))`
+/// or `Nullable(Some(0u64))` are encoded as null and decode as `Nullable(None)`. Fixed
+/// size hashes such as addresses are fine.
+///
+/// ```
+/// use rlp::Nullable;
+/// let to: Nullable<Vec<u8>> = Nullable(None);
+/// assert_eq!(rlp::encode(&to).to_vec(), vec![0x80]);
+/// assert_eq!(rlp::encode(&Nullable(Some(vec![1u8, 2]))).to_vec(), vec![0x82, 1, 2]);
+/// assert_eq!(rlp::decode::<Nullable<Vec<u8>>>(&[0x80]), Ok(to));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Nullable<T>(pub Option<T>);
+
+impl<T> From<Option<T>> for Nullable<T> {
+	fn from(value: Option<T>) -> Self {
+		Nullable(value)
+	}
+}
+
+impl<T> From<Nullable<T>> for Option<T> {
+	fn from(value: Nullable<T>) -> Self {
+		value.0
+	}
+}
+
+impl<T: Encodable> Encodable for Nullable<T> {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		match self.0 {
+			None => {
+				s.append_empty_data();
+			}
+			Some(ref value) => {
+				s.append(value);
+			}
+		}
+	}
+}
+
+impl<T: Decodable> Decodable for Nullable<T> {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		if rlp.is_data() && rlp.is_empty() {
+			Ok(Nullable(None))
+		} else {
+			rlp.as_val().map(|value| Nullable(Some(value)))
+		}
+	}
+}
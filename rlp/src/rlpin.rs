@@ -8,14 +8,15 @@
 
 #[cfg(not(feature = "std"))]
 use alloc::{string::String, vec::Vec};
-use core::cell::Cell;
-use core::fmt;
+use core::cell::{Cell, RefCell};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::{cmp, fmt};
 
 use rustc_hex::ToHex;
 
 use crate::error::DecoderError;
 use crate::impls::decode_usize;
-use crate::traits::Decodable;
+use crate::traits::{Decodable, DecodableBorrowed};
 
 /// rlp offset
 #[derive(Copy, Clone, Debug)]
@@ -53,6 +54,7 @@ pub struct PayloadInfo {
 fn calculate_payload_info(header_bytes: &[u8], len_of_len: usize) -> Result<PayloadInfo, DecoderError> {
 	let header_len = 1 + len_of_len;
 	match header_bytes.get(1) {
+		Some(&0) if header_bytes[0] >= 0xf8 => return Err(DecoderError::RlpListLenWithZeroPrefix),
 		Some(&0) => return Err(DecoderError::RlpDataLenWithZeroPrefix),
 		None => return Err(DecoderError::RlpIsTooShort),
 		_ => (),
@@ -72,9 +74,9 @@ impl PayloadInfo {
 		PayloadInfo { header_len, value_len }
 	}
 
-	/// Total size of the RLP.
-	pub fn total(&self) -> usize {
-		self.header_len + self.value_len
+	/// Total size of the RLP, failing with `RlpInvalidLength` if it overflows a `usize`.
+	pub fn total(&self) -> Result<usize, DecoderError> {
+		self.header_len.checked_add(self.value_len).ok_or(DecoderError::RlpInvalidLength)
 	}
 
 	/// Create a new object from the given bytes RLP. The bytes
@@ -96,6 +98,10 @@ impl PayloadInfo {
 	}
 }
 
+/// How many levels of nested lists an `Rlp` may descend into unless created with
+/// `Rlp::new_with_max_depth`.
+pub const DEFAULT_MAX_DEPTH: usize = 256;
+
 /// Data-oriented view onto rlp-slice.
 ///
 /// This is an immutable structure. No operations change it.
@@ -105,21 +111,48 @@ impl PayloadInfo {
 #[derive(Debug, Clone)]
 pub struct Rlp<'a> {
 	bytes: &'a [u8],
+	max_size: usize,
+	max_depth: usize,
+	max_items: usize,
+	lenient: bool,
 	offset_cache: Cell<Option<OffsetCache>>,
+	/// Offsets of the items before the cached position, filled on first access to such an item.
+	item_offsets: RefCell<Vec<usize>>,
 	count_cache: Cell<Option<usize>>,
+	/// Where the address of the innermost item which failed to decode is recorded, for
+	/// `decode_located`. Holds `NO_FAILED_ITEM` until an item fails; atomic so that views
+	/// stay `Send`.
+	failed_item: Option<&'a AtomicUsize>,
 }
 
+/// Value of the `failed_item` of a view before any item failed to decode.
+pub(crate) const NO_FAILED_ITEM: usize = usize::MAX;
+
+// Views can be moved to other threads.
+const _: fn() = || {
+	fn assert_send<T: Send>() {}
+	assert_send::<Rlp<'static>>();
+};
+
 impl<'a> fmt::Display for Rlp<'a> {
 	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
 		match self.prototype() {
 			Ok(Prototype::Null) => write!(f, "null"),
-			Ok(Prototype::Data(_)) => write!(f, "\"0x{}\"", self.data().unwrap().to_hex::<String>()),
-			Ok(Prototype::List(len)) => {
+			Ok(Prototype::Data(_)) => match self.data() {
+				Ok(data) => write!(f, "\"0x{}\"", data.to_hex::<String>()),
+				Err(err) => write!(f, "{:?}", err),
+			},
+			Ok(Prototype::List(_)) => {
 				write!(f, "[")?;
-				for i in 0..len - 1 {
-					write!(f, "{}, ", self.at(i).unwrap())?;
+				for (i, item) in self.iter_limited(usize::MAX).enumerate() {
+					if i > 0 {
+						write!(f, ", ")?;
+					}
+					match item {
+						Ok(item) => write!(f, "{}", item)?,
+						Err(err) => write!(f, "{:?}", err)?,
+					}
 				}
-				write!(f, "{}", self.at(len - 1).unwrap())?;
 				write!(f, "]")
 			}
 			Err(err) => write!(f, "{:?}", err),
@@ -129,7 +162,102 @@ impl<'a> fmt::Display for Rlp<'a> {
 
 impl<'a> Rlp<'a> {
 	pub const fn new(bytes: &'a [u8]) -> Rlp<'a> {
-		Rlp { bytes, offset_cache: Cell::new(None), count_cache: Cell::new(None) }
+		Rlp::new_with_max_size(bytes, usize::MAX)
+	}
+
+	/// Creates a view which allows lists to be nested at most `max_depth` levels below it.
+	/// Descending further fails with `DecoderError::DepthLimitExceeded`.
+	///
+	/// Views created with the other constructors use `DEFAULT_MAX_DEPTH`.
+	pub const fn new_with_max_depth(bytes: &'a [u8], max_depth: usize) -> Rlp<'a> {
+		Rlp {
+			bytes,
+			max_size: usize::MAX,
+			max_depth,
+			max_items: usize::MAX,
+			lenient: false,
+			offset_cache: Cell::new(None),
+			item_offsets: RefCell::new(Vec::new()),
+			count_cache: Cell::new(None),
+			failed_item: None,
+		}
+	}
+
+	/// Creates a view which rejects any byte string, including nested ones, whose declared
+	/// length exceeds `max_size` bytes with `DecoderError::RlpIsTooBig`. Lists may be longer,
+	/// so that the items within the limit can still be read.
+	///
+	/// Use this for untrusted input, to bound the size of values decoded from it.
+	pub const fn new_with_max_size(bytes: &'a [u8], max_size: usize) -> Rlp<'a> {
+		Rlp {
+			bytes,
+			max_size,
+			max_depth: DEFAULT_MAX_DEPTH,
+			max_items: usize::MAX,
+			lenient: false,
+			offset_cache: Cell::new(None),
+			item_offsets: RefCell::new(Vec::new()),
+			count_cache: Cell::new(None),
+			failed_item: None,
+		}
+	}
+
+	/// Creates a view with the limits of `new_with_max_size` and `new_with_max_depth`, which
+	/// also rejects lists, including nested ones, of more than `max_items` items with
+	/// `DecoderError::ListLimitExceeded` when decoding them with `as_list` or `list_at`.
+	pub const fn new_with_limits(bytes: &'a [u8], max_size: usize, max_depth: usize, max_items: usize) -> Rlp<'a> {
+		Rlp {
+			bytes,
+			max_size,
+			max_depth,
+			max_items,
+			lenient: false,
+			offset_cache: Cell::new(None),
+			item_offsets: RefCell::new(Vec::new()),
+			count_cache: Cell::new(None),
+			failed_item: None,
+		}
+	}
+
+	/// Creates a view which, as earlier versions of this crate did, decodes values with a
+	/// non-minimal long-form header: one declaring fewer than 56 bytes, or whose length has
+	/// a leading zero. Items of lists are still checked for canonical headers when traversing.
+	///
+	/// The same value then has several accepted encodings, so only use this for existing
+	/// data written with such headers.
+	pub const fn new_lenient(bytes: &'a [u8]) -> Rlp<'a> {
+		Rlp {
+			bytes,
+			max_size: usize::MAX,
+			max_depth: DEFAULT_MAX_DEPTH,
+			max_items: usize::MAX,
+			lenient: true,
+			offset_cache: Cell::new(None),
+			item_offsets: RefCell::new(Vec::new()),
+			count_cache: Cell::new(None),
+			failed_item: None,
+		}
+	}
+
+	/// Creates a view which records in `failed_item` the address of the innermost item whose
+	/// decoding with `as_val` failed.
+	pub(crate) fn new_recording_failures(bytes: &'a [u8], failed_item: &'a AtomicUsize) -> Rlp<'a> {
+		Rlp { failed_item: Some(failed_item), ..Rlp::new(bytes) }
+	}
+
+	/// The maximum length of a byte string, see `new_with_max_size`.
+	pub fn max_size(&self) -> usize {
+		self.max_size
+	}
+
+	/// The number of list levels which may still be descended into, see `new_with_max_depth`.
+	pub fn max_depth(&self) -> usize {
+		self.max_depth
+	}
+
+	/// The maximum number of items of a list decoded with `as_list`, see `new_with_limits`.
+	pub fn max_items(&self) -> usize {
+		self.max_items
 	}
 
 	pub fn as_raw<'view>(&'view self) -> &'a [u8]
@@ -158,12 +286,13 @@ impl<'a> Rlp<'a> {
 	where
 		'a: 'view,
 	{
-		let pi = BasicDecoder::payload_info(self.bytes)?;
-		Ok(&self.bytes[pi.header_len..(pi.header_len + pi.value_len)])
+		let pi = self.checked_payload_info(self.bytes)?;
+		Ok(&self.bytes[pi.header_len..pi.total()?])
 	}
 
 	pub fn item_count(&self) -> Result<usize, DecoderError> {
 		if self.is_list() {
+			self.check_depth()?;
 			match self.count_cache.get() {
 				Some(c) => Ok(c),
 				None => {
@@ -208,30 +337,50 @@ impl<'a> Rlp<'a> {
 		if !self.is_list() {
 			return Err(DecoderError::RlpExpectedToBeList);
 		}
+		self.check_depth()?;
 
 		// move to cached position if its index is less or equal to
-		// current search index, otherwise move to beginning of list
+		// current search index, otherwise look up the offset of the item
+		// in the index of the items before it, or move to beginning of list
 		let cache = self.offset_cache.get();
-		let (bytes, indexes_to_skip, bytes_consumed) = match cache {
-			Some(ref cache) if cache.index <= index => {
-				(Rlp::consume(self.bytes, cache.offset)?, index - cache.index, cache.offset)
+		let (bytes, offset) = match cache {
+			Some(ref cache) if cache.index > index => {
+				let offset = self.indexed_offset(index)?;
+				(Rlp::consume(self.bytes, offset)?, offset)
 			}
 			_ => {
-				let (bytes, consumed) = self.consume_list_payload()?;
-				(bytes, index, consumed)
+				let (bytes, indexes_to_skip, bytes_consumed) = match cache {
+					Some(ref cache) => (Rlp::consume(self.bytes, cache.offset)?, index - cache.index, cache.offset),
+					None => {
+						let (bytes, consumed) = self.consume_list_payload()?;
+						(bytes, index, consumed)
+					}
+				};
+
+				// skip up to x items
+				let (bytes, consumed) = Rlp::consume_items(bytes, indexes_to_skip)?;
+
+				// update the cache
+				let offset = bytes_consumed + consumed;
+				self.offset_cache.set(Some(OffsetCache::new(index, offset)));
+				(bytes, offset)
 			}
 		};
 
-		// skip up to x items
-		let (bytes, consumed) = Rlp::consume_items(bytes, indexes_to_skip)?;
-
-		// update the cache
-		let offset = bytes_consumed + consumed;
-		self.offset_cache.set(Some(OffsetCache::new(index, offset)));
-
 		// construct new rlp
-		let found = BasicDecoder::payload_info(bytes)?;
-		Ok((Rlp::new(&bytes[0..found.header_len + found.value_len]), offset))
+		let found = self.checked_payload_info(bytes)?;
+		let item = Rlp {
+			bytes: &bytes[0..found.total()?],
+			max_size: self.max_size,
+			max_depth: self.max_depth - 1,
+			max_items: self.max_items,
+			lenient: self.lenient,
+			offset_cache: Cell::new(None),
+			item_offsets: RefCell::new(Vec::new()),
+			count_cache: Cell::new(None),
+			failed_item: self.failed_item,
+		};
+		Ok((item, offset))
 	}
 
 	pub fn is_null(&self) -> bool {
@@ -257,7 +406,7 @@ impl<'a> Rlp<'a> {
 
 		match self.bytes[0] {
 			0..=0x80 => true,
-			0x81..=0xb7 => self.bytes[1] != 0,
+			0x81..=0xb7 => self.bytes.get(1).is_some_and(|&byte| byte != 0),
 			b @ 0xb8..=0xbf => {
 				let payload_idx = 1 + b as usize - 0xb7;
 				payload_idx < self.bytes.len() && self.bytes[payload_idx] != 0
@@ -266,6 +415,9 @@ impl<'a> Rlp<'a> {
 		}
 	}
 
+	/// Iterates over the items of the list, stopping at the first item which cannot be
+	/// read, e.g. a malformed one or one beyond the depth limit. Use `iter_limited` to
+	/// tell these apart from the end of the list.
 	pub fn iter<'view>(&'view self) -> RlpIterator<'a, 'view>
 	where
 		'a: 'view,
@@ -277,14 +429,48 @@ impl<'a> Rlp<'a> {
 	where
 		T: Decodable,
 	{
-		T::decode(self)
+		let result = T::decode(self);
+		if result.is_err() {
+			self.record_failure();
+		}
+		result
 	}
 
+	/// Decodes every item of the list, the list analogue of `as_val`.
+	///
+	/// Fails with `RlpExpectedToBeList` if this is not a list, and with the error of the first
+	/// item that is malformed or cannot be decoded as `T`, rather than stopping there like `iter`.
+	/// Lists of more items than `max_items` fail with `ListLimitExceeded`.
 	pub fn as_list<T>(&self) -> Result<Vec<T>, DecoderError>
 	where
 		T: Decodable,
 	{
-		self.iter().map(|rlp| rlp.as_val()).collect()
+		self.as_list_with_max(self.max_items)
+	}
+
+	/// Decodes every item of the list as `as_list` does, failing with `ListLimitExceeded` as
+	/// soon as the list turns out to have more than `max_items` items, before decoding more.
+	pub fn as_list_with_max<T>(&self, max_items: usize) -> Result<Vec<T>, DecoderError>
+	where
+		T: Decodable,
+	{
+		self.iter_limited(max_items).map(|item| item?.as_val()).collect()
+	}
+
+	/// Iterates over the items of the list like `iter`, but yields an error instead of
+	/// stopping on a malformed item, and fails with `ListLimitExceeded` on the item after the
+	/// first `max_items` ones. Nothing is yielded after an error.
+	pub fn iter_limited<'view>(&'view self, max_items: usize) -> RlpLimitedIterator<'a, 'view>
+	where
+		'a: 'view,
+	{
+		let payload = if self.is_list() { self.consume_list_payload() } else { Err(DecoderError::RlpExpectedToBeList) };
+		let (offset, end) = match payload {
+			Ok((payload, header_len)) => (header_len, Ok(header_len + payload.len())),
+			Err(err) => (0, Err(err)),
+		};
+		let max_items = cmp::min(max_items, self.max_items);
+		RlpLimitedIterator { rlp: self, index: 0, offset, end: Some(end), max_items }
 	}
 
 	pub fn val_at<T>(&self, index: usize) -> Result<T, DecoderError>
@@ -294,6 +480,27 @@ impl<'a> Rlp<'a> {
 		self.at(index)?.as_val()
 	}
 
+	/// Decodes a value borrowing from the input, such as a `&'a [u8]` or `&'a str`, without
+	/// copying it. The value can outlive this `Rlp`.
+	pub fn as_val_borrowed<T>(&self) -> Result<T, DecoderError>
+	where
+		T: DecodableBorrowed<'a>,
+	{
+		let result = T::decode_borrowed(self);
+		if result.is_err() {
+			self.record_failure();
+		}
+		result
+	}
+
+	/// Decodes the item at `index` as a value borrowing from the input, see `as_val_borrowed`.
+	pub fn val_at_borrowed<T>(&self, index: usize) -> Result<T, DecoderError>
+	where
+		T: DecodableBorrowed<'a>,
+	{
+		self.at(index)?.as_val_borrowed()
+	}
+
 	pub fn list_at<T>(&self, index: usize) -> Result<Vec<T>, DecoderError>
 	where
 		T: Decodable,
@@ -301,17 +508,66 @@ impl<'a> Rlp<'a> {
 		self.at(index)?.as_list()
 	}
 
-	pub fn decoder(&self) -> BasicDecoder {
-		BasicDecoder::new(self.bytes)
+	pub fn decoder(&self) -> BasicDecoder<'a> {
+		BasicDecoder { rlp: self.bytes, max_size: self.max_size, lenient: self.lenient }
+	}
+
+	/// Records this item as the one which failed to decode, unless an item inside it already
+	/// has been, when created with `new_recording_failures`.
+	fn record_failure(&self) {
+		if let Some(failed_item) = self.failed_item {
+			let start = self.bytes.as_ptr() as usize;
+			let end = start + self.bytes.len();
+			let address = failed_item.load(Ordering::Relaxed);
+			if address == NO_FAILED_ITEM || address < start || end <= address {
+				failed_item.store(start, Ordering::Relaxed);
+			}
+		}
+	}
+
+	/// Fails if the items of this list would be nested deeper than allowed.
+	fn check_depth(&self) -> Result<(), DecoderError> {
+		if self.max_depth == 0 && !self.is_empty() {
+			return Err(DecoderError::DepthLimitExceeded);
+		}
+		Ok(())
+	}
+
+	/// Payload info of the first item in `bytes`, failing if it is a byte string longer than
+	/// `max_size`. Lists are not limited themselves, only the byte strings within them.
+	fn checked_payload_info(&self, bytes: &[u8]) -> Result<PayloadInfo, DecoderError> {
+		// Checked before the length of the input, so oversized items are reported as such.
+		let is_data = bytes.first().is_some_and(|&b| b < 0xc0);
+		if is_data && PayloadInfo::from(bytes)?.value_len > self.max_size {
+			return Err(DecoderError::RlpIsTooBig);
+		}
+		BasicDecoder::payload_info(bytes)
+	}
+
+	/// Offset of the item at `index`, which has been walked past before. The offsets of the
+	/// items are recorded up to `index`, so that accessing items in reverse order is linear.
+	fn indexed_offset(&self, index: usize) -> Result<usize, DecoderError> {
+		let mut offsets = self.item_offsets.borrow_mut();
+		if offsets.is_empty() {
+			let (_, header_len) = self.consume_list_payload()?;
+			offsets.push(header_len);
+		}
+		while offsets.len() <= index {
+			let last = offsets[offsets.len() - 1];
+			let (_, consumed) = Rlp::consume_items(Rlp::consume(self.bytes, last)?, 1)?;
+			offsets.push(last + consumed);
+		}
+		Ok(offsets[index])
 	}
 
 	/// consumes first found prefix
 	fn consume_list_payload(&self) -> Result<(&'a [u8], usize), DecoderError> {
-		let item = BasicDecoder::payload_info(self.bytes)?;
-		if self.bytes.len() < (item.header_len + item.value_len) {
+		let item = self.checked_payload_info(self.bytes)?;
+		let total = item.total()?;
+		if self.bytes.len() < total {
 			return Err(DecoderError::RlpIsTooShort);
 		}
-		Ok((&self.bytes[item.header_len..item.header_len + item.value_len], item.header_len))
+		Ok((&self.bytes[item.header_len..total], item.header_len))
 	}
 
 	/// consumes fixed number of items
@@ -319,8 +575,7 @@ impl<'a> Rlp<'a> {
 		let mut result = bytes;
 		let mut consumed = 0;
 		for _ in 0..items {
-			let i = BasicDecoder::payload_info(result)?;
-			let to_consume = i.header_len + i.value_len;
+			let to_consume = BasicDecoder::payload_info(result)?.total()?;
 			result = Rlp::consume(result, to_consume)?;
 			consumed += to_consume;
 		}
@@ -337,6 +592,41 @@ impl<'a> Rlp<'a> {
 	}
 }
 
+/// Iterator over the items of a list which reports errors, see `Rlp::iter_limited`.
+pub struct RlpLimitedIterator<'a, 'view>
+where
+	'a: 'view,
+{
+	rlp: &'view Rlp<'a>,
+	index: usize,
+	offset: usize,
+	/// The end of the list payload, or the error to yield first, and `None` after an error.
+	end: Option<Result<usize, DecoderError>>,
+	max_items: usize,
+}
+
+impl<'a, 'view> Iterator for RlpLimitedIterator<'a, 'view> {
+	type Item = Result<Rlp<'a>, DecoderError>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let result = match self.end.take()? {
+			Err(err) => Err(err),
+			Ok(end) if self.offset >= end => return None,
+			Ok(_) if self.index == self.max_items => Err(DecoderError::ListLimitExceeded),
+			Ok(end) => match self.rlp.at_with_offset(self.index) {
+				Ok((item, offset)) => {
+					self.index += 1;
+					self.offset = offset + item.as_raw().len();
+					self.end = Some(Ok(end));
+					Ok(item)
+				}
+				Err(err) => Err(err),
+			},
+		};
+		Some(result)
+	}
+}
+
 /// Iterator over rlp-slice list elements.
 pub struct RlpIterator<'a, 'view>
 where
@@ -377,34 +667,50 @@ impl<'a, 'view> ExactSizeIterator for RlpIterator<'a, 'view> {
 
 pub struct BasicDecoder<'a> {
 	rlp: &'a [u8],
+	max_size: usize,
+	lenient: bool,
 }
 
 impl<'a> BasicDecoder<'a> {
 	pub const fn new(rlp: &'a [u8]) -> BasicDecoder<'a> {
-		BasicDecoder { rlp }
+		BasicDecoder { rlp, max_size: usize::MAX, lenient: false }
 	}
 
 	/// Return first item info.
+	///
+	/// Fails if the declared length of the item exceeds the available bytes
+	/// or if a single byte below `0x80` is not encoded as itself.
 	fn payload_info(bytes: &[u8]) -> Result<PayloadInfo, DecoderError> {
 		let item = PayloadInfo::from(bytes)?;
-		match item.header_len.checked_add(item.value_len) {
-			Some(x) if x <= bytes.len() => Ok(item),
-			_ => Err(DecoderError::RlpIsTooShort),
+		if item.total()? > bytes.len() {
+			return Err(DecoderError::RlpInconsistentLengthAndData);
 		}
+		if bytes[0] == 0x81 && bytes[1] < 0x80 {
+			return Err(DecoderError::RlpInvalidIndirection);
+		}
+		Ok(item)
 	}
 
 	pub fn decode_value<T, F>(&self, f: F) -> Result<T, DecoderError>
 	where
 		F: Fn(&[u8]) -> Result<T, DecoderError>,
 	{
+		f(self.decode_value_borrowed()?)
+	}
+
+	/// The payload of the value, borrowed from the input.
+	pub fn decode_value_borrowed(&self) -> Result<&'a [u8], DecoderError> {
 		let bytes = self.rlp;
 
 		let l = *bytes.first().ok_or_else(|| DecoderError::RlpIsTooShort)?;
 
 		if l <= 0x7f {
-			Ok(f(&[l])?)
+			Ok(&bytes[..1])
 		} else if l <= 0xb7 {
 			let last_index_of = 1 + l as usize - 0x80;
+			if last_index_of - 1 > self.max_size {
+				return Err(DecoderError::RlpIsTooBig);
+			}
 			if bytes.len() < last_index_of {
 				return Err(DecoderError::RlpInconsistentLengthAndData);
 			}
@@ -412,20 +718,30 @@ impl<'a> BasicDecoder<'a> {
 			if l == 0x81 && d[0] < 0x80 {
 				return Err(DecoderError::RlpInvalidIndirection);
 			}
-			Ok(f(d)?)
+			Ok(d)
 		} else if l <= 0xbf {
 			let len_of_len = l as usize - 0xb7;
 			let begin_of_value = 1 as usize + len_of_len;
 			if bytes.len() < begin_of_value {
 				return Err(DecoderError::RlpInconsistentLengthAndData);
 			}
+			if !self.lenient && bytes[1] == 0 {
+				return Err(DecoderError::RlpDataLenWithZeroPrefix);
+			}
 			let len = decode_usize(&bytes[1..begin_of_value])?;
+			if !self.lenient && len <= 55 {
+				return Err(DecoderError::RlpInvalidIndirection);
+			}
+			// Checked before the length of the input, so oversized items are reported as such.
+			if len > self.max_size {
+				return Err(DecoderError::RlpIsTooBig);
+			}
 
 			let last_index_of_value = begin_of_value.checked_add(len).ok_or(DecoderError::RlpInvalidLength)?;
 			if bytes.len() < last_index_of_value {
 				return Err(DecoderError::RlpInconsistentLengthAndData);
 			}
-			Ok(f(&bytes[begin_of_value..last_index_of_value])?)
+			Ok(&bytes[begin_of_value..last_index_of_value])
 		} else {
 			Err(DecoderError::RlpExpectedToBeData)
 		}
@@ -11,7 +11,7 @@ use alloc::vec::Vec;
 use bytes::{BufMut, BytesMut};
 use core::borrow::Borrow;
 
-use crate::traits::Encodable;
+use crate::{error::DecoderError, rlpin::PayloadInfo, traits::Encodable};
 
 #[derive(Debug, Copy, Clone)]
 struct ListInfo {
@@ -51,6 +51,12 @@ impl RlpStream {
 		Self::new_list_with_buffer(BytesMut::with_capacity(1024), len)
 	}
 
+	/// Initializes the `Stream` as a list of `items` items, with room for `bytes` bytes of
+	/// encoding.
+	pub fn with_capacity(items: usize, bytes: usize) -> Self {
+		Self::new_list_with_buffer(BytesMut::with_capacity(bytes), items)
+	}
+
 	/// Initializes instance of empty `Stream`.
 	pub fn new_with_buffer(buffer: BytesMut) -> Self {
 		RlpStream { unfinished_lists: Vec::with_capacity(16), start_pos: buffer.len(), buffer, finished_list: false }
@@ -67,14 +73,16 @@ impl RlpStream {
 		self.buffer.len() - self.start_pos
 	}
 
-	/// Apends null to the end of stream, chainable.
+	/// Apends null, i.e. the empty byte string `0x80`, to the end of stream, chainable.
+	///
+	/// This is not the empty list, which is appended with `begin_list(0)` and encoded as `0xc0`.
 	///
 	/// ```
 	/// use rlp::RlpStream;
-	/// let mut stream = RlpStream::new_list(2);
-	/// stream.append_empty_data().append_empty_data();
+	/// let mut stream = RlpStream::new_list(3);
+	/// stream.append_empty_data().append_empty_data().begin_list(0);
 	/// let out = stream.out();
-	/// assert_eq!(out, vec![0xc2, 0x80, 0x80]);
+	/// assert_eq!(out, vec![0xc3, 0x80, 0x80, 0xc0]);
 	/// ```
 	pub fn append_empty_data(&mut self) -> &mut Self {
 		// self push raw item
@@ -99,6 +107,44 @@ impl RlpStream {
 		self
 	}
 
+	/// Appends the concatenation of several slices of raw (pre-serialised) RLP data, holding
+	/// `item_count` items in total. Use with caution. Chainable.
+	///
+	/// ```
+	/// use rlp::RlpStream;
+	/// let mut stream = RlpStream::new_list(2);
+	/// stream.append_raw_slices(&[&[0x83, b'c', b'a', b't'], &[0x83, b'd', b'o', b'g']], 2);
+	/// assert_eq!(stream.out(), vec![0xc8, 0x83, b'c', b'a', b't', 0x83, b'd', b'o', b'g']);
+	/// ```
+	pub fn append_raw_slices(&mut self, items: &[&[u8]], item_count: usize) -> &mut Self {
+		self.buffer.reserve(items.iter().map(|item| item.len()).sum());
+		for item in items {
+			self.buffer.extend_from_slice(item);
+		}
+		self.note_appended(item_count);
+		self
+	}
+
+	/// Appends a single raw (pre-serialised) RLP item after checking that it is well-formed,
+	/// i.e. that `bytes` hold exactly one item, and that the lengths of the item and of any
+	/// nested items are canonical and consistent. Chainable.
+	///
+	/// ```
+	/// use rlp::{DecoderError, RlpStream};
+	/// let mut stream = RlpStream::new_list(2);
+	/// stream.append_raw_validated(&[0x83, b'c', b'a', b't']).unwrap();
+	/// let truncated = stream.append_raw_validated(&[0x83, b'd', b'o']).err();
+	/// assert_eq!(truncated, Some(DecoderError::RlpInconsistentLengthAndData));
+	/// stream.append_raw_validated(&[0xc0]).unwrap();
+	/// assert_eq!(stream.out(), vec![0xc5, 0x83, b'c', b'a', b't', 0xc0]);
+	/// ```
+	pub fn append_raw_validated(&mut self, bytes: &[u8]) -> Result<&mut Self, DecoderError> {
+		if validate_item(bytes)? != bytes.len() {
+			return Err(DecoderError::RlpInconsistentLengthAndData);
+		}
+		Ok(self.append_raw(bytes, 1))
+	}
+
 	/// Appends value to the end of stream, chainable.
 	///
 	/// ```
@@ -112,6 +158,9 @@ impl RlpStream {
 	where
 		E: Encodable,
 	{
+		if let Some(len) = value.rlp_len_hint() {
+			self.buffer.reserve(len);
+		}
 		self.finished_list = false;
 		value.rlp_append(self);
 		if !self.finished_list {
@@ -154,6 +203,34 @@ impl RlpStream {
 		self
 	}
 
+	/// Appends the items of an iterator as a list, without knowing their number in advance.
+	/// Chainable.
+	///
+	/// The output is the same as that of `append_list` for the collected items. The size hint
+	/// of the iterator is only used to reserve buffer space.
+	///
+	/// ```
+	/// use rlp::RlpStream;
+	/// let mut stream = RlpStream::new();
+	/// stream.append_list_from_iter((1u8..=6).filter(|n| n % 2 == 0));
+	/// assert_eq!(stream.out(), vec![0xc3, 0x02, 0x04, 0x06]);
+	/// ```
+	pub fn append_list_from_iter<E, K, I>(&mut self, values: I) -> &mut Self
+	where
+		E: Encodable,
+		K: Borrow<E>,
+		I: IntoIterator<Item = K>,
+	{
+		let values = values.into_iter();
+		self.buffer.reserve(values.size_hint().0);
+		self.begin_unbounded_list();
+		for value in values {
+			self.append(value.borrow());
+		}
+		self.finalize_unbounded_list();
+		self
+	}
+
 	/// Appends value to the end of stream, but do not count it as an appended item.
 	/// It's useful for wrapper types
 	pub fn append_internal<E>(&mut self, value: &E) -> &mut Self
@@ -338,6 +415,36 @@ impl RlpStream {
 	}
 }
 
+/// Checks that `bytes` start with a well-formed RLP item and returns its total length.
+fn validate_item(bytes: &[u8]) -> Result<usize, DecoderError> {
+	// Ends of the payloads of the lists being walked, innermost last. Keeping them on the heap
+	// rather than recursing lets arbitrarily nested input be validated without overflowing the stack.
+	let mut list_ends = Vec::new();
+	let mut pos = 0;
+	loop {
+		let item = &bytes[pos..list_ends.last().copied().unwrap_or(bytes.len())];
+		let info = PayloadInfo::from(item)?;
+		let total = info.total()?;
+		if item.len() < total {
+			return Err(DecoderError::RlpInconsistentLengthAndData);
+		}
+		if item[0] >= 0xc0 {
+			list_ends.push(pos + total);
+			pos += info.header_len;
+		} else if info.header_len == 1 && info.value_len == 1 && item[1] < 0x80 {
+			return Err(DecoderError::RlpInvalidIndirection);
+		} else {
+			pos += total;
+		}
+		while list_ends.last() == Some(&pos) {
+			list_ends.pop();
+		}
+		if list_ends.is_empty() {
+			return Ok(pos);
+		}
+	}
+}
+
 pub struct BasicEncoder<'a> {
 	buffer: &'a mut BytesMut,
 	start_pos: usize,
@@ -0,0 +1,233 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Decoding of RLP items read one at a time from an `io::Read`, e.g. a chain export file.
+
+use std::error::Error as StdError;
+use std::fmt;
+use std::io::{self, Read};
+use std::marker::PhantomData;
+
+use crate::error::DecoderError;
+use crate::rlpin::{PayloadInfo, Rlp};
+use crate::traits::Decodable;
+
+/// Error reading an item from a stream.
+#[derive(Debug)]
+pub enum ReadError {
+	/// The underlying reader failed.
+	Io(io::Error),
+	/// The data read is not valid RLP, or not a valid encoding of the requested type.
+	Decoder(DecoderError),
+}
+
+impl fmt::Display for ReadError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ReadError::Io(err) => write!(f, "I/O error: {}", err),
+			ReadError::Decoder(err) => write!(f, "Decoder error: {}", err),
+		}
+	}
+}
+
+impl StdError for ReadError {
+	fn source(&self) -> Option<&(dyn StdError + 'static)> {
+		match self {
+			ReadError::Io(err) => Some(err),
+			ReadError::Decoder(err) => Some(err),
+		}
+	}
+}
+
+impl From<io::Error> for ReadError {
+	fn from(err: io::Error) -> Self {
+		ReadError::Io(err)
+	}
+}
+
+impl From<DecoderError> for ReadError {
+	fn from(err: DecoderError) -> Self {
+		ReadError::Decoder(err)
+	}
+}
+
+/// Reads RLP items from `R` one at a time, only keeping the current item in memory.
+///
+/// The items are read one after the other, as they are laid out in a chain export. Lists can
+/// be entered with `enter_list` to read their items one at a time as well.
+///
+/// Declared lengths are checked against the data read: input ending within an item fails with
+/// `RlpIsTooShort` in its header and `RlpInconsistentLengthAndData` in its payload, as when
+/// decoding a slice.
+///
+/// ```
+/// use rlp::stream_decode::ItemReader;
+/// let data = [0x83, b'c', b'a', b't', 0xc2, 0x01, 0x02];
+/// let mut items = ItemReader::new(&data[..]);
+/// assert_eq!(items.read_value::<String>().unwrap(), Some("cat".to_owned()));
+/// assert_eq!(items.enter_list().unwrap(), 2);
+/// assert_eq!(items.read_value::<u8>().unwrap(), Some(1));
+/// assert_eq!(items.read_value::<u8>().unwrap(), Some(2));
+/// assert_eq!(items.read_value::<u8>().unwrap(), None);
+/// assert_eq!(items.read_item().unwrap(), None);
+/// ```
+pub struct ItemReader<R> {
+	reader: R,
+	buffer: Vec<u8>,
+	max_size: usize,
+	/// Payload bytes left to read in each entered list, innermost last.
+	lists: Vec<usize>,
+}
+
+impl<R: Read> ItemReader<R> {
+	/// Creates a reader of the items in `reader`.
+	pub fn new(reader: R) -> Self {
+		ItemReader::with_max_size(reader, usize::MAX)
+	}
+
+	/// Creates a reader which rejects any item whose declared length exceeds `max_size` bytes
+	/// with `DecoderError::RlpIsTooBig`, bounding the memory used. Entered lists are not
+	/// held in memory and so are not limited.
+	pub fn with_max_size(reader: R, max_size: usize) -> Self {
+		ItemReader { reader, buffer: Vec::new(), max_size, lists: Vec::new() }
+	}
+
+	/// Reads the next item and returns its raw bytes.
+	///
+	/// Returns `None` at the end of the input, or at the end of the innermost entered list,
+	/// which is then left.
+	pub fn read_item(&mut self) -> Result<Option<&[u8]>, ReadError> {
+		let info = match self.read_header()? {
+			Some(info) => info,
+			None => return Ok(None),
+		};
+		if info.value_len > self.max_size {
+			return Err(DecoderError::RlpIsTooBig.into());
+		}
+		self.consume_from_lists(&info)?;
+
+		let payload_len = info.total()? - self.buffer.len();
+		let read = (&mut self.reader).take(payload_len as u64).read_to_end(&mut self.buffer)?;
+		if read < payload_len {
+			return Err(DecoderError::RlpInconsistentLengthAndData.into());
+		}
+		if self.buffer[0] == 0x81 && self.buffer[1] < 0x80 {
+			return Err(DecoderError::RlpInvalidIndirection.into());
+		}
+		Ok(Some(&self.buffer))
+	}
+
+	/// Reads and decodes the next item, see `read_item`.
+	pub fn read_value<T: Decodable>(&mut self) -> Result<Option<T>, ReadError> {
+		match self.read_item()? {
+			Some(bytes) => Ok(Some(Rlp::new(bytes).as_val()?)),
+			None => Ok(None),
+		}
+	}
+
+	/// Reads the header of the next item, which must be a list, and returns the length of
+	/// its payload. The following calls of `read_item` return the items of this list.
+	pub fn enter_list(&mut self) -> Result<usize, ReadError> {
+		if self.lists.last() == Some(&0) {
+			return Err(DecoderError::RlpIsTooShort.into());
+		}
+		let info = self.read_header()?.ok_or(DecoderError::RlpIsTooShort)?;
+		if self.buffer[0] < 0xc0 {
+			return Err(DecoderError::RlpExpectedToBeList.into());
+		}
+		self.consume_from_lists(&info)?;
+		// The payload is consumed from the enclosing lists by reading the items of this one.
+		for remaining in &mut self.lists {
+			*remaining += info.value_len;
+		}
+		self.lists.push(info.value_len);
+		Ok(info.value_len)
+	}
+
+	/// Reads the header of the next item into the buffer, or returns `None` at the end of the
+	/// input or of the innermost entered list, leaving that list.
+	fn read_header(&mut self) -> Result<Option<PayloadInfo>, ReadError> {
+		if self.lists.last() == Some(&0) {
+			self.lists.pop();
+			return Ok(None);
+		}
+		let mut first = [0u8];
+		loop {
+			match self.reader.read(&mut first) {
+				Ok(0) if self.lists.is_empty() => return Ok(None),
+				Ok(0) => return Err(DecoderError::RlpInconsistentLengthAndData.into()),
+				Ok(_) => break,
+				Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+				Err(err) => return Err(err.into()),
+			}
+		}
+		let len_of_len = match first[0] {
+			l @ 0xb8..=0xbf => l as usize - 0xb7,
+			l @ 0xf8..=0xff => l as usize - 0xf7,
+			_ => 0,
+		};
+		self.buffer.clear();
+		self.buffer.push(first[0]);
+		let read = (&mut self.reader).take(len_of_len as u64).read_to_end(&mut self.buffer)?;
+		if read < len_of_len {
+			return Err(DecoderError::RlpIsTooShort.into());
+		}
+		Ok(Some(PayloadInfo::from(&self.buffer)?))
+	}
+
+	/// Accounts for the item described by `info` in the entered lists, failing if it exceeds
+	/// the payload left in the innermost one.
+	fn consume_from_lists(&mut self, info: &PayloadInfo) -> Result<(), DecoderError> {
+		let len = info.total()?;
+		match self.lists.last() {
+			Some(&remaining) if remaining < len => Err(DecoderError::RlpInconsistentLengthAndData),
+			_ => {
+				for remaining in &mut self.lists {
+					*remaining -= len;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+/// Iterator decoding the items read by an `ItemReader` into values of type `T`.
+///
+/// Iteration ends after the first error.
+///
+/// ```
+/// use rlp::stream_decode::{ItemReader, RlpFileIter};
+/// let data = [0x01, 0x82, 0x04, 0x00, 0x80];
+/// let values: Result<Vec<u16>, _> = RlpFileIter::new(ItemReader::new(&data[..])).collect();
+/// assert_eq!(values.unwrap(), vec![1, 0x400, 0]);
+/// ```
+pub struct RlpFileIter<R, T> {
+	items: ItemReader<R>,
+	failed: bool,
+	_marker: PhantomData<fn() -> T>,
+}
+
+impl<R: Read, T: Decodable> RlpFileIter<R, T> {
+	/// Creates an iterator over the values read by `items`.
+	pub fn new(items: ItemReader<R>) -> Self {
+		RlpFileIter { items, failed: false, _marker: PhantomData }
+	}
+}
+
+impl<R: Read, T: Decodable> Iterator for RlpFileIter<R, T> {
+	type Item = Result<T, ReadError>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.failed {
+			return None;
+		}
+		let result = self.items.read_value().transpose();
+		self.failed = matches!(result, Some(Err(_)));
+		result
+	}
+}
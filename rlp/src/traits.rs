@@ -17,15 +17,64 @@ pub trait Decodable: Sized {
 	fn decode(rlp: &Rlp) -> Result<Self, DecoderError>;
 }
 
+/// RLP decodable trait for values borrowing from the input, like `&'a [u8]`, which can be
+/// decoded without copying.
+pub trait DecodableBorrowed<'a>: Sized {
+	/// Decode a value borrowing from the bytes of `rlp`
+	fn decode_borrowed(rlp: &Rlp<'a>) -> Result<Self, DecoderError>;
+}
+
 /// Structure encodable to RLP
 pub trait Encodable {
 	/// Append a value to the stream
 	fn rlp_append(&self, s: &mut RlpStream);
 
+	/// An upper bound of the length of the encoding of this instance, if it is cheap to
+	/// compute. `RlpStream::append` and `encode` reserve this much room for the encoding.
+	///
+	/// `encoded_len_bound` helps implementing this.
+	fn rlp_len_hint(&self) -> Option<usize> {
+		None
+	}
+
 	/// Get rlp-encoded bytes for this instance
 	fn rlp_bytes(&self) -> BytesMut {
 		let mut s = RlpStream::new();
 		self.rlp_append(&mut s);
 		s.out()
 	}
+
+	/// Append rlp-encoded bytes for this instance to `out`, reusing its capacity.
+	fn rlp_bytes_into(&self, out: &mut BytesMut) {
+		let mut s = RlpStream::new_with_buffer(core::mem::take(out));
+		self.rlp_append(&mut s);
+		*out = s.out();
+	}
+}
+
+/// The fields of a structure encoded as a list, which can be spliced into the list of an
+/// enclosing structure.
+///
+/// Implemented by `#[derive(RlpEncodable)]`, and used for fields marked `#[rlp(flatten)]`.
+pub trait EncodableFields {
+	/// Number of list items appended by `rlp_append_fields`.
+	fn rlp_fields_len(&self) -> usize;
+
+	/// Append the fields to the stream, without a list header.
+	fn rlp_append_fields(&self, s: &mut RlpStream);
+}
+
+/// The fields of a structure decoded from a list, which can be read from the list of an
+/// enclosing structure.
+///
+/// Implemented by `#[derive(RlpDecodable)]`, and used for fields marked `#[rlp(flatten)]`.
+pub trait DecodableFields: Sized {
+	/// Whether some of the fields may be missing from the end of the list, as is the case for
+	/// `#[rlp(default)]` and `#[rlp(trailing)]` fields. Such fields can only be flattened into
+	/// the end of the enclosing list.
+	const HAS_OPTIONAL_FIELDS: bool;
+
+	/// Decode the fields from the items of the list `rlp` starting at `index`, returning the
+	/// number of items decoded.
+	fn rlp_decode_fields(rlp: &Rlp, index: usize) -> Result<(Self, usize), DecoderError>;
 }
@@ -7,11 +7,15 @@
 // except according to those terms.
 
 use core::{cmp, fmt};
+use std::borrow::Cow;
 
 use bytes::{Bytes, BytesMut};
 use hex_literal::hex;
-use primitive_types::{H160, U256};
-use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+use primitive_types::{H160, H256, U256};
+use rlp::{
+	stream_decode::{ItemReader, ReadError, RlpFileIter},
+	Decodable, DecoderError, Encodable, FixedBytes, LocatedError, Nullable, PayloadInfo, Rlp, RlpStream,
+};
 
 #[test]
 fn test_rlp_display() {
@@ -28,6 +32,44 @@ fn length_overflow() {
 	assert_eq!(Err(DecoderError::RlpInvalidLength), res);
 }
 
+#[test]
+fn malformed_lengths_do_not_panic() {
+	// Headers declaring `usize::MAX` bytes, whose total length with the header overflows.
+	let huge_list = [0xff; 9];
+	let huge_data = [0xbf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+	let info = PayloadInfo::from(&huge_list).unwrap();
+	assert_eq!((info.header_len, info.value_len), (9, usize::max_value()));
+	assert_eq!(info.total(), Err(DecoderError::RlpInvalidLength));
+	assert_eq!(PayloadInfo::from(&[0x83, b'c', b'a', b't']).unwrap().total(), Ok(4));
+
+	let rlp = Rlp::new(&huge_list);
+	assert_eq!(rlp.payload_info().err(), Some(DecoderError::RlpInvalidLength));
+	assert_eq!(rlp.at(0).err(), Some(DecoderError::RlpInvalidLength));
+	assert_eq!(rlp.as_list::<u8>(), Err(DecoderError::RlpInvalidLength));
+	assert_eq!(rlp.item_count(), Ok(0));
+	assert_eq!(rlp.to_string(), "[RlpInvalidLength]");
+	assert_eq!(rlp::decode_list::<u8>(&huge_list), Err(DecoderError::RlpInvalidLength));
+	assert_eq!(RlpStream::new().append_raw_validated(&huge_list).err(), Some(DecoderError::RlpInvalidLength));
+	assert!(matches!(
+		ItemReader::new(&huge_list[..]).read_item(),
+		Err(ReadError::Decoder(DecoderError::RlpInvalidLength))
+	));
+
+	let rlp = Rlp::new(&huge_data);
+	assert_eq!(rlp.data(), Err(DecoderError::RlpInvalidLength));
+	assert_eq!(rlp.as_val::<Vec<u8>>(), Err(DecoderError::RlpInvalidLength));
+	assert_eq!(rlp.to_string(), "RlpInvalidLength");
+	assert!(rlp.is_int());
+
+	// Truncated headers and payloads.
+	assert!(!Rlp::new(&[0x81]).is_int());
+	assert_eq!(Rlp::new(&[0x81]).as_val::<u8>(), Err(DecoderError::RlpInconsistentLengthAndData));
+	assert_eq!(Rlp::new(&[0xb8]).data(), Err(DecoderError::RlpIsTooShort));
+	assert_eq!(Rlp::new(&[0xf9, 0x01]).at(0).err(), Some(DecoderError::RlpIsTooShort));
+	assert_eq!(Rlp::new(&[0x83, b'c']).to_string(), "RlpInconsistentLengthAndData");
+	assert_eq!(Rlp::new(&[0xc0]).to_string(), "[]");
+}
+
 #[test]
 fn rlp_at() {
 	let data = vec![0xc8, 0x83, b'c', b'a', b't', 0x83, b'd', b'o', b'g'];
@@ -91,10 +133,10 @@ fn rlp_at_err() {
 		assert!(rlp.is_list());
 
 		let cat_err = rlp.at(0).unwrap_err();
-		assert_eq!(cat_err, DecoderError::RlpIsTooShort);
+		assert_eq!(cat_err, DecoderError::RlpInconsistentLengthAndData);
 
 		let dog_err = rlp.at(1).unwrap_err();
-		assert_eq!(dog_err, DecoderError::RlpIsTooShort);
+		assert_eq!(dog_err, DecoderError::RlpInconsistentLengthAndData);
 	}
 }
 
@@ -366,7 +408,7 @@ where
 	T: Decodable + fmt::Debug + cmp::Eq,
 {
 	for t in &tests {
-		let res: Vec<T> = rlp::decode_list(&t.1);
+		let res: Vec<T> = rlp::decode_list(&t.1).unwrap();
 		assert_eq!(res, t.0);
 	}
 }
@@ -590,7 +632,7 @@ fn test_rlp_list_length_overflow() {
 	let data = hex!("ffffffffffffffffff000000");
 	let rlp = Rlp::new(&data);
 	let as_val: Result<String, DecoderError> = rlp.val_at(0);
-	assert_eq!(Err(DecoderError::RlpIsTooShort), as_val);
+	assert_eq!(Err(DecoderError::RlpInvalidLength), as_val);
 }
 
 #[test]
@@ -661,10 +703,19 @@ fn test_canonical_list_encoding() {
 #[test]
 fn test_inner_length_capping_for_short_lists() {
 	assert_eq!(Rlp::new(&[0xc0, 0x82, b'a', b'b']).val_at::<String>(0), Err(DecoderError::RlpIsTooShort));
-	assert_eq!(Rlp::new(&[0xc0 + 1, 0x82, b'a', b'b']).val_at::<String>(0), Err(DecoderError::RlpIsTooShort));
-	assert_eq!(Rlp::new(&[0xc0 + 2, 0x82, b'a', b'b']).val_at::<String>(0), Err(DecoderError::RlpIsTooShort));
+	assert_eq!(
+		Rlp::new(&[0xc0 + 1, 0x82, b'a', b'b']).val_at::<String>(0),
+		Err(DecoderError::RlpInconsistentLengthAndData)
+	);
+	assert_eq!(
+		Rlp::new(&[0xc0 + 2, 0x82, b'a', b'b']).val_at::<String>(0),
+		Err(DecoderError::RlpInconsistentLengthAndData)
+	);
 	assert_eq!(Rlp::new(&[0xc0 + 3, 0x82, b'a', b'b']).val_at::<String>(0), Ok("ab".to_owned()));
-	assert_eq!(Rlp::new(&[0xc0 + 4, 0x82, b'a', b'b']).val_at::<String>(0), Err(DecoderError::RlpIsTooShort));
+	assert_eq!(
+		Rlp::new(&[0xc0 + 4, 0x82, b'a', b'b']).val_at::<String>(0),
+		Err(DecoderError::RlpInconsistentLengthAndData)
+	);
 }
 
 // test described in
@@ -730,3 +781,950 @@ fn test_list_at() {
 	let rlp2 = rlp.at(2).unwrap();
 	assert_eq!(rlp2.val_at::<u16>(2).unwrap(), 33338);
 }
+
+#[test]
+fn test_declared_length_exceeds_buffer() {
+	// String header claims 5 bytes, only 3 follow.
+	assert_eq!(Rlp::new(&[0x85, b'd', b'o', b'g']).data(), Err(DecoderError::RlpInconsistentLengthAndData));
+	// Long string header claims 56 bytes.
+	assert_eq!(Rlp::new(&[0xb8, 56, b'd', b'o', b'g']).data(), Err(DecoderError::RlpInconsistentLengthAndData));
+	// List header claims more bytes than available.
+	assert_eq!(Rlp::new(&[0xc3, 0x01]).at(0).unwrap_err(), DecoderError::RlpInconsistentLengthAndData);
+	assert_eq!(Rlp::new(&[0xf8, 56, 0x01]).at(0).unwrap_err(), DecoderError::RlpInconsistentLengthAndData);
+	// Nested item overruns its parent list.
+	assert_eq!(Rlp::new(&[0xc2, 0x83, b'd', b'o']).at(0).unwrap_err(), DecoderError::RlpInconsistentLengthAndData);
+	// Header itself is truncated.
+	assert_eq!(Rlp::new(&[0xb9, 0x01]).data(), Err(DecoderError::RlpIsTooShort));
+}
+
+#[test]
+fn test_non_canonical_single_byte() {
+	assert_eq!(Rlp::new(&[0x81, 0x00]).data(), Err(DecoderError::RlpInvalidIndirection));
+	assert_eq!(Rlp::new(&[0x81, 0x7f]).payload_info().unwrap_err(), DecoderError::RlpInvalidIndirection);
+	assert_eq!(Rlp::new(&[0xc2, 0x81, 0x05]).at(0).unwrap_err(), DecoderError::RlpInvalidIndirection);
+	assert_eq!(Rlp::new(&[0xc2, 0x81, 0x05]).val_at::<u8>(0), Err(DecoderError::RlpInvalidIndirection));
+
+	// 0x80 and above must use the length prefix.
+	assert_eq!(Rlp::new(&[0x81, 0x80]).data(), Ok(&[0x80u8][..]));
+	assert_eq!(Rlp::new(&[0x7f]).data(), Ok(&[0x7fu8][..]));
+}
+
+#[test]
+fn test_list_roundtrip() {
+	let numbers: Vec<u64> = vec![0, 1, 0x7f, 0x80, 0xffff, u64::max_value()];
+	let encoded = rlp::encode_list(&numbers);
+	assert_eq!(rlp::decode_list::<u64>(&encoded), Ok(numbers));
+
+	let hashes: Vec<H256> = vec![H256::zero(), H256::repeat_byte(0x7f), H256::repeat_byte(0xff)];
+	let encoded = rlp::encode_list(&hashes);
+	assert_eq!(rlp::decode_list::<H256>(&encoded), Ok(hashes));
+
+	let empty: Vec<u64> = Vec::new();
+	assert_eq!(rlp::encode_list(&empty), vec![0xc0]);
+	assert_eq!(rlp::decode_list::<u64>(&[0xc0]), Ok(empty));
+}
+
+#[test]
+fn test_decode_list_errors() {
+	// Not a list.
+	assert_eq!(rlp::decode_list::<u64>(&[0x83, b'c', b'a', b't']), Err(DecoderError::RlpExpectedToBeList));
+	// Item of the wrong size.
+	assert_eq!(rlp::decode_list::<H256>(&[0xc2, 0x81, 0xff]), Err(DecoderError::RlpIsTooShort));
+	// Second item overruns the list.
+	assert_eq!(rlp::decode_list::<u64>(&[0xc2, 0x01, 0x82]), Err(DecoderError::RlpInconsistentLengthAndData));
+}
+
+#[test]
+fn test_net_addr_roundtrip() {
+	use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+	let v4 = Ipv4Addr::new(192, 168, 1, 42);
+	assert_eq!(rlp::encode(&v4).to_vec(), vec![0x84, 192, 168, 1, 42]);
+	assert_eq!(rlp::decode::<Ipv4Addr>(&rlp::encode(&v4)), Ok(v4));
+	assert_eq!(rlp::decode::<IpAddr>(&rlp::encode(&v4)), Ok(IpAddr::V4(v4)));
+
+	let v6 = Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+	let encoded = rlp::encode(&v6);
+	assert_eq!(encoded.len(), 17);
+	assert_eq!(rlp::decode::<Ipv6Addr>(&encoded), Ok(v6));
+	assert_eq!(rlp::decode::<IpAddr>(&rlp::encode(&IpAddr::V6(v6))), Ok(IpAddr::V6(v6)));
+
+	for socket in [SocketAddr::new(v4.into(), 30303), SocketAddr::new(v6.into(), 0)].iter() {
+		assert_eq!(rlp::decode::<SocketAddr>(&rlp::encode(socket)), Ok(*socket));
+	}
+	let encoded = rlp::encode(&SocketAddr::new(v4.into(), 30303));
+	assert_eq!(encoded.to_vec(), vec![0xc8, 0x84, 192, 168, 1, 42, 0x82, 0x76, 0x5f]);
+}
+
+#[test]
+fn test_net_addr_invalid_length() {
+	use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+	assert_eq!(rlp::decode::<Ipv4Addr>(&[0x83, 1, 2, 3]), Err(DecoderError::RlpIsTooShort));
+	assert_eq!(rlp::decode::<Ipv4Addr>(&[0x85, 1, 2, 3, 4, 5]), Err(DecoderError::RlpIsTooBig));
+	assert_eq!(rlp::decode::<Ipv6Addr>(&[0x84, 1, 2, 3, 4]), Err(DecoderError::RlpIsTooShort));
+	assert!(rlp::decode::<IpAddr>(&[0x85, 1, 2, 3, 4, 5]).is_err());
+	assert_eq!(rlp::decode::<IpAddr>(&[0xc0]), Err(DecoderError::RlpExpectedToBeData));
+	// Socket with a three byte address.
+	assert_eq!(
+		rlp::decode::<SocketAddr>(&[0xc7, 0x83, 1, 2, 3, 0x82, 0x76, 0x5f]),
+		Err(DecoderError::Custom("IP address must have 4 or 16 bytes"))
+	);
+	// Socket without a port.
+	assert_eq!(rlp::decode::<SocketAddr>(&[0xc5, 0x84, 1, 2, 3, 4]), Err(DecoderError::RlpIncorrectListLen));
+}
+
+#[test]
+fn test_max_size_rejects_huge_declared_length() {
+	// A string header declaring a five byte length of about 1 TiB, followed by a few bytes only.
+	let data = [0xbc, 0xff, 0xff, 0xff, 0xff, 0x01, 0x02, 0x03];
+	let rlp = Rlp::new_with_max_size(&data, 1024);
+	assert_eq!(rlp.as_val::<Vec<u8>>(), Err(DecoderError::RlpIsTooBig));
+	assert_eq!(rlp.data(), Err(DecoderError::RlpIsTooBig));
+	// Without a limit, the inconsistency with the input is reported instead.
+	assert_eq!(Rlp::new(&data).as_val::<Vec<u8>>(), Err(DecoderError::RlpInconsistentLengthAndData));
+}
+
+#[test]
+fn test_max_size_applies_to_nested_items() {
+	let mut stream = RlpStream::new_list(2);
+	stream.append(&vec![1u8; 16]).append(&vec![2u8; 64]);
+	let data = stream.out();
+
+	let rlp = Rlp::new_with_max_size(&data, 32);
+	assert_eq!(rlp.max_size(), 32);
+	assert_eq!(rlp.val_at::<Vec<u8>>(0), Ok(vec![1u8; 16]));
+	assert_eq!(rlp.val_at::<Vec<u8>>(1), Err(DecoderError::RlpIsTooBig));
+	assert_eq!(rlp.at(1).map(|_| ()), Err(DecoderError::RlpIsTooBig));
+	assert_eq!(rlp.at(0).unwrap().max_size(), 32);
+
+	// The list itself may be larger than the limit.
+	assert_eq!(Rlp::new_with_max_size(&data, 64).as_list::<Vec<u8>>().map(|items| items.len()), Ok(2));
+	assert_eq!(Rlp::new_with_max_size(&data, 63).as_list::<Vec<u8>>(), Err(DecoderError::RlpIsTooBig));
+}
+
+/// A list of lists, nested arbitrarily deep.
+#[derive(Debug, PartialEq)]
+struct Nested(Vec<Nested>);
+
+impl Decodable for Nested {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		rlp.as_list().map(Nested)
+	}
+}
+
+/// `depth` lists, each containing the next one, around an empty list.
+fn nested_lists(depth: usize) -> Vec<u8> {
+	let mut headers = Vec::with_capacity(depth);
+	let mut len: usize = 1;
+	for _ in 0..depth {
+		let header = if len < 56 {
+			vec![0xc0 + len as u8]
+		} else {
+			let len_bytes: Vec<u8> = len.to_be_bytes().iter().copied().skip_while(|&b| b == 0).collect();
+			let mut header = vec![0xf7 + len_bytes.len() as u8];
+			header.extend(len_bytes);
+			header
+		};
+		len += header.len();
+		headers.push(header);
+	}
+	let mut data: Vec<u8> = headers.into_iter().rev().flatten().collect();
+	data.push(0xc0);
+	data
+}
+
+#[test]
+fn test_max_depth_stops_deep_nesting() {
+	// Deep enough to overflow the stack of the thread if every level was descended into.
+	let data = nested_lists(100_000);
+	let result = std::thread::Builder::new()
+		.stack_size(1024 * 1024)
+		.spawn(move || rlp::decode::<Nested>(&data).map(|_| ()))
+		.unwrap()
+		.join()
+		.unwrap();
+	assert_eq!(result, Err(DecoderError::DepthLimitExceeded));
+}
+
+#[test]
+fn test_append_raw_validated_deep_nesting() {
+	// Validating walks the nesting without recursing, so any depth fits in a small stack.
+	let data = nested_lists(300_000);
+	let result = std::thread::Builder::new()
+		.stack_size(1024 * 1024)
+		.spawn(move || {
+			let mut stream = RlpStream::new();
+			let valid = stream.append_raw_validated(&data).map(|_| ());
+			let truncated = RlpStream::new().append_raw_validated(&data[..data.len() - 1]).map(|_| ());
+			(valid, truncated)
+		})
+		.unwrap()
+		.join()
+		.unwrap();
+	assert_eq!(result, (Ok(()), Err(DecoderError::RlpInconsistentLengthAndData)));
+}
+
+#[test]
+fn test_max_depth_limits_descent() {
+	use std::collections::VecDeque;
+
+	let data = nested_lists(rlp::DEFAULT_MAX_DEPTH);
+	assert!(rlp::decode::<Nested>(&data).is_ok());
+	let data = nested_lists(rlp::DEFAULT_MAX_DEPTH + 1);
+	assert_eq!(rlp::decode::<Nested>(&data), Err(DecoderError::DepthLimitExceeded));
+	assert!(Rlp::new_with_max_depth(&data, rlp::DEFAULT_MAX_DEPTH + 1).as_val::<Nested>().is_ok());
+
+	let data = nested_lists(2);
+	let rlp = Rlp::new_with_max_depth(&data, 1);
+	assert_eq!(rlp.max_depth(), 1);
+	let inner = rlp.at(0).unwrap();
+	assert_eq!(inner.max_depth(), 0);
+	assert_eq!(inner.item_count(), Err(DecoderError::DepthLimitExceeded));
+	assert_eq!(inner.at(0).map(|_| ()), Err(DecoderError::DepthLimitExceeded));
+	assert_eq!(
+		inner.iter_limited(usize::max_value()).next().map(|item| item.map(|_| ())),
+		Some(Err(DecoderError::DepthLimitExceeded))
+	);
+	assert_eq!(rlp.as_val::<VecDeque<VecDeque<Nested>>>(), Err(DecoderError::DepthLimitExceeded));
+	assert_eq!(inner.iter().count(), 0);
+	assert_eq!(rlp.to_string(), "[DepthLimitExceeded]");
+	// Empty lists and data can still be decoded at the limit.
+	assert_eq!(Rlp::new_with_max_depth(&[0xc0], 0).item_count(), Ok(0));
+	assert_eq!(Rlp::new_with_max_depth(&[0x05], 0).as_val::<u8>(), Ok(5));
+}
+
+#[test]
+fn test_non_canonical_encodings_are_rejected() {
+	// Single byte wrapped in a string header.
+	assert_eq!(rlp::decode::<u8>(&[0x81, 0x05]), Err(DecoderError::RlpInvalidIndirection));
+	assert_eq!(rlp::decode::<Vec<u8>>(&[0xb8, 0x01, 0x05]), Err(DecoderError::RlpInvalidIndirection));
+	// Long-form header for a short string.
+	assert_eq!(rlp::decode::<String>(&[0xb8, 0x02, b'a', b'b']), Err(DecoderError::RlpInvalidIndirection));
+	assert_eq!(rlp::decode::<u64>(&[0xb8, 0x02, 0x04, 0x00]), Err(DecoderError::RlpInvalidIndirection));
+	// Zero-prefixed lengths.
+	let mut data = vec![0xb9, 0x00, 0x38];
+	data.extend_from_slice(&[b'a'; 56]);
+	assert_eq!(rlp::decode::<Vec<u8>>(&data), Err(DecoderError::RlpDataLenWithZeroPrefix));
+	let mut data = vec![0xf9, 0x00, 0x38];
+	data.extend_from_slice(&[0x01; 56]);
+	assert_eq!(rlp::decode_list::<u8>(&data), Err(DecoderError::RlpListLenWithZeroPrefix));
+	// Integers with leading zeros.
+	assert_eq!(rlp::decode::<u8>(&[0x00]), Err(DecoderError::RlpInvalidIndirection));
+	assert_eq!(rlp::decode::<u32>(&[0x82, 0x00, 0x01]), Err(DecoderError::RlpInvalidIndirection));
+	assert_eq!(rlp::decode::<U256>(&[0x82, 0x00, 0x01]), Err(DecoderError::RlpInvalidIndirection));
+
+	// Canonical encodings still decode.
+	let mut data = vec![0xb8, 0x38];
+	data.extend_from_slice(&[b'a'; 56]);
+	assert_eq!(rlp::decode::<Vec<u8>>(&data), Ok(vec![b'a'; 56]));
+}
+
+#[test]
+fn test_lenient_accepts_long_form_headers() {
+	assert_eq!(Rlp::new_lenient(&[0xb8, 0x02, b'a', b'b']).as_val::<String>(), Ok("ab".to_owned()));
+	assert_eq!(Rlp::new_lenient(&[0xb8, 0x02, 0x04, 0x00]).as_val::<u64>(), Ok(0x0400));
+	// The length still has to be consistent with the data.
+	assert_eq!(
+		Rlp::new_lenient(&[0xb8, 0x03, b'a', b'b']).as_val::<String>(),
+		Err(DecoderError::RlpInconsistentLengthAndData)
+	);
+	// Items of lists are checked when traversing.
+	assert_eq!(
+		Rlp::new_lenient(&[0xc4, 0xb8, 0x02, b'a', b'b']).val_at::<String>(0),
+		Err(DecoderError::RlpInvalidIndirection)
+	);
+}
+
+#[test]
+fn test_rlp_stream_empty_data_and_empty_list() {
+	let mut stream = RlpStream::new();
+	stream.append_empty_data();
+	assert_eq!(stream.out().to_vec(), vec![0x80]);
+
+	let mut stream = RlpStream::new();
+	stream.begin_list(0);
+	assert_eq!(stream.out().to_vec(), vec![0xc0]);
+
+	let mut stream = RlpStream::new_list(2);
+	stream.append_empty_data().begin_list(0);
+	let out = stream.out();
+	assert_eq!(out.to_vec(), vec![0xc2, 0x80, 0xc0]);
+	let rlp = Rlp::new(&out);
+	assert!(rlp.at(0).unwrap().is_data() && rlp.at(0).unwrap().is_empty());
+	assert!(rlp.at(1).unwrap().is_list() && rlp.at(1).unwrap().is_empty());
+	assert_ne!(rlp.at(0).unwrap().as_raw(), rlp.at(1).unwrap().as_raw());
+}
+
+#[test]
+fn test_rlp_stream_append_raw_validated() {
+	let mut item = RlpStream::new_list(2);
+	item.append(&"cat").begin_list(1).append(&1u8);
+	let item = item.out();
+
+	let mut stream = RlpStream::new_list(3);
+	stream.append_raw_validated(&[0x80]).unwrap().append_raw_validated(&item).unwrap();
+	stream.append_raw_validated(&[0x7f]).unwrap();
+	let out = stream.out();
+	assert_eq!(Rlp::new(&out).at(1).unwrap().as_raw(), &item[..]);
+
+	let malformed: &[(&[u8], DecoderError)] = &[
+		(&[], DecoderError::RlpIsTooShort),
+		// declared length exceeds the data
+		(&[0x83, b'c', b'a'], DecoderError::RlpInconsistentLengthAndData),
+		// more than a single item
+		(&[0x80, 0x80], DecoderError::RlpInconsistentLengthAndData),
+		// list item exceeding the list payload
+		(&[0xc2, 0x82, b'c', b'a'], DecoderError::RlpInconsistentLengthAndData),
+		// single byte below 0x80 wrapped in a string header
+		(&[0xc2, 0x81, 0x05], DecoderError::RlpInvalidIndirection),
+		// long string header for a short string
+		(&[0xb8, 0x01, 0x80], DecoderError::RlpInvalidIndirection),
+	];
+	for (bytes, err) in malformed {
+		let mut stream = RlpStream::new_list(1);
+		assert_eq!(stream.append_raw_validated(bytes).err(), Some(err.clone()), "{:?}", bytes);
+		assert!(!stream.is_finished());
+	}
+}
+
+#[test]
+fn test_option_keeps_none_and_empty_distinct() {
+	let cases: [(Option<Vec<u8>>, Vec<u8>); 3] =
+		[(None, vec![0xc0]), (Some(vec![]), vec![0xc1, 0x80]), (Some(vec![1, 2]), vec![0xc3, 0x82, 1, 2])];
+	for (value, encoded) in cases.iter() {
+		assert_eq!(rlp::encode(value).to_vec(), *encoded);
+		assert_eq!(rlp::decode::<Option<Vec<u8>>>(encoded).as_ref(), Ok(value));
+	}
+
+	assert_eq!(rlp::decode::<Option<u64>>(&[0xc1, 0x80]), Ok(Some(0)));
+	assert_eq!(rlp::decode::<Option<Option<u8>>>(&[0xc1, 0xc0]), Ok(Some(None)));
+	assert_eq!(rlp::decode::<Option<u8>>(&[0xc2, 0x01, 0x02]), Err(DecoderError::RlpIncorrectListLen));
+	assert_eq!(rlp::decode::<Option<u8>>(&[0x80]), Err(DecoderError::RlpExpectedToBeList));
+}
+
+#[test]
+fn test_nullable() {
+	let address = H160::repeat_byte(0x11);
+	let mut encoded = vec![0x94];
+	encoded.extend_from_slice(address.as_bytes());
+	assert_eq!(rlp::encode(&Nullable(Some(address))).to_vec(), encoded);
+	assert_eq!(rlp::decode::<Nullable<H160>>(&encoded), Ok(Nullable(Some(address))));
+	assert_eq!(rlp::encode(&Nullable::<H160>(None)).to_vec(), vec![0x80]);
+	assert_eq!(rlp::decode::<Nullable<H160>>(&[0x80]), Ok(Nullable(None)));
+	assert_eq!(rlp::decode::<Nullable<H160>>(&[0x81, 0x80]), Err(DecoderError::RlpIsTooShort));
+
+	// Values encoding as null cannot be told apart from `None`.
+	assert_eq!(rlp::encode(&Nullable(Some(Vec::<u8>::new()))).to_vec(), vec![0x80]);
+	assert_eq!(rlp::decode::<Nullable<Vec<u8>>>(&[0x80]), Ok(Nullable(None)));
+	assert_eq!(rlp::decode::<Nullable<Vec<u8>>>(&[0x81, 0x80]), Ok(Nullable(Some(vec![0x80]))));
+	// The empty list is not null.
+	assert_eq!(rlp::decode::<Nullable<Option<u64>>>(&[0xc0]), Ok(Nullable(Some(None))));
+}
+
+#[test]
+fn test_array_list_round_trip() {
+	let value = [1u64, 0, 1024];
+	let encoded = rlp::encode(&value);
+	assert_eq!(encoded.to_vec(), vec![0xc5, 0x01, 0x80, 0x82, 0x04, 0x00]);
+	assert_eq!(rlp::decode::<[u64; 3]>(&encoded), Ok(value));
+	assert_eq!(rlp::decode::<[u64; 2]>(&encoded), Err(DecoderError::RlpIncorrectListLen));
+	assert_eq!(rlp::decode::<[u64; 4]>(&encoded), Err(DecoderError::RlpIncorrectListLen));
+	assert_eq!(rlp::decode::<[u64; 3]>(&[0x83, 1, 2, 3]), Err(DecoderError::RlpExpectedToBeList));
+
+	let strings = [String::from("cat"), String::from("dog")];
+	assert_eq!(rlp::decode::<[String; 2]>(&rlp::encode(&strings)), Ok(strings));
+	assert_eq!(rlp::encode(&[0u8; 0]).to_vec(), vec![0xc0]);
+	assert_eq!(rlp::decode::<[u8; 0]>(&[0xc0]), Ok([]));
+
+	let bytes = [0x01u8, 0xff];
+	assert_eq!(rlp::encode(&bytes).to_vec(), vec![0xc3, 0x01, 0x81, 0xff]);
+	assert_eq!(rlp::decode::<[u8; 2]>(&[0xc3, 0x01, 0x81, 0xff]), Ok(bytes));
+}
+
+#[test]
+fn test_fixed_bytes_round_trip() {
+	let bytes = FixedBytes([0x11u8; 32]);
+	let encoded = rlp::encode(&bytes);
+	assert_eq!(encoded[0], 0xa0);
+	assert_eq!(&encoded[1..], &[0x11u8; 32][..]);
+	assert_eq!(rlp::decode::<FixedBytes<32>>(&encoded), Ok(bytes));
+	assert_eq!(rlp::encode(&bytes), rlp::encode(&H256::repeat_byte(0x11)));
+	assert_eq!(rlp::decode::<FixedBytes<31>>(&encoded), Err(DecoderError::RlpIsTooBig));
+	assert_eq!(rlp::decode::<FixedBytes<33>>(&encoded), Err(DecoderError::RlpIsTooShort));
+	assert_eq!(rlp::decode::<FixedBytes<1>>(&[0x05]), Ok(FixedBytes([0x05])));
+	assert_eq!(rlp::decode::<FixedBytes<0>>(&[0x80]), Ok(FixedBytes([])));
+
+	let mut stream = RlpStream::new_list(2);
+	stream.append(&FixedBytes([0xab; 4])).append(&[FixedBytes([1u8, 2]), FixedBytes([3, 4])]);
+	let out = stream.out();
+	assert_eq!(out.to_vec(), vec![0xcc, 0x84, 0xab, 0xab, 0xab, 0xab, 0xc6, 0x82, 1, 2, 0x82, 3, 4]);
+	let rlp = Rlp::new(&out);
+	assert_eq!(rlp.val_at::<[FixedBytes<2>; 2]>(1), Ok([FixedBytes([1, 2]), FixedBytes([3, 4])]));
+}
+
+#[test]
+fn test_tuple_round_trip() {
+	assert_eq!(rlp::encode(&()).to_vec(), vec![0xc0]);
+	assert_eq!(rlp::decode::<()>(&[0xc0]), Ok(()));
+
+	let pair = (vec![0xcau8, 0xfe], 1024u32);
+	let encoded = rlp::encode(&pair);
+	assert_eq!(encoded.to_vec(), vec![0xc6, 0x82, 0xca, 0xfe, 0x82, 0x04, 0x00]);
+	assert_eq!(rlp::decode::<(Vec<u8>, u32)>(&encoded), Ok(pair));
+
+	let nested = ((1u8, String::from("cat")), (), (vec![0u8; 0],), true);
+	let encoded = rlp::encode(&nested);
+	assert_eq!(encoded.to_vec(), vec![0xca, 0xc5, 0x01, 0x83, b'c', b'a', b't', 0xc0, 0xc1, 0x80, 0x01]);
+	assert_eq!(rlp::decode::<((u8, String), (), (Vec<u8>,), bool)>(&encoded), Ok(nested));
+
+	let twelve = (1u8, 2u8, 3u8, 4u8, 5u8, 6u8, 7u8, 8u8, 9u8, 10u8, 11u8, 12u8);
+	let encoded = rlp::encode(&twelve);
+	assert_eq!(encoded.to_vec(), vec![0xcc, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+	assert_eq!(rlp::decode(&encoded), Ok(twelve));
+}
+
+#[test]
+fn test_tuple_wrong_arity() {
+	let encoded = rlp::encode(&(1u8, 2u8, 3u8));
+	assert_eq!(rlp::decode::<(u8, u8)>(&encoded), Err(DecoderError::RlpIncorrectListLen));
+	assert_eq!(rlp::decode::<(u8, u8, u8, u8)>(&encoded), Err(DecoderError::RlpIncorrectListLen));
+	assert_eq!(rlp::decode::<()>(&encoded), Err(DecoderError::RlpIncorrectListLen));
+	assert_eq!(rlp::decode::<(u8,)>(&[0xc0]), Err(DecoderError::RlpIncorrectListLen));
+	assert_eq!(rlp::decode::<(u8, u8)>(&[0x82, 1, 2]), Err(DecoderError::RlpExpectedToBeList));
+	assert_eq!(rlp::decode::<(u8, String)>(&[0xc2, 0x01, 0xc0]), Err(DecoderError::RlpExpectedToBeData));
+}
+
+#[test]
+fn test_collections_round_trip() {
+	use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+	let deque: VecDeque<u16> = vec![1, 2, 1024].into();
+	let encoded = rlp::encode(&deque);
+	assert_eq!(encoded.to_vec(), vec![0xc5, 0x01, 0x02, 0x82, 0x04, 0x00]);
+	assert_eq!(rlp::decode::<VecDeque<u16>>(&encoded), Ok(deque));
+	assert_eq!(rlp::decode::<VecDeque<u8>>(&[0x82, 0x01, 0x02]), Err(DecoderError::RlpExpectedToBeList));
+
+	let set: BTreeSet<String> = vec!["dog".to_owned(), "cat".to_owned()].into_iter().collect();
+	let encoded = rlp::encode(&set);
+	assert_eq!(encoded.to_vec(), vec![0xc8, 0x83, b'c', b'a', b't', 0x83, b'd', b'o', b'g']);
+	assert_eq!(rlp::decode::<BTreeSet<String>>(&encoded), Ok(set));
+
+	let mut map = BTreeMap::new();
+	map.insert(2u8, vec![0xcau8, 0xfe]);
+	map.insert(1u8, vec![]);
+	let encoded = rlp::encode(&map);
+	assert_eq!(encoded.to_vec(), vec![0xc8, 0xc2, 0x01, 0x80, 0xc4, 0x02, 0x82, 0xca, 0xfe]);
+	assert_eq!(rlp::decode::<BTreeMap<u8, Vec<u8>>>(&encoded), Ok(map));
+	assert_eq!(rlp::encode(&BTreeMap::<u8, u8>::new()).to_vec(), vec![0xc0]);
+}
+
+#[test]
+fn test_collections_canonical_order() {
+	use std::collections::{BTreeMap, BTreeSet};
+
+	// [[2, 0xcafe], [1, ""]]
+	let out_of_order = [0xc8, 0xc4, 0x02, 0x82, 0xca, 0xfe, 0xc2, 0x01, 0x80];
+	assert!(matches!(rlp::decode::<BTreeMap<u8, Vec<u8>>>(&out_of_order), Err(DecoderError::Custom(_))));
+	// [[1, 1], [1, 2]]
+	let duplicate = [0xc6, 0xc2, 0x01, 0x01, 0xc2, 0x01, 0x02];
+	assert!(matches!(rlp::decode::<BTreeMap<u8, u8>>(&duplicate), Err(DecoderError::Custom(_))));
+	// [[1, 1, 1]]
+	let triple = [0xc4, 0xc3, 0x01, 0x01, 0x01];
+	assert_eq!(rlp::decode::<BTreeMap<u8, u8>>(&triple), Err(DecoderError::RlpIncorrectListLen));
+
+	assert!(matches!(rlp::decode::<BTreeSet<u8>>(&[0xc2, 0x02, 0x01]), Err(DecoderError::Custom(_))));
+	assert!(matches!(rlp::decode::<BTreeSet<u8>>(&[0xc2, 0x01, 0x01]), Err(DecoderError::Custom(_))));
+	assert_eq!(rlp::decode::<BTreeSet<u8>>(&[0xc2, 0x01, 0x02]), Ok(vec![1, 2].into_iter().collect()));
+}
+
+#[test]
+fn test_collections_malformed_items() {
+	use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+	// [1, <truncated string>]: the second item is cut off instead of the collection.
+	let truncated = [0xc3, 0x01, 0x82, 0x02];
+	assert_eq!(rlp::decode::<VecDeque<u64>>(&truncated), Err(DecoderError::RlpInconsistentLengthAndData));
+	assert_eq!(rlp::decode::<BTreeSet<u64>>(&truncated), Err(DecoderError::RlpInconsistentLengthAndData));
+	// [[1, 1], <truncated string>]
+	let truncated = [0xc5, 0xc2, 0x01, 0x01, 0x82, 0x02];
+	assert_eq!(rlp::decode::<BTreeMap<u8, u8>>(&truncated), Err(DecoderError::RlpInconsistentLengthAndData));
+
+	assert_eq!(rlp::decode::<BTreeSet<u8>>(&[0x82, 0x01, 0x02]), Err(DecoderError::RlpExpectedToBeList));
+	assert_eq!(rlp::decode::<BTreeMap<u8, u8>>(&[0x82, 0x01, 0x02]), Err(DecoderError::RlpExpectedToBeList));
+}
+
+#[cfg(feature = "smallvec")]
+#[test]
+fn test_smallvec_round_trip() {
+	use smallvec::{smallvec, SmallVec};
+
+	let inline: SmallVec<[u32; 4]> = smallvec![1, 2];
+	let encoded = rlp::encode(&inline);
+	assert_eq!(encoded.to_vec(), vec![0xc2, 0x01, 0x02]);
+	assert_eq!(rlp::decode::<SmallVec<[u32; 4]>>(&encoded), Ok(inline));
+
+	let spilled: SmallVec<[String; 1]> = smallvec!["cat".to_owned(), "dog".to_owned()];
+	let decoded = rlp::decode::<SmallVec<[String; 1]>>(&rlp::encode(&spilled)).unwrap();
+	assert!(decoded.spilled());
+	assert_eq!(decoded, spilled);
+
+	assert_eq!(
+		rlp::decode::<SmallVec<[u64; 4]>>(&[0xc3, 0x01, 0x82, 0x02]),
+		Err(DecoderError::RlpInconsistentLengthAndData)
+	);
+}
+
+#[test]
+fn test_append_list_from_iter_matches_counted_list() {
+	let words: Vec<String> = (0..40).map(|i| format!("word{}", i)).collect();
+	let long: Vec<&String> = words.iter().filter(|w| w.len() > 5).collect();
+
+	let mut counted = RlpStream::new();
+	counted.begin_list(long.len());
+	for word in &long {
+		counted.append(*word);
+	}
+	let mut streamed = RlpStream::new();
+	streamed.append_list_from_iter::<String, _, _>(words.iter().filter(|w| w.len() > 5));
+	assert_eq!(streamed.out(), counted.out());
+
+	let mut counted = RlpStream::new();
+	counted.begin_list(0);
+	let mut streamed = RlpStream::new();
+	streamed.append_list_from_iter(std::iter::empty::<u64>());
+	assert_eq!(streamed.out(), counted.out());
+}
+
+#[test]
+fn test_append_list_from_iter_nested() {
+	struct Counted(Vec<Vec<u64>>);
+	struct Streamed(Vec<Vec<u64>>);
+
+	impl Encodable for Counted {
+		fn rlp_append(&self, s: &mut RlpStream) {
+			s.begin_list(self.0.len() + 1);
+			for inner in &self.0 {
+				s.append_list(inner);
+			}
+			s.append(&"end");
+		}
+	}
+
+	impl Encodable for Streamed {
+		fn rlp_append(&self, s: &mut RlpStream) {
+			s.begin_unbounded_list();
+			for inner in &self.0 {
+				s.append_list_from_iter(inner.iter().copied());
+			}
+			s.append(&"end");
+			s.finalize_unbounded_list();
+		}
+	}
+
+	let nested = vec![vec![], vec![1, 2, 3], (0..30).map(|i| i << 40).collect(), vec![0]];
+	let counted = Counted(nested.clone());
+	let streamed = Streamed(nested);
+	assert_eq!(rlp::encode(&streamed), rlp::encode(&counted));
+
+	let mut stream = RlpStream::new_list(2);
+	stream.append_list_from_iter(vec![Counted(vec![vec![7]]), Counted(vec![])]).append(&streamed);
+	let mut expected = RlpStream::new_list(2);
+	expected.begin_list(2).append(&Counted(vec![vec![7]])).append(&Counted(vec![])).append(&counted);
+	assert_eq!(stream.out(), expected.out());
+}
+
+#[test]
+fn test_append_raw_slices_reconstructs_list() {
+	let words: Vec<String> = (0..20).map(|i| format!("word{}", i)).collect();
+	let mut direct = RlpStream::new_list(words.len() + 1);
+	for word in &words {
+		direct.append(word);
+	}
+	direct.append_list::<u64, _>(&[1, 2, 3]);
+
+	let encoded: Vec<Vec<u8>> = words.iter().map(|word| rlp::encode(word).to_vec()).collect();
+	let mut slices: Vec<&[u8]> = encoded.iter().map(|item| &item[..]).collect();
+	let tail = rlp::encode_list::<u64>(&[1, 2, 3]);
+	slices.push(&tail);
+	let mut spliced = RlpStream::new_list(words.len() + 1);
+	spliced.append_raw_slices(&slices[..10], 10);
+	assert!(!spliced.is_finished());
+	spliced.append_raw_slices(&slices[10..], slices.len() - 10);
+	assert!(spliced.is_finished());
+	assert_eq!(spliced.out(), direct.out());
+}
+
+/// The encodings of `0..count` as `u64`, produced one at a time while being read.
+struct EncodedCounter {
+	next: u64,
+	count: u64,
+	pending: Vec<u8>,
+	position: usize,
+}
+
+impl std::io::Read for EncodedCounter {
+	fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+		if self.position == self.pending.len() {
+			if self.next == self.count {
+				return Ok(0);
+			}
+			self.pending = rlp::encode(&self.next).to_vec();
+			self.position = 0;
+			self.next += 1;
+		}
+		let len = cmp::min(buf.len(), self.pending.len() - self.position);
+		buf[..len].copy_from_slice(&self.pending[self.position..self.position + len]);
+		self.position += len;
+		Ok(len)
+	}
+}
+
+#[test]
+fn test_stream_decode_many_items() {
+	let count = 2_000_000;
+	let reader = EncodedCounter { next: 0, count, pending: Vec::new(), position: 0 };
+	let mut expected = 0;
+	for value in RlpFileIter::<_, u64>::new(ItemReader::with_max_size(reader, 8)) {
+		assert_eq!(value.unwrap(), expected);
+		expected += 1;
+	}
+	assert_eq!(expected, count);
+}
+
+#[test]
+fn test_stream_decode_lists() {
+	let mut stream = RlpStream::new();
+	stream.append(&"header");
+	stream.begin_list(3).append(&"cat").append_list::<u8, _>(&[1, 2]).append(&"dog");
+	stream.append(&7u8);
+	let data = stream.out();
+
+	let mut items = ItemReader::new(&data[..]);
+	assert_eq!(items.read_value::<String>().unwrap(), Some("header".to_owned()));
+	assert_eq!(items.enter_list().unwrap(), 11);
+	assert_eq!(items.read_value::<String>().unwrap(), Some("cat".to_owned()));
+	assert_eq!(items.enter_list().unwrap(), 2);
+	assert_eq!(items.read_item().unwrap(), Some(&[0x01][..]));
+	assert_eq!(items.read_item().unwrap(), Some(&[0x02][..]));
+	assert_eq!(items.read_item().unwrap(), None);
+	assert_eq!(items.read_value::<String>().unwrap(), Some("dog".to_owned()));
+	assert_eq!(items.read_item().unwrap(), None);
+	assert_eq!(items.read_value::<u8>().unwrap(), Some(7));
+	assert_eq!(items.read_item().unwrap(), None);
+}
+
+#[test]
+fn test_stream_decode_errors() {
+	fn decoder_error(result: Result<Option<&[u8]>, ReadError>) -> DecoderError {
+		match result {
+			Err(ReadError::Decoder(err)) => err,
+			other => panic!("expected a decoder error, got {:?}", other),
+		}
+	}
+
+	// Truncated within the payload of an item, after a complete one.
+	let data = [0x83, b'c', b'a', b't', 0x83, b'd', b'o'];
+	let mut values = RlpFileIter::<_, String>::new(ItemReader::new(&data[..]));
+	assert_eq!(values.next().unwrap().unwrap(), "cat");
+	match values.next() {
+		Some(Err(ReadError::Decoder(DecoderError::RlpInconsistentLengthAndData))) => (),
+		other => panic!("unexpected {:?}", other),
+	}
+	assert!(values.next().is_none());
+
+	// Truncated within a header.
+	assert_eq!(decoder_error(ItemReader::new(&[0xb9, 0x01][..]).read_item()), DecoderError::RlpIsTooShort);
+	// Truncated within an entered list.
+	let mut items = ItemReader::new(&[0xc3, 0x01][..]);
+	items.enter_list().unwrap();
+	items.read_item().unwrap();
+	assert_eq!(decoder_error(items.read_item()), DecoderError::RlpInconsistentLengthAndData);
+	// An item exceeding the payload of the entered list.
+	let mut items = ItemReader::new(&[0xc2, 0x82, 0x01, 0x02][..]);
+	items.enter_list().unwrap();
+	assert_eq!(decoder_error(items.read_item()), DecoderError::RlpInconsistentLengthAndData);
+	// Non-canonical and oversized items.
+	assert_eq!(decoder_error(ItemReader::new(&[0x81, 0x05][..]).read_item()), DecoderError::RlpInvalidIndirection);
+	assert_eq!(
+		decoder_error(ItemReader::new(&[0xb8, 0x01, 0x80][..]).read_item()),
+		DecoderError::RlpInvalidIndirection
+	);
+	let huge = [0xbf, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+	assert_eq!(decoder_error(ItemReader::with_max_size(&huge[..], 1024).read_item()), DecoderError::RlpIsTooBig);
+
+	// I/O errors are passed on as such.
+	struct Failing;
+	impl std::io::Read for Failing {
+		fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+			Err(std::io::Error::new(std::io::ErrorKind::Other, "disk on fire"))
+		}
+	}
+	match ItemReader::new(Failing).read_item() {
+		Err(ReadError::Io(err)) => assert_eq!(err.to_string(), "disk on fire"),
+		other => panic!("expected an I/O error, got {:?}", other),
+	}
+}
+
+#[test]
+fn test_encode_into_existing_buffers() {
+	let words = ["cat".to_owned(), "dog".to_owned(), "a".repeat(60)];
+	let mut expected = rlp::encode(&0x1234u64).to_vec();
+	expected.extend_from_slice(&rlp::encode_list(&words));
+
+	let mut vec = Vec::new();
+	rlp::encode_into(&0x1234u64, &mut vec);
+	rlp::encode_into(&words, &mut vec);
+	assert_eq!(vec, expected);
+
+	// The encoding is written into the allocation of the `Vec`.
+	let mut vec = Vec::with_capacity(expected.len());
+	let ptr = vec.as_ptr();
+	rlp::encode_into(&0x1234u64, &mut vec);
+	rlp::encode_into(&words, &mut vec);
+	assert_eq!(vec, expected);
+	assert_eq!(vec.as_ptr(), ptr);
+
+	let mut buf = BytesMut::new();
+	rlp::encode_to_buf(&0x1234u64, &mut buf);
+	rlp::encode_to_buf(&words, &mut buf);
+	assert_eq!(buf, expected);
+
+	let mut buf = BytesMut::new();
+	0x1234u64.rlp_bytes_into(&mut buf);
+	words.rlp_bytes_into(&mut buf);
+	assert_eq!(buf, expected);
+}
+
+#[test]
+fn test_rlp_bytes_into_reuses_buffer() {
+	let mut buf = BytesMut::with_capacity(4096);
+	for i in 0..1000u64 {
+		i.rlp_bytes_into(&mut buf);
+	}
+	let mut stream = RlpStream::new();
+	for i in 0..1000u64 {
+		stream.append(&i);
+	}
+	assert_eq!(buf, stream.out());
+
+	let (ptr, capacity) = (buf.as_ptr(), buf.capacity());
+	buf.clear();
+	for i in 0..1000u64 {
+		i.rlp_bytes_into(&mut buf);
+	}
+	assert_eq!((buf.as_ptr(), buf.capacity()), (ptr, capacity));
+}
+
+#[test]
+fn test_rlp_len_hint_bounds_encoding() {
+	fn check<E: Encodable>(value: E) {
+		let hint = value.rlp_len_hint().expect("all checked types give a hint");
+		let len = rlp::encode(&value).len();
+		assert!(hint >= len, "hint {} below the length {} of {:?}", hint, len, rlp::encode(&value));
+	}
+
+	for &value in &[0u8, 1, 0x7f, 0x80, 0xff] {
+		check(value);
+	}
+	for &value in &[0u64, 1, 0x80, 0x1234, u64::max_value()] {
+		check(value);
+		check(value as u16);
+		check(value as u32);
+		check(value as u128);
+		check(value as usize);
+	}
+	check(u128::max_value());
+	check(true);
+	check(false);
+	for len in &[0usize, 1, 55, 56, 255, 256, 70_000] {
+		let bytes = vec![0xaa; *len];
+		check(&bytes[..]);
+		check(Box::new(bytes.clone()));
+		check(Bytes::from(bytes.clone()));
+		check(BytesMut::from(&bytes[..]));
+		check("a".repeat(*len));
+		check(&*"a".repeat(*len));
+		check(bytes);
+	}
+	check([0u8; 0]);
+	check([0x80u8; 20]);
+	check([u64::max_value(); 10]);
+	check(FixedBytes([0xff; 1]));
+	check(FixedBytes([0xff; 56]));
+	check(U256::zero());
+	check(U256::max_value());
+	check(H160::repeat_byte(0x12));
+	check(H256::zero());
+}
+
+#[test]
+fn test_encoded_len_bound() {
+	assert_eq!(rlp::encoded_len_bound(0), 1);
+	assert_eq!(rlp::encoded_len_bound(55), 56);
+	assert_eq!(rlp::encoded_len_bound(56), 58);
+	assert_eq!(rlp::encoded_len_bound(255), 257);
+	assert_eq!(rlp::encoded_len_bound(256), 259);
+	assert_eq!(rlp::encoded_len_bound(1 << 24), (1 << 24) + 5);
+
+	let mut stream = RlpStream::with_capacity(2, 64);
+	stream.append(&"cat").append(&"dog");
+	assert_eq!(stream.out(), rlp::encode_list(&["cat", "dog"]));
+}
+
+#[test]
+fn test_at_in_any_order() {
+	let mut stream = RlpStream::new_list(100);
+	for i in 0..100u64 {
+		stream.append(&(i * 1000));
+	}
+	let data = stream.out();
+	let rlp = Rlp::new(&data);
+	for i in (0..100).rev() {
+		assert_eq!(rlp.val_at::<u64>(i), Ok(i as u64 * 1000));
+	}
+	for &i in &[50, 3, 99, 0, 42, 98, 1] {
+		assert_eq!(rlp.val_at::<u64>(i), Ok(i as u64 * 1000));
+		assert_eq!(rlp.at_with_offset(i).unwrap().1, Rlp::new(&data).at_with_offset(i).unwrap().1);
+	}
+	assert_eq!(rlp.at(100).map(|_| ()), Err(DecoderError::RlpIsTooShort));
+	assert_eq!(rlp.val_at::<u64>(10), Ok(10000));
+
+	// Errors past the end of the data are the same whichever item was accessed first.
+	let data = [0xc4, 0x01, 0x02, 0x83, 0x03];
+	let rlp = Rlp::new(&data);
+	assert_eq!(rlp.at(2).map(|_| ()), Err(DecoderError::RlpInconsistentLengthAndData));
+	assert_eq!(rlp.val_at::<u8>(1), Ok(2));
+	assert_eq!(rlp.val_at::<u8>(0), Ok(1));
+	assert_eq!(rlp.at(2).map(|_| ()), Err(DecoderError::RlpInconsistentLengthAndData));
+}
+
+#[test]
+fn test_as_list() {
+	let values: Vec<u64> = vec![0, 1, 0x7f, 0x80, 0xffff, u64::max_value()];
+	let mut stream = RlpStream::new();
+	stream.append_list(&values);
+	let data = stream.out();
+	assert_eq!(Rlp::new(&data).as_list::<u64>(), Ok(values));
+	assert_eq!(Rlp::new(&[0xc0]).as_list::<u64>(), Ok(vec![]));
+
+	assert_eq!(Rlp::new(&[0x83, b'c', b'a', b't']).as_list::<u8>(), Err(DecoderError::RlpExpectedToBeList));
+	assert_eq!(Rlp::new(&[0x05]).as_list::<u8>(), Err(DecoderError::RlpExpectedToBeList));
+	// The second item declares more bytes than are left in the list.
+	assert_eq!(Rlp::new(&[0xc3, 0x01, 0x82, 0x02]).as_list::<u8>(), Err(DecoderError::RlpInconsistentLengthAndData));
+	// The second item is too big for a `u8`.
+	assert_eq!(Rlp::new(&[0xc4, 0x01, 0x82, 0x01, 0x00]).as_list::<u8>(), Err(DecoderError::RlpIsTooBig));
+}
+
+#[test]
+fn test_list_limits() {
+	let values: Vec<u64> = (0..10).collect();
+	let mut stream = RlpStream::new();
+	stream.append_list(&values);
+	let data = stream.out();
+	let rlp = Rlp::new(&data);
+	assert_eq!(rlp.as_list_with_max::<u64>(10), Ok(values.clone()));
+	assert_eq!(rlp.as_list_with_max::<u64>(9), Err(DecoderError::ListLimitExceeded));
+	assert_eq!(Rlp::new(&[0xc0]).as_list_with_max::<u64>(0), Ok(vec![]));
+
+	let items: Vec<_> = rlp.iter_limited(3).collect();
+	assert_eq!(items.len(), 4);
+	assert_eq!(items[2].as_ref().unwrap().as_val::<u64>(), Ok(2));
+	assert_eq!(items[3].as_ref().err(), Some(&DecoderError::ListLimitExceeded));
+	assert_eq!(rlp.iter_limited(10).count(), 10);
+	let errors: Vec<_> = Rlp::new(&[0x05]).iter_limited(10).map(|item| item.err()).collect();
+	assert_eq!(errors, vec![Some(DecoderError::RlpExpectedToBeList)]);
+
+	// Items after the limit are not decoded.
+	struct Counted;
+	impl Decodable for Counted {
+		fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+			DECODED.with(|decoded| decoded.set(decoded.get() + 1));
+			rlp.as_val::<u64>().map(|_| Counted)
+		}
+	}
+	thread_local!(static DECODED: std::cell::Cell<usize> = std::cell::Cell::new(0));
+	assert!(rlp.as_list_with_max::<Counted>(4).is_err());
+	assert_eq!(DECODED.with(|decoded| decoded.get()), 4);
+
+	// A header declaring a list of `u32::MAX` bytes over a tiny payload fails without
+	// allocating for it.
+	let forged = [0xfb, 0xff, 0xff, 0xff, 0xff, 0x01, 0x02];
+	assert_eq!(Rlp::new(&forged).as_list::<u8>(), Err(DecoderError::RlpInconsistentLengthAndData));
+	assert_eq!(Rlp::new(&forged).iter_limited(usize::max_value()).count(), 1);
+
+	// The limit of a view applies to nested lists, and caps explicit limits.
+	let mut stream = RlpStream::new_list(2);
+	stream.append_list(&values[..2]).append_list(&values);
+	let data = stream.out();
+	let rlp = Rlp::new_with_limits(&data, usize::max_value(), 8, 4);
+	assert_eq!(rlp.max_items(), 4);
+	assert_eq!(rlp.list_at::<u64>(0), Ok(vec![0, 1]));
+	assert_eq!(rlp.list_at::<u64>(1), Err(DecoderError::ListLimitExceeded));
+	assert_eq!(rlp.at(1).unwrap().as_list_with_max::<u64>(100), Err(DecoderError::ListLimitExceeded));
+	assert_eq!(Rlp::new(&data).list_at::<u64>(1), Ok(values));
+}
+
+#[test]
+fn test_decode_borrowed() {
+	let payload = vec![0xab; 100];
+	let mut stream = RlpStream::new_list(4);
+	stream.append(&"cat").append(&payload).append(&0x05u8).append(&&[0xffu8, 0xfe][..]);
+	let data = stream.out();
+	let range = data.as_ptr_range();
+
+	// Borrowed values point into the input and outlive the temporary `Rlp` of each item.
+	let rlp = Rlp::new(&data);
+	let (animal, bytes, single): (&str, &[u8], &[u8]) = (
+		rlp.at(0).unwrap().as_val_borrowed().unwrap(),
+		rlp.at(1).unwrap().as_val_borrowed().unwrap(),
+		rlp.at(2).unwrap().as_val_borrowed().unwrap(),
+	);
+	assert_eq!(animal, "cat");
+	assert!(range.contains(&animal.as_ptr()));
+	assert_eq!(bytes, &payload[..]);
+	assert!(range.contains(&bytes.as_ptr()));
+	assert_eq!(single, &[0x05]);
+	assert!(range.contains(&single.as_ptr()));
+
+	let cow: Cow<[u8]> = rlp.val_at_borrowed(1).unwrap();
+	assert!(matches!(cow, Cow::Borrowed(bytes) if range.contains(&bytes.as_ptr())));
+	assert_eq!(rlp.val_at_borrowed::<&[u8]>(3), Ok(&[0xff, 0xfe][..]));
+
+	// Invalid UTF-8 is rejected as for `String`.
+	assert_eq!(rlp.val_at_borrowed::<&str>(3), Err(DecoderError::RlpExpectedToBeData));
+	assert_eq!(rlp.val_at::<String>(3), Err(DecoderError::RlpExpectedToBeData));
+	// Lists and malformed values are rejected as when decoding owned values.
+	assert_eq!(rlp.as_val_borrowed::<&[u8]>(), Err(DecoderError::RlpExpectedToBeData));
+	assert_eq!(Rlp::new(&[0x83, b'c']).as_val_borrowed::<&str>(), Err(DecoderError::RlpInconsistentLengthAndData));
+	assert_eq!(Rlp::new(&[0x81, 0x05]).as_val_borrowed::<&[u8]>(), Err(DecoderError::RlpInvalidIndirection));
+}
+
+#[test]
+fn test_decode_located() {
+	// [[1, "cat"], [2, 0x0003]]: the second number of the second list has a leading zero byte.
+	let data = [0xcb, 0xc5, 0x01, 0x83, b'c', b'a', b't', 0xc4, 0x02, 0x82, 0x00, 0x03];
+	type Pairs = ((u64, String), (u64, u64));
+	let err = rlp::decode_located::<Pairs>(&data).unwrap_err();
+	assert_eq!(err, LocatedError { offset: 9, error: DecoderError::RlpInvalidIndirection });
+	assert_eq!(rlp::decode::<Pairs>(&data), Err(err.error.clone()));
+	assert_eq!(err.to_string(), "RlpInvalidIndirection at byte offset 9");
+
+	// An item of the wrong kind is reported at that item.
+	let err = rlp::decode_located::<(u64, u64)>(&data).unwrap_err();
+	assert_eq!(err, LocatedError { offset: 1, error: DecoderError::RlpExpectedToBeData });
+
+	// A failure to decode an earlier item which was recovered from is not reported.
+	#[derive(Debug)]
+	struct Recovering(Option<u8>);
+	impl Decodable for Recovering {
+		fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+			Ok(Recovering(rlp.as_val().ok()))
+		}
+	}
+	let data = rlp::encode_list(&[vec![0xff, 0xfe], b"cat".to_vec()]);
+	let err = rlp::decode_located::<(Recovering, u8)>(&data).unwrap_err();
+	assert_eq!(err, LocatedError { offset: 4, error: DecoderError::RlpIsTooBig });
+
+	assert_eq!(rlp::decode_located::<(u64, u64)>(&[0xc2, 0x01, 0x02]), Ok((1, 2)));
+}
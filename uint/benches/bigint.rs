@@ -41,6 +41,7 @@ criterion_group!(
 	u256_sub,
 	u256_mul,
 	u256_mul_full,
+	u256_square,
 	u256_div,
 	u512_div_mod,
 	u256_rem,
@@ -57,6 +58,7 @@ criterion_group!(
 	u512_add,
 	u512_sub,
 	u512_mul,
+	u512_square,
 	u512_div,
 	u512_rem,
 	u512_integer_sqrt,
@@ -167,6 +169,23 @@ fn u256_mul(c: &mut Criterion) {
 	);
 }
 
+fn u256_square(c: &mut Criterion) {
+	c.bench(
+		"u256_square",
+		ParameterizedBenchmark::new("squared", |b, x| b.iter(|| black_box(x.overflowing_squared().0)), u256_squares())
+			.with_function("mul", |b, x| b.iter(|| black_box(x.overflowing_mul(*x).0))),
+	);
+}
+
+fn u256_squares() -> Vec<U256> {
+	vec![
+		U256::from(u64::max_value()),
+		U256::from(u128::max_value()),
+		U256::from_dec_str("21674844646682989462120101885968193938394323990565507610662749").unwrap(),
+		U256::MAX,
+	]
+}
+
 fn u512_div_mod(c: &mut Criterion) {
 	c.bench(
 		"u512_div_mod",
@@ -304,6 +323,15 @@ fn u512_mul(c: &mut Criterion) {
 	);
 }
 
+fn u512_square(c: &mut Criterion) {
+	let values = vec![U512::from(u128::max_value()), U256::MAX.full_mul(U256::one()), U512::MAX];
+	c.bench(
+		"u512_square",
+		ParameterizedBenchmark::new("squared", |b, x| b.iter(|| black_box(x.overflowing_squared().0)), values)
+			.with_function("mul", |b, x| b.iter(|| black_box(x.overflowing_mul(*x).0))),
+	);
+}
+
 fn u512_integer_sqrt(c: &mut Criterion) {
 	c.bench(
 		"u512_integer_sqrt",
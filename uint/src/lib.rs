@@ -33,6 +33,20 @@ pub use rand07;
 #[doc(hidden)]
 pub use arbitrary;
 
+#[cfg(feature = "bytemuck")]
+#[doc(hidden)]
+pub use bytemuck;
+
+#[cfg(feature = "proptest")]
+extern crate alloc;
+
+#[cfg(feature = "proptest")]
+#[doc(hidden)]
+pub use proptest;
+
+#[cfg(feature = "proptest")]
+pub mod strategy;
+
 #[doc(hidden)]
 pub use static_assertions;
 
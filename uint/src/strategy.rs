@@ -0,0 +1,211 @@
+// Copyright 2021 Parity Technologies
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Proptest strategies for integers created by `construct_uint!`.
+//!
+//! Generated values shrink numerically towards the lower bound of the
+//! strategy (zero for `any::<U256>()`) rather than limb by limb.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::{Add, Not, Range, Shl, Shr, Sub};
+
+use alloc::vec;
+
+use proptest::prelude::{Rng, RngCore};
+use proptest::strategy::{NewTree, Strategy, ValueTree};
+use proptest::test_runner::{TestRng, TestRunner};
+
+/// Operations needed by the strategies, implemented by `construct_uint!`.
+#[doc(hidden)]
+pub trait Uint:
+	Copy
+	+ Ord
+	+ Default
+	+ fmt::Debug
+	+ From<u64>
+	+ for<'a> From<&'a [u8]>
+	+ Add<Self, Output = Self>
+	+ Sub<Self, Output = Self>
+	+ Shl<usize, Output = Self>
+	+ Shr<usize, Output = Self>
+	+ Not<Output = Self>
+{
+	/// Width of the type in bytes.
+	const BYTES: usize;
+
+	/// Least number of bits needed to represent the number.
+	fn bits(&self) -> usize;
+}
+
+/// Strategy producing any value of the type, biased towards edge cases.
+///
+/// This is the strategy used by the `proptest::arbitrary::Arbitrary` impl.
+pub struct Any<T>(PhantomData<T>);
+
+impl<T> Any<T> {
+	/// Create a new strategy.
+	pub const fn new() -> Self {
+		Any(PhantomData)
+	}
+}
+
+impl<T> Clone for Any<T> {
+	fn clone(&self) -> Self {
+		Any::new()
+	}
+}
+
+impl<T> Copy for Any<T> {}
+
+impl<T> Default for Any<T> {
+	fn default() -> Self {
+		Any::new()
+	}
+}
+
+impl<T> fmt::Debug for Any<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("Any")
+	}
+}
+
+impl<T: Uint> Strategy for Any<T> {
+	type Tree = BinarySearch<T>;
+	type Value = T;
+
+	fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+		let value = interesting_value(runner.rng());
+		Ok(BinarySearch::new(T::default(), value))
+	}
+}
+
+/// Strategy producing values uniformly distributed in a half-open range.
+///
+/// Values shrink towards the start of the range.
+#[derive(Clone, Copy, Debug)]
+pub struct UniformRange<T> {
+	start: T,
+	end: T,
+}
+
+/// Create a strategy producing values uniformly distributed in `range`.
+///
+/// # Panics
+///
+/// Panics if the range is empty.
+pub fn uniform_range<T: Uint>(range: Range<T>) -> UniformRange<T> {
+	assert!(range.start < range.end, "uniform_range: empty range");
+	UniformRange { start: range.start, end: range.end }
+}
+
+impl<T: Uint> Strategy for UniformRange<T> {
+	type Tree = BinarySearch<T>;
+	type Value = T;
+
+	fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+		let width = self.end - self.start;
+		let value = self.start + below(runner.rng(), width);
+		Ok(BinarySearch::new(self.start, value))
+	}
+}
+
+/// Value tree shrinking by binary search between a lower bound and the generated value.
+#[derive(Clone, Copy, Debug)]
+pub struct BinarySearch<T> {
+	lo: T,
+	curr: T,
+	hi: T,
+}
+
+impl<T: Uint> BinarySearch<T> {
+	fn new(lo: T, value: T) -> Self {
+		BinarySearch { lo, curr: value, hi: value }
+	}
+
+	fn reposition(&mut self) -> bool {
+		let mid = self.lo + ((self.hi - self.lo) >> 1);
+		if mid == self.curr {
+			false
+		} else {
+			self.curr = mid;
+			true
+		}
+	}
+}
+
+impl<T: Uint> ValueTree for BinarySearch<T> {
+	type Value = T;
+
+	fn current(&self) -> T {
+		self.curr
+	}
+
+	fn simplify(&mut self) -> bool {
+		if self.hi <= self.lo {
+			return false;
+		}
+		self.hi = self.curr;
+		self.reposition()
+	}
+
+	fn complicate(&mut self) -> bool {
+		if self.hi <= self.lo {
+			return false;
+		}
+		self.lo = self.curr + T::from(1u64);
+		self.reposition()
+	}
+}
+
+/// Uniformly random value using up to `bytes` low bytes.
+fn random_bytes<T: Uint>(rng: &mut TestRng, bytes: usize) -> T {
+	let mut buf = vec![0u8; bytes];
+	rng.fill_bytes(&mut buf);
+	T::from(&buf[..])
+}
+
+/// Uniformly random value in `0..bound`.
+fn below<T: Uint>(rng: &mut TestRng, bound: T) -> T {
+	let bits = bound.bits();
+	let bytes = (bits + 7) / 8;
+	loop {
+		let value: T = random_bytes::<T>(rng, bytes) >> (bytes * 8 - bits);
+		if value < bound {
+			return value;
+		}
+	}
+}
+
+fn interesting_value<T: Uint>(rng: &mut TestRng) -> T {
+	let bits = T::BYTES * 8;
+	let one = T::from(1u64);
+	match rng.gen_range(0..10) {
+		0 => T::default(),
+		1 => one,
+		2 => !T::default(),
+		3 => !T::default() - T::from(rng.gen_range(1..4u64)),
+		4 => one << rng.gen_range(0..bits),
+		5 => {
+			// Around the word boundaries where carries propagate.
+			let boundary = if bits > 128 && rng.gen() { 128 } else { 64 };
+			let delta = T::from(rng.gen_range(0..3u64));
+			if boundary < bits {
+				(one << boundary) - one + delta
+			} else {
+				!T::default() - delta
+			}
+		}
+		// Random value of random length, so that small numbers are frequent.
+		6 | 7 => {
+			let len = rng.gen_range(0..=T::BYTES);
+			random_bytes(rng, len)
+		}
+		_ => random_bytes(rng, T::BYTES),
+	}
+}
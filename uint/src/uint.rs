@@ -109,6 +109,9 @@ impl From<FromDecStrErr> for FromStrRadixErr {
 		let kind = match e {
 			FromDecStrErr::InvalidCharacter => FromStrRadixErrKind::InvalidCharacter,
 			FromDecStrErr::InvalidLength => FromStrRadixErrKind::InvalidLength,
+			FromDecStrErr::Empty => FromStrRadixErrKind::InvalidLength,
+			FromDecStrErr::Whitespace => FromStrRadixErrKind::InvalidCharacter,
+			FromDecStrErr::LeadingZero => FromStrRadixErrKind::InvalidCharacter,
 		};
 
 		Self { kind, source: Some(FromStrRadixErrSrc::Dec(e)) }
@@ -134,6 +137,12 @@ pub enum FromDecStrErr {
 	InvalidCharacter,
 	/// Value does not fit into type
 	InvalidLength,
+	/// The string is empty
+	Empty,
+	/// The string starts or ends with whitespace
+	Whitespace,
+	/// The number has a leading zero
+	LeadingZero,
 }
 
 impl fmt::Display for FromDecStrErr {
@@ -144,6 +153,9 @@ impl fmt::Display for FromDecStrErr {
 			match self {
 				FromDecStrErr::InvalidCharacter => "a character is not in the range 0-9",
 				FromDecStrErr::InvalidLength => "the number is too large for the type",
+				FromDecStrErr::Empty => "the string is empty",
+				FromDecStrErr::Whitespace => "the string starts or ends with whitespace",
+				FromDecStrErr::LeadingZero => "the number has a leading zero",
 			}
 		)
 	}
@@ -317,12 +329,109 @@ macro_rules! uint_full_mul_reg {
 	}};
 }
 
+/// Full multiplication in product-scanning (Comba) form, fully unrolled.
+///
+/// Each output word is computed at once from all the partial products of its
+/// column, which gives better instruction-level parallelism than the
+/// row-by-row form of `uint_full_mul_reg`. Only defined for 4 and 8 words.
 #[macro_export]
 #[doc(hidden)]
-macro_rules! uint_overflowing_mul {
-	($name:ident, $n_words: tt, $self_expr: expr, $other: expr) => {{
-		let ret: [u64; $n_words * 2] =
-			$crate::uint_full_mul_reg!($name, $n_words, $self_expr, $other);
+macro_rules! uint_full_mul_comba {
+	($name:ident, 4, $self_expr:expr, $other:expr) => {
+		$crate::uint_full_mul_comba!(@scan $name, 4, 7, $self_expr, $other)
+	};
+	($name:ident, 8, $self_expr:expr, $other:expr) => {
+		$crate::uint_full_mul_comba!(@scan $name, 8, 15, $self_expr, $other)
+	};
+	(@scan $name:ident, $n_words:tt, $n_columns:tt, $self_expr:expr, $other:expr) => {{
+		let $name(ref me) = $self_expr;
+		let $name(ref you) = $other;
+		let mut ret = [0u64; $n_words * 2];
+
+		// 192-bit column accumulator.
+		let mut acc = 0u128;
+		let mut acc_hi = 0u64;
+
+		use $crate::unroll;
+		unroll! {
+			for k in 0..$n_columns {
+				unroll! {
+					for i in 0..$n_words {
+						let j = k.wrapping_sub(i);
+						if j < $n_words {
+							let (sum, overflow) = acc.overflowing_add(me[i] as u128 * you[j] as u128);
+							acc = sum;
+							acc_hi += overflow as u64;
+						}
+					}
+				}
+				ret[k] = acc as u64;
+				acc = (acc >> 64) | ((acc_hi as u128) << 64);
+				acc_hi = 0;
+			}
+		}
+		ret[$n_words * 2 - 1] = acc as u64;
+
+		ret
+	}};
+}
+
+/// Full squaring, computing each cross product only once.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! uint_full_square {
+	($name:ident, $n_words:tt, $self_expr:expr) => {{
+		let $name(ref me) = $self_expr;
+		let mut ret = [0u64; $n_words * 2];
+
+		use $crate::unroll;
+		// Cross products `me[i] * me[j]` for `i < j`.
+		unroll! {
+			for i in 0..$n_words {
+				let mut carry = 0u64;
+				unroll! {
+					for j in 0..$n_words {
+						if j > i {
+							let t = me[i] as u128 * me[j] as u128 + ret[i + j] as u128 + carry as u128;
+							ret[i + j] = t as u64;
+							carry = (t >> 64) as u64;
+						}
+					}
+				}
+				ret[i + $n_words] = carry;
+			}
+		}
+
+		// Double them, the top bit is always clear since the result fits.
+		for k in (1..$n_words * 2).rev() {
+			ret[k] = (ret[k] << 1) | (ret[k - 1] >> 63);
+		}
+		ret[0] <<= 1;
+
+		// Add the squares `me[i] * me[i]` on the diagonal.
+		let mut carry = 0u64;
+		unroll! {
+			for i in 0..$n_words {
+				let square = me[i] as u128 * me[i] as u128;
+				let lo = ret[2 * i] as u128 + (square as u64) as u128 + carry as u128;
+				ret[2 * i] = lo as u64;
+				let hi = ret[2 * i + 1] as u128 + (square >> 64) + (lo >> 64);
+				ret[2 * i + 1] = hi as u64;
+				carry = (hi >> 64) as u64;
+			}
+		}
+		debug_assert_eq!(carry, 0);
+
+		ret
+	}};
+}
+
+/// Splits a full product into its low half and whether the high half is non-zero.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! uint_split_full {
+	($name:ident, $n_words:tt, $full:expr) => {{
+		let ret: [u64; $n_words * 2] = $full;
 
 		// The safety of this is enforced by the compiler
 		let ret: [[u64; $n_words]; 2] = unsafe { $crate::core_::mem::transmute(ret) };
@@ -346,6 +455,20 @@ macro_rules! uint_overflowing_mul {
 	}};
 }
 
+#[macro_export]
+#[doc(hidden)]
+macro_rules! uint_overflowing_mul {
+	($name:ident, 4, $self_expr: expr, $other: expr) => {
+		$crate::uint_split_full!($name, 4, $crate::uint_full_mul_comba!($name, 4, $self_expr, $other))
+	};
+	($name:ident, 8, $self_expr: expr, $other: expr) => {
+		$crate::uint_split_full!($name, 8, $crate::uint_full_mul_comba!($name, 8, $self_expr, $other))
+	};
+	($name:ident, $n_words: tt, $self_expr: expr, $other: expr) => {
+		$crate::uint_split_full!($name, $n_words, $crate::uint_full_mul_reg!($name, $n_words, $self_expr, $other))
+	};
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! overflowing {
@@ -435,8 +558,8 @@ macro_rules! impl_mul_for_primitive {
 			type Output = $name;
 
 			fn mul(self, other: $other) -> $name {
-				let (result, carry) = self.overflowing_mul_u64(other as u64);
-				$crate::panic_on_overflow!(carry > 0);
+				let (result, overflow) = self.overflowing_mul_word(other as u64);
+				$crate::panic_on_overflow!(overflow);
 				result
 			}
 		}
@@ -445,8 +568,8 @@ macro_rules! impl_mul_for_primitive {
 			type Output = $name;
 
 			fn mul(self, other: &'a $other) -> $name {
-				let (result, carry) = self.overflowing_mul_u64(*other as u64);
-				$crate::panic_on_overflow!(carry > 0);
+				let (result, overflow) = self.overflowing_mul_word(*other as u64);
+				$crate::panic_on_overflow!(overflow);
 				result
 			}
 		}
@@ -455,8 +578,8 @@ macro_rules! impl_mul_for_primitive {
 			type Output = $name;
 
 			fn mul(self, other: &'a $other) -> $name {
-				let (result, carry) = self.overflowing_mul_u64(*other as u64);
-				$crate::panic_on_overflow!(carry > 0);
+				let (result, overflow) = self.overflowing_mul_word(*other as u64);
+				$crate::panic_on_overflow!(overflow);
 				result
 			}
 		}
@@ -465,8 +588,8 @@ macro_rules! impl_mul_for_primitive {
 			type Output = $name;
 
 			fn mul(self, other: $other) -> $name {
-				let (result, carry) = self.overflowing_mul_u64(other as u64);
-				$crate::panic_on_overflow!(carry > 0);
+				let (result, overflow) = self.overflowing_mul_word(other as u64);
+				$crate::panic_on_overflow!(overflow);
 				result
 			}
 		}
@@ -483,17 +606,38 @@ macro_rules! impl_mul_for_primitive {
 #[macro_export]
 macro_rules! construct_uint {
 	( $(#[$attr:meta])* $visibility:vis struct $name:ident (1); ) => {
-		$crate::construct_uint!{ @construct $(#[$attr])* $visibility struct $name (1); }
+		$crate::construct_uint!{ @construct $(#[$attr])* $visibility struct $name (1, (64)); }
+		$crate::impl_bytemuck_for_uint!($name, pod);
+	};
+
+	( $(#[$attr:meta])* $visibility:vis struct $name:ident (1, $n_bits:tt); ) => {
+		$crate::construct_uint!{ @construct $(#[$attr])* $visibility struct $name (1, $n_bits); }
+		$crate::impl_bytemuck_for_uint!($name, zeroable);
 	};
 
+	// Types declared with an explicit width may have bits above it, so that only types of whole
+	// words are `Pod`: any bit pattern of those is a valid value.
 	( $(#[$attr:meta])* $visibility:vis struct $name:ident ( $n_words:tt ); ) => {
-			$crate::construct_uint! { @construct $(#[$attr])* $visibility struct $name ($n_words); }
+		$crate::construct_uint!{ @words pod $(#[$attr])* $visibility struct $name ($n_words, ($n_words * 64)); }
+	};
+
+	( $(#[$attr:meta])* $visibility:vis struct $name:ident ( $n_words:tt, $n_bits:tt ); ) => {
+		$crate::construct_uint!{ @words zeroable $(#[$attr])* $visibility struct $name ($n_words, $n_bits); }
+	};
+
+	( @words $bytemuck:ident $(#[$attr:meta])* $visibility:vis struct $name:ident ( $n_words:tt, $n_bits:tt ); ) => {
+			$crate::construct_uint! { @construct $(#[$attr])* $visibility struct $name ($n_words, $n_bits); }
+			$crate::impl_bytemuck_for_uint!($name, $bytemuck);
 
 			impl $crate::core_::convert::From<u128> for $name {
 				fn from(value: u128) -> $name {
 					let mut ret = [0; $n_words];
 					ret[0] = value as u64;
 					ret[1] = (value >> 64) as u64;
+					// Only a two word number can be narrower than 128 bits.
+					if $n_words == 2 && ret[1] & !Self::TOP_MASK != 0 {
+						panic!(concat!("Integer overflow when casting to ", stringify!($name)));
+					}
 					$name(ret)
 				}
 			}
@@ -563,13 +707,17 @@ macro_rules! construct_uint {
 				}
 			}
 	};
-	( @construct $(#[$attr:meta])* $visibility:vis struct $name:ident ( $n_words:tt ); ) => {
+	( @construct $(#[$attr:meta])* $visibility:vis struct $name:ident ( $n_words:tt, $n_bits:tt ); ) => {
 		/// Little-endian large integer type
 		#[repr(C)]
 		$(#[$attr])*
 		#[derive(Copy, Clone, Eq, PartialEq, Hash)]
 		$visibility struct $name (pub [u64; $n_words]);
 
+		$crate::static_assertions::const_assert!(
+			$n_bits % 8 == 0 && $n_bits > ($n_words - 1) * 64 && $n_bits <= $n_words * 64
+		);
+
 		/// Get a reference to the underlying little-endian words.
 		impl AsRef<[u64]> for $name {
 			#[inline]
@@ -586,8 +734,20 @@ macro_rules! construct_uint {
 
 		impl $name {
 			const WORD_BITS: usize = 64;
+			// Bits of the most significant word which are part of the number.
+			const TOP_MASK: u64 = u64::max_value() >> ($n_words * 64 - $n_bits);
+
+			/// Width of the number in bits.
+			pub const BITS: u32 = $n_bits;
+			/// Width of the number in bytes, as used by the byte conversions.
+			pub const BYTES: usize = $n_bits / 8;
+
 			/// Maximum value.
-			pub const MAX: $name = $name([u64::max_value(); $n_words]);
+			pub const MAX: $name = {
+				let mut words = [u64::max_value(); $n_words];
+				words[$n_words - 1] = Self::TOP_MASK;
+				$name(words)
+			};
 
 			/// Converts a string slice in a given base to an integer. Only supports radixes of 10
 			/// and 16.
@@ -602,14 +762,41 @@ macro_rules! construct_uint {
 			}
 
 			/// Convert from a decimal string.
+			///
+			/// Only the canonical form is accepted: the string must consist of digits only,
+			/// without surrounding whitespace or leading zeros, apart from `"0"` itself.
+			/// See `from_dec_str_lenient` for tolerant parsing.
 			pub fn from_dec_str(value: &str) -> $crate::core_::result::Result<Self, $crate::FromDecStrErr> {
+				if value.is_empty() {
+					return Err($crate::FromDecStrErr::Empty);
+				}
+				if value.trim().len() != value.len() {
+					return Err($crate::FromDecStrErr::Whitespace);
+				}
+				let res = Self::from_dec_digits(value)?;
+				if value.len() > 1 && value.starts_with('0') {
+					return Err($crate::FromDecStrErr::LeadingZero);
+				}
+				Ok(res)
+			}
+
+			/// Convert from a decimal string, ignoring surrounding whitespace and leading zeros.
+			pub fn from_dec_str_lenient(value: &str) -> $crate::core_::result::Result<Self, $crate::FromDecStrErr> {
+				let value = value.trim();
+				if value.is_empty() {
+					return Err($crate::FromDecStrErr::Empty);
+				}
+				Self::from_dec_digits(value)
+			}
+
+			fn from_dec_digits(value: &str) -> $crate::core_::result::Result<Self, $crate::FromDecStrErr> {
 				let mut res = Self::default();
 				for b in value.bytes().map(|b| b.wrapping_sub(b'0')) {
 					if b > 9 {
 						return Err($crate::FromDecStrErr::InvalidCharacter)
 					}
-					let (r, overflow) = res.overflowing_mul_u64(10);
-					if overflow > 0 {
+					let (r, overflow) = res.overflowing_mul_word(10);
+					if overflow {
 						return Err($crate::FromDecStrErr::InvalidLength);
 					}
 					let (r, overflow) = r.overflowing_add(b.into());
@@ -717,13 +904,14 @@ macro_rules! construct_uint {
 
 			/// Returns the number of leading zeros in the binary representation of self.
 			pub fn leading_zeros(&self) -> u32 {
-				let mut r = 0;
+				// Start below zero to discount the unused bits of the top word.
+				let mut r = Self::BITS.wrapping_sub($n_words * 64);
 				for i in 0..$n_words {
 					let w = self.0[$n_words - i - 1];
 					if w == 0 {
-						r += 64;
+						r = r.wrapping_add(64);
 					} else {
-						r += w.leading_zeros();
+						r = r.wrapping_add(w.leading_zeros());
 						break;
 					}
 				}
@@ -742,40 +930,188 @@ macro_rules! construct_uint {
 						break;
 					}
 				}
-				r
+				r.min(Self::BITS)
 			}
 
-			/// Return specific byte.
+			/// Reverses the order of the bits, so that bit 0 becomes bit `BITS - 1` and vice versa.
+			#[inline]
+			pub fn reverse_bits(self) -> Self {
+				let mut ret = [0u64; $n_words];
+				for i in 0..$n_words {
+					ret[$n_words - i - 1] = self.0[i].reverse_bits();
+				}
+				// The unused bits of the top word end up at the bottom.
+				$name(ret) >> (($n_words * 64 - $n_bits) as usize)
+			}
+
+			/// Return specific byte, counting from the least significant one.
+			///
+			/// Index `0` is the least significant byte. Use `byte_be` to count from the most
+			/// significant one instead.
 			///
 			/// # Panics
 			///
 			/// Panics if `index` exceeds the byte width of the number.
 			#[inline]
 			pub const fn byte(&self, index: usize) -> u8 {
+				assert!(index < Self::BYTES, "byte index out of range");
 				let &$name(ref arr) = self;
 				(arr[index / 8] >> (((index % 8)) * 8)) as u8
 			}
 
+			/// Set specific byte, using the same little-endian indexing as `byte`.
+			///
+			/// Index `0` is the least significant byte. Use `set_byte_be` to count from the most
+			/// significant one instead.
+			///
+			/// # Panics
+			///
+			/// Panics if `index` exceeds the byte width of the number.
+			#[inline]
+			pub fn set_byte(&mut self, index: usize, value: u8) {
+				assert!(index < Self::BYTES, "byte index out of range");
+				let &mut $name(ref mut arr) = self;
+				let shift = (index % 8) * 8;
+				arr[index / 8] = (arr[index / 8] & !(0xff << shift)) | ((value as u64) << shift);
+			}
+
+			/// Return specific byte, counting from the most significant one as the EVM `BYTE`
+			/// opcode does.
+			///
+			/// # Panics
+			///
+			/// Panics if `index` exceeds the byte width of the number.
+			#[inline]
+			pub const fn byte_be(&self, index: usize) -> u8 {
+				assert!(index < Self::BYTES, "byte index out of range");
+				self.byte(Self::BYTES - 1 - index)
+			}
+
+			/// Set specific byte, using the same big-endian indexing as `byte_be`.
+			///
+			/// # Panics
+			///
+			/// Panics if `index` exceeds the byte width of the number.
+			#[inline]
+			pub fn set_byte_be(&mut self, index: usize, value: u8) {
+				assert!(index < Self::BYTES, "byte index out of range");
+				self.set_byte(Self::BYTES - 1 - index, value)
+			}
+
 			/// Write to the slice in big-endian format.
+			///
+			/// The slice must be `Self::BYTES` long.
 			#[inline]
 			pub fn to_big_endian(&self, bytes: &mut [u8]) {
 				use $crate::byteorder::{ByteOrder, BigEndian};
-				debug_assert!($n_words * 8 == bytes.len());
-				for i in 0..$n_words {
-					BigEndian::write_u64(&mut bytes[8 * i..], self.0[$n_words - i - 1]);
+				debug_assert!(Self::BYTES == bytes.len());
+				if Self::BYTES == $n_words * 8 {
+					for i in 0..$n_words {
+						BigEndian::write_u64(&mut bytes[8 * i..], self.0[$n_words - i - 1]);
+					}
+				} else {
+					let mut padded = [0u8; $n_words * 8];
+					for i in 0..$n_words {
+						BigEndian::write_u64(&mut padded[8 * i..], self.0[$n_words - i - 1]);
+					}
+					bytes.copy_from_slice(&padded[$n_words * 8 - Self::BYTES..]);
 				}
 			}
 
 			/// Write to the slice in little-endian format.
+			///
+			/// The slice must be `Self::BYTES` long.
 			#[inline]
 			pub fn to_little_endian(&self, bytes: &mut [u8]) {
 				use $crate::byteorder::{ByteOrder, LittleEndian};
-				debug_assert!($n_words * 8 == bytes.len());
-				for i in 0..$n_words {
-					LittleEndian::write_u64(&mut bytes[8 * i..], self.0[i]);
+				debug_assert!(Self::BYTES == bytes.len());
+				if Self::BYTES == $n_words * 8 {
+					for i in 0..$n_words {
+						LittleEndian::write_u64(&mut bytes[8 * i..], self.0[i]);
+					}
+				} else {
+					let mut padded = [0u8; $n_words * 8];
+					for i in 0..$n_words {
+						LittleEndian::write_u64(&mut padded[8 * i..], self.0[i]);
+					}
+					bytes.copy_from_slice(&padded[..Self::BYTES]);
+				}
+			}
+
+			/// Return the memory representation of the number as a byte array in big-endian byte order.
+			#[inline]
+			pub fn to_be_bytes(&self) -> [u8; $n_bits / 8] {
+				let mut bytes = [0u8; $n_bits / 8];
+				self.to_big_endian(&mut bytes);
+				bytes
+			}
+
+			/// Return the memory representation of the number as a byte array in little-endian byte order.
+			#[inline]
+			pub fn to_le_bytes(&self) -> [u8; $n_bits / 8] {
+				let mut bytes = [0u8; $n_bits / 8];
+				self.to_little_endian(&mut bytes);
+				bytes
+			}
+
+			/// Return the memory representation of the number as a byte array in native byte order.
+			#[inline]
+			pub fn to_ne_bytes(&self) -> [u8; $n_bits / 8] {
+				if cfg!(target_endian = "big") {
+					self.to_be_bytes()
+				} else {
+					self.to_le_bytes()
+				}
+			}
+
+			/// Create the number from its representation as a byte array in big-endian byte order.
+			#[inline]
+			pub fn from_be_bytes(bytes: [u8; $n_bits / 8]) -> Self {
+				Self::from_big_endian(&bytes)
+			}
+
+			/// Create the number from its representation as a byte array in little-endian byte order.
+			#[inline]
+			pub fn from_le_bytes(bytes: [u8; $n_bits / 8]) -> Self {
+				Self::from_little_endian(&bytes)
+			}
+
+			/// Create the number from its representation as a byte array in native byte order.
+			#[inline]
+			pub fn from_ne_bytes(bytes: [u8; $n_bits / 8]) -> Self {
+				if cfg!(target_endian = "big") {
+					Self::from_be_bytes(bytes)
+				} else {
+					Self::from_le_bytes(bytes)
 				}
 			}
 
+			/// View the number as its 64-bit limbs, least significant first.
+			#[inline]
+			pub const fn as_limbs(&self) -> &[u64; $n_words] {
+				&self.0
+			}
+
+			/// Mutable view of the 64-bit limbs, least significant first.
+			///
+			/// Bits above `Self::BITS` in the last limb must be left clear.
+			#[inline]
+			pub fn as_limbs_mut(&mut self) -> &mut [u64; $n_words] {
+				&mut self.0
+			}
+
+			/// Create the number from its 64-bit limbs, least significant first.
+			///
+			/// # Panics
+			///
+			/// Panics if any bit above `Self::BITS` is set.
+			#[inline]
+			pub fn from_limbs(limbs: [u64; $n_words]) -> Self {
+				let (value, overflow) = $name(limbs).truncate();
+				assert!(!overflow, "limbs exceed the width of the number");
+				value
+			}
+
 
 			/// Create `10**n` as this type.
 			///
@@ -805,36 +1141,36 @@ macro_rules! construct_uint {
 			/// The maximum value which can be inhabited by this type.
 			#[inline]
 			pub fn max_value() -> Self {
-				let mut result = [0; $n_words];
-				for i in 0..$n_words {
-					result[i] = u64::max_value();
-				}
-				$name(result)
+				Self::MAX
 			}
 
-			fn full_shl(self, shift: u32) -> [u64; $n_words + 1] {
-				debug_assert!(shift < Self::WORD_BITS as u32);
-				let mut u = [0u64; $n_words + 1];
-				let u_lo = self.0[0] << shift;
-				let u_hi = self >> (Self::WORD_BITS as u32 - shift);
-				u[0] = u_lo;
-				u[1..].copy_from_slice(&u_hi.0[..]);
-				u
+			/// Clears the bits above the width of the number,
+			/// returning whether any of them were set.
+			#[inline(always)]
+			fn truncate(mut self) -> (Self, bool) {
+				let overflow = self.0[$n_words - 1] & !Self::TOP_MASK != 0;
+				self.0[$n_words - 1] &= Self::TOP_MASK;
+				(self, overflow)
 			}
 
-			fn full_shr(u: [u64; $n_words + 1], shift: u32) -> Self {
-				debug_assert!(shift < Self::WORD_BITS as u32);
-				let mut res = Self::zero();
-				for i in 0..$n_words {
-					res.0[i] = u[i] >> shift;
+			// Shift left without truncating to the width of the number.
+			fn shl_words(self, shift: usize) -> Self {
+				let $name(ref original) = self;
+				let mut ret = [0u64; $n_words];
+				let word_shift = shift / 64;
+				let bit_shift = shift % 64;
+
+				// shift
+				for i in word_shift..$n_words {
+					ret[i] = original[i - word_shift] << bit_shift;
 				}
 				// carry
-				if shift > 0 {
-					for i in 1..=$n_words {
-						res.0[i - 1] |= u[i] << (Self::WORD_BITS as u32 - shift);
+				if bit_shift > 0 {
+					for i in word_shift+1..$n_words {
+						ret[i] += original[i - 1 - word_shift] >> (64 - bit_shift);
 					}
 				}
-				res
+				$name(ret)
 			}
 
 			fn full_mul_u64(self, by: u64) -> [u64; $n_words + 1] {
@@ -855,21 +1191,43 @@ macro_rules! construct_uint {
 				(self, rem.into())
 			}
 
-			// See Knuth, TAOCP, Volume 2, section 4.3.1, Algorithm D.
-			fn div_mod_knuth(self, mut v: Self, n: usize, m: usize) -> (Self, Self) {
+			fn div_mod_knuth(self, v: Self, n: usize, m: usize) -> (Self, Self) {
 				debug_assert!(self.bits() >= v.bits() && !v.fits_word());
 				debug_assert!(n + m <= $n_words);
+				let mut u = [0u64; $n_words + 1];
+				let mut q = Self::zero();
+				let remainder = Self::knuth_div_words(&self.0[..n + m], v, n, &mut u[..n + m + 1], |j, q_j| q.0[j] = q_j);
+				(q, remainder)
+			}
+
+			// See Knuth, TAOCP, Volume 2, section 4.3.1, Algorithm D.
+			//
+			// Divides the words of `numerator` by `v`, which has `n > 1` words, passing each
+			// digit of the quotient to `quotient` and returning the remainder. `u` is scratch
+			// space one word longer than `numerator`.
+			fn knuth_div_words(
+				numerator: &[u64],
+				mut v: Self,
+				n: usize,
+				u: &mut [u64],
+				mut quotient: impl FnMut(usize, u64),
+			) -> Self {
+				let len = numerator.len();
+				debug_assert!(n > 1 && len >= n && u.len() == len + 1);
 				// D1.
 				// Make sure 64th bit in v's highest word is set.
-				// If we shift both self and v, it won't affect the quotient
+				// If we shift both the numerator and v, it won't affect the quotient
 				// and the remainder will only need to be shifted back.
 				let shift = v.0[n - 1].leading_zeros();
-				v <<= shift;
+				v = v.shl_words(shift as usize);
 				// u will store the remainder (shifted)
-				let mut u = self.full_shl(shift);
+				u[0] = numerator[0] << shift;
+				for i in 1..len {
+					u[i] = (numerator[i] << shift) | numerator[i - 1].checked_shr(64 - shift).unwrap_or(0);
+				}
+				u[len] = numerator[len - 1].checked_shr(64 - shift).unwrap_or(0);
 
-				// quotient
-				let mut q = Self::zero();
+				let m = len - n;
 				let v_n_1 = v.0[n - 1];
 				let v_n_2 = v.0[n - 2];
 
@@ -929,13 +1287,15 @@ macro_rules! construct_uint {
 					}
 
 					// D5.
-					q.0[j] = q_hat;
+					quotient(j, q_hat);
 				}
 
 				// D8.
-				let remainder = Self::full_shr(u, shift);
-
-				(q, remainder)
+				let mut remainder = Self::zero();
+				for i in 0..n {
+					remainder.0[i] = (u[i] >> shift) | u[i + 1].checked_shl(64 - shift).unwrap_or(0);
+				}
+				remainder
 			}
 
 			// Returns the least number of words needed to represent the nonzero number
@@ -975,6 +1335,50 @@ macro_rules! construct_uint {
 				self.div_mod_knuth(other, n, m)
 			}
 
+			/// Returns `(self + other) % modulus`, computed without overflow, or zero if `modulus`
+			/// is zero, like the EVM `ADDMOD` opcode.
+			pub fn add_mod(self, other: Self, modulus: Self) -> Self {
+				if modulus.is_zero() {
+					return Self::zero();
+				}
+				(self % modulus).add_mod_reduced(other % modulus, modulus)
+			}
+
+			/// Returns `(self * other) % modulus`, computed without overflow, or zero if `modulus`
+			/// is zero, like the EVM `MULMOD` opcode.
+			pub fn mul_mod(self, other: Self, modulus: Self) -> Self {
+				if modulus.is_zero() {
+					return Self::zero();
+				}
+				let a = self % modulus;
+				let b = other % modulus;
+				let product = $crate::uint_full_mul_reg!($name, $n_words, a, b);
+				Self::rem_wide(product, modulus)
+			}
+
+			// Returns `product % modulus` for a product twice as wide as `Self`, using the same
+			// long division as `div_mod_knuth`.
+			fn rem_wide(product: [u64; $n_words * 2], modulus: Self) -> Self {
+				if modulus.fits_word() {
+					let divisor = modulus.low_u64();
+					let rem = product.iter().rev().fold(0u64, |rem, &d| Self::div_mod_word(rem, d, divisor).1);
+					return rem.into();
+				}
+
+				let mut u = [0u64; $n_words * 2 + 1];
+				Self::knuth_div_words(&product, modulus, Self::words(modulus.bits()), &mut u, |_, _| {})
+			}
+
+			// Returns `(self + other) % modulus` for `self < modulus` and `other <= modulus`.
+			fn add_mod_reduced(self, other: Self, modulus: Self) -> Self {
+				let (sum, overflow) = self.overflowing_add(other);
+				if overflow || sum >= modulus {
+					sum.overflowing_sub(modulus).0
+				} else {
+					sum
+				}
+			}
+
 			/// Compute the highest `n` such that `n * n <= self`.
 			pub fn integer_sqrt(&self) -> Self {
 				let one = Self::one();
@@ -997,6 +1401,44 @@ macro_rules! construct_uint {
 				}
 			}
 
+			/// Greatest common divisor, using Stein's binary algorithm.
+			///
+			/// `gcd(0, x)` and `gcd(x, 0)` are `x`.
+			pub fn gcd(self, other: Self) -> Self {
+				let (mut a, mut b) = (self, other);
+				if a.is_zero() {
+					return b;
+				}
+				if b.is_zero() {
+					return a;
+				}
+
+				// Common factors of two.
+				let shift = (a | b).trailing_zeros();
+				a >>= a.trailing_zeros();
+				loop {
+					// `a` is odd here, so factors of two in `b` can be dropped.
+					b >>= b.trailing_zeros();
+					if a > b {
+						$crate::core_::mem::swap(&mut a, &mut b);
+					}
+					b = b - a;
+					if b.is_zero() {
+						return a << shift;
+					}
+				}
+			}
+
+			/// Least common multiple. Returns `None` if it overflows the type.
+			///
+			/// The least common multiple of zero and any number is zero.
+			pub fn checked_lcm(self, other: Self) -> Option<Self> {
+				if self.is_zero() || other.is_zero() {
+					return Some(Self::zero());
+				}
+				(self / self.gcd(other)).checked_mul(other)
+			}
+
 			/// Fast exponentiation by squaring
 			/// https://en.wikipedia.org/wiki/Exponentiation_by_squaring
 			///
@@ -1015,11 +1457,11 @@ macro_rules! construct_uint {
 				let mut x = self;
 				while n > u_one {
 					if is_even(&n) {
-						x = x * x;
+						x = x.squared();
 						n = n >> 1usize;
 					} else {
 						y = x * y;
-						x = x * x;
+						x = x.squared();
 						// to reduce odd number by 1 we should just clear the last bit
 						n.0[$n_words-1] = n.0[$n_words-1] & ((!0u64)>>1);
 						n = n >> 1usize;
@@ -1042,11 +1484,11 @@ macro_rules! construct_uint {
 
 				while n > u_one {
 					if is_even(&n) {
-						x = $crate::overflowing!(x.overflowing_mul(x), overflow);
+						x = $crate::overflowing!(x.overflowing_squared(), overflow);
 						n = n >> 1usize;
 					} else {
 						y = $crate::overflowing!(x.overflowing_mul(y), overflow);
-						x = $crate::overflowing!(x.overflowing_mul(x), overflow);
+						x = $crate::overflowing!(x.overflowing_squared(), overflow);
 						n = (n - u_one) >> 1usize;
 					}
 				}
@@ -1065,13 +1507,15 @@ macro_rules! construct_uint {
 			/// Add with overflow.
 			#[inline(always)]
 			pub fn overflowing_add(self, other: $name) -> ($name, bool) {
-				$crate::uint_overflowing_binop!(
+				let (result, carry) = $crate::uint_overflowing_binop!(
 					$name,
 					$n_words,
 					self,
 					other,
 					u64::overflowing_add
-				)
+				);
+				let (result, truncated) = result.truncate();
+				(result, carry || truncated)
 			}
 
 			/// Addition which saturates at the maximum value (Self::max_value()).
@@ -1082,6 +1526,12 @@ macro_rules! construct_uint {
 				}
 			}
 
+			/// In-place addition which saturates at the maximum value (Self::max_value()).
+			#[inline]
+			pub fn saturating_add_assign(&mut self, other: $name) {
+				*self = self.saturating_add(other);
+			}
+
 			/// Checked addition. Returns `None` if overflow occurred.
 			pub fn checked_add(self, other: $name) -> Option<$name> {
 				match self.overflowing_add(other) {
@@ -1093,13 +1543,14 @@ macro_rules! construct_uint {
 			/// Subtraction which underflows and returns a flag if it does.
 			#[inline(always)]
 			pub fn overflowing_sub(self, other: $name) -> ($name, bool) {
-				$crate::uint_overflowing_binop!(
+				let (result, borrow) = $crate::uint_overflowing_binop!(
 					$name,
 					$n_words,
 					self,
 					other,
 					u64::overflowing_sub
-				)
+				);
+				(result.truncate().0, borrow)
 			}
 
 			/// Subtraction which saturates at zero.
@@ -1110,6 +1561,12 @@ macro_rules! construct_uint {
 				}
 			}
 
+			/// In-place subtraction which saturates at zero.
+			#[inline]
+			pub fn saturating_sub_assign(&mut self, other: $name) {
+				*self = self.saturating_sub(other);
+			}
+
 			/// Checked subtraction. Returns `None` if overflow occurred.
 			pub fn checked_sub(self, other: $name) -> Option<$name> {
 				match self.overflowing_sub(other) {
@@ -1121,7 +1578,34 @@ macro_rules! construct_uint {
 			/// Multiply with overflow, returning a flag if it does.
 			#[inline(always)]
 			pub fn overflowing_mul(self, other: $name) -> ($name, bool) {
-				$crate::uint_overflowing_mul!($name, $n_words, self, other)
+				let (result, overflow) = $crate::uint_overflowing_mul!($name, $n_words, self, other);
+				let (result, truncated) = result.truncate();
+				(result, overflow || truncated)
+			}
+
+			/// Square with overflow, returning a flag if it does.
+			///
+			/// Faster than `self.overflowing_mul(self)` as each cross product is only computed once.
+			#[inline(always)]
+			pub fn overflowing_squared(self) -> ($name, bool) {
+				let (result, overflow) =
+					$crate::uint_split_full!($name, $n_words, $crate::uint_full_square!($name, $n_words, self));
+				let (result, truncated) = result.truncate();
+				(result, overflow || truncated)
+			}
+
+			/// Square of the number.
+			///
+			/// Faster than `self * self` as each cross product is only computed once.
+			///
+			/// # Panics
+			///
+			/// Panics if the result overflows the type.
+			#[inline]
+			pub fn squared(self) -> $name {
+				let (result, overflow) = self.overflowing_squared();
+				$crate::panic_on_overflow!(overflow);
+				result
 			}
 
 			/// Multiplication which saturates at the maximum value..
@@ -1158,6 +1642,59 @@ macro_rules! construct_uint {
 				}
 			}
 
+			/// Euclidean division. For unsigned integers this is the same as `self / other`.
+			///
+			/// # Panics
+			///
+			/// Panics if `other` is zero.
+			#[inline]
+			pub fn div_euclid(self, other: $name) -> $name {
+				self / other
+			}
+
+			/// Least non-negative remainder of `self (mod other)`.
+			/// For unsigned integers this is the same as `self % other`.
+			///
+			/// # Panics
+			///
+			/// Panics if `other` is zero.
+			#[inline]
+			pub fn rem_euclid(self, other: $name) -> $name {
+				self % other
+			}
+
+			/// Checked Euclidean division. Returns `None` if `other == 0`.
+			#[inline]
+			pub fn checked_div_euclid(self, other: $name) -> Option<$name> {
+				self.checked_div(other)
+			}
+
+			/// Checked Euclidean modulus. Returns `None` if `other == 0`.
+			#[inline]
+			pub fn checked_rem_euclid(self, other: $name) -> Option<$name> {
+				self.checked_rem(other)
+			}
+
+			/// Euclidean division with overflow. The flag is always `false` for unsigned integers.
+			///
+			/// # Panics
+			///
+			/// Panics if `other` is zero.
+			#[inline]
+			pub fn overflowing_div_euclid(self, other: $name) -> ($name, bool) {
+				(self / other, false)
+			}
+
+			/// Wrapping Euclidean division. Never wraps for unsigned integers.
+			///
+			/// # Panics
+			///
+			/// Panics if `other` is zero.
+			#[inline]
+			pub fn wrapping_div_euclid(self, other: $name) -> $name {
+				self / other
+			}
+
 			/// Negation with overflow.
 			pub fn overflowing_neg(self) -> ($name, bool) {
 				if self.is_zero() {
@@ -1175,6 +1712,21 @@ macro_rules! construct_uint {
 				}
 			}
 
+			/// Two's complement negation, wrapping around at the boundary of the type.
+			///
+			/// This is `2^BITS - self`, and `0` for `0`.
+			#[inline]
+			pub fn wrapping_neg(self) -> $name {
+				(!self).overflowing_add($name::one()).0
+			}
+
+			/// Returns `true` if the number is negative when interpreted as a two's complement
+			/// signed integer, i.e. if its most significant bit is set.
+			#[inline]
+			pub const fn is_negative_as_signed(&self) -> bool {
+				self.bit(Self::BITS as usize - 1)
+			}
+
 			#[inline(always)]
 			fn div_mod_word(hi: u64, lo: u64, y: u64) -> (u64, u64) {
 				debug_assert!(hi < y);
@@ -1276,10 +1828,23 @@ macro_rules! construct_uint {
 				(self, carry)
 			}
 
+			/// Overflowing multiplication by u64, truncated to the width of the number.
+			/// Returns the result and whether it overflowed.
+			#[inline(always)]
+			fn overflowing_mul_word(self, other: u64) -> (Self, bool) {
+				let (result, carry) = self.overflowing_mul_u64(other);
+				let (result, truncated) = result.truncate();
+				(result, carry > 0 || truncated)
+			}
+
 			/// Converts from big endian representation bytes in memory.
+			///
+			/// # Panics
+			///
+			/// Panics if the slice is longer than `Self::BYTES`.
 			pub fn from_big_endian(slice: &[u8]) -> Self {
 				use $crate::byteorder::{ByteOrder, BigEndian};
-				assert!($n_words * 8 >= slice.len());
+				assert!(Self::BYTES >= slice.len());
 
 				let mut padded = [0u8; $n_words * 8];
 				padded[$n_words * 8 - slice.len() .. $n_words * 8].copy_from_slice(&slice);
@@ -1293,9 +1858,13 @@ macro_rules! construct_uint {
 			}
 
 			/// Converts from little endian representation bytes in memory.
+			///
+			/// # Panics
+			///
+			/// Panics if the slice is longer than `Self::BYTES`.
 			pub fn from_little_endian(slice: &[u8]) -> Self {
 				use $crate::byteorder::{ByteOrder, LittleEndian};
-				assert!($n_words * 8 >= slice.len());
+				assert!(Self::BYTES >= slice.len());
 
 				let mut padded = [0u8; $n_words * 8];
 				padded[0..slice.len()].copy_from_slice(&slice);
@@ -1309,22 +1878,22 @@ macro_rules! construct_uint {
 			}
 		}
 
-		impl $crate::core_::convert::From<$name> for [u8; $n_words * 8] {
+		impl $crate::core_::convert::From<$name> for [u8; $n_bits / 8] {
 			fn from(number: $name) -> Self {
-				let mut arr = [0u8; $n_words * 8];
+				let mut arr = [0u8; $n_bits / 8];
 				number.to_big_endian(&mut arr);
 				arr
 			}
 		}
 
-		impl $crate::core_::convert::From<[u8; $n_words * 8]> for $name {
-			fn from(bytes: [u8; $n_words * 8]) -> Self {
+		impl $crate::core_::convert::From<[u8; $n_bits / 8]> for $name {
+			fn from(bytes: [u8; $n_bits / 8]) -> Self {
 				Self::from(&bytes)
 			}
 		}
 
-		impl<'a> $crate::core_::convert::From<&'a [u8; $n_words * 8]> for $name {
-			fn from(bytes: &[u8; $n_words * 8]) -> Self {
+		impl<'a> $crate::core_::convert::From<&'a [u8; $n_bits / 8]> for $name {
+			fn from(bytes: &[u8; $n_bits / 8]) -> Self {
 				Self::from(&bytes[..])
 			}
 		}
@@ -1339,6 +1908,9 @@ macro_rules! construct_uint {
 			fn from(value: u64) -> $name {
 				let mut ret = [0; $n_words];
 				ret[0] = value;
+				if $n_words == 1 && value & !Self::TOP_MASK != 0 {
+					panic!(concat!("Integer overflow when casting to ", stringify!($name)));
+				}
 				$name(ret)
 			}
 		}
@@ -1550,7 +2122,7 @@ macro_rules! construct_uint {
 				for i in 0..$n_words {
 					ret[i] = !arr[i];
 				}
-				$name(ret)
+				$name(ret).truncate().0
 			}
 		}
 
@@ -1559,22 +2131,7 @@ macro_rules! construct_uint {
 
 			fn shl(self, shift: T) -> $name {
 				let shift = shift.into().as_usize();
-				let $name(ref original) = self;
-				let mut ret = [0u64; $n_words];
-				let word_shift = shift / 64;
-				let bit_shift = shift % 64;
-
-				// shift
-				for i in word_shift..$n_words {
-					ret[i] = original[i - word_shift] << bit_shift;
-				}
-				// carry
-				if bit_shift > 0 {
-					for i in word_shift+1..$n_words {
-						ret[i] += original[i - 1 - word_shift] >> (64 - bit_shift);
-					}
-				}
-				$name(ret)
+				self.shl_words(shift).truncate().0
 			}
 		}
 
@@ -1651,7 +2208,7 @@ macro_rules! construct_uint {
 		impl $crate::core_::fmt::Display for $name {
 			fn fmt(&self, f: &mut $crate::core_::fmt::Formatter) -> $crate::core_::fmt::Result {
 				if self.is_zero() {
-					return $crate::core_::write!(f, "0");
+					return f.pad_integral(true, "", "0");
 				}
 
 				let mut buf = [0_u8; $n_words*20];
@@ -1673,35 +2230,45 @@ macro_rules! construct_uint {
 				let s = unsafe {
 					$crate::core_::str::from_utf8_unchecked(&buf[i..])
 				};
-				f.write_str(s)
+				f.pad_integral(true, "", s)
 			}
 		}
 
-		impl $crate::core_::fmt::LowerHex for $name {
-			fn fmt(&self, f: &mut $crate::core_::fmt::Formatter) -> $crate::core_::fmt::Result {
+		impl $name {
+			/// Writes the hex digits without leading zeros, honoring the formatter's
+			/// width, fill and `#` flag like the primitive integer types do.
+			fn fmt_hex(&self, f: &mut $crate::core_::fmt::Formatter, digits: &[u8; 16]) -> $crate::core_::fmt::Result {
 				let &$name(ref data) = self;
-				if f.alternate() {
-					$crate::core_::write!(f, "0x")?;
-				}
-				// special case.
-				if self.is_zero() {
-					return $crate::core_::write!(f, "0");
+				let mut buf = [0_u8; $n_words * 16];
+				let mut i = buf.len();
+				for word in data.iter() {
+					let mut word = *word;
+					for _ in 0..16 {
+						i -= 1;
+						buf[i] = digits[(word & 15) as usize];
+						word >>= 4;
+					}
 				}
+				// Keep at least one digit for zero.
+				let start = buf[..buf.len() - 1].iter().position(|&c| c != b'0').unwrap_or(buf.len() - 1);
 
-				let mut latch = false;
-				for ch in data.iter().rev() {
-					for x in 0..16 {
-						let nibble = (ch & (15u64 << ((15 - x) * 4) as u64)) >> (((15 - x) * 4) as u64);
-						if !latch {
-							latch = nibble != 0;
-						}
+				// sequence of hex digits is guaranteed to be a valid UTF8 string
+				let s = unsafe {
+					$crate::core_::str::from_utf8_unchecked(&buf[start..])
+				};
+				f.pad_integral(true, "0x", s)
+			}
+		}
 
-						if latch {
-							$crate::core_::write!(f, "{:x}", nibble)?;
-						}
-					}
-				}
-				Ok(())
+		impl $crate::core_::fmt::LowerHex for $name {
+			fn fmt(&self, f: &mut $crate::core_::fmt::Formatter) -> $crate::core_::fmt::Result {
+				self.fmt_hex(f, b"0123456789abcdef")
+			}
+		}
+
+		impl $crate::core_::fmt::UpperHex for $name {
+			fn fmt(&self, f: &mut $crate::core_::fmt::Formatter) -> $crate::core_::fmt::Result {
+				self.fmt_hex(f, b"0123456789ABCDEF")
 			}
 		}
 
@@ -1710,7 +2277,7 @@ macro_rules! construct_uint {
 
 			fn from_str(value: &str) -> $crate::core_::result::Result<$name, Self::Err> {
 				let value = value.strip_prefix("0x").unwrap_or(value);
-				const BYTES_LEN: usize = $n_words * 8;
+				const BYTES_LEN: usize = $n_bits / 8;
 				const MAX_ENCODED_LEN: usize = BYTES_LEN * 2;
 
 				let mut bytes = [0_u8; BYTES_LEN];
@@ -1747,10 +2314,10 @@ macro_rules! construct_uint {
 			}
 		}
 
-		// `$n_words * 8` because macro expects bytes and
-		// uints use 64 bit (8 byte) words
-		$crate::impl_quickcheck_arbitrary_for_uint!($name, ($n_words * 8));
-		$crate::impl_arbitrary_for_uint!($name, ($n_words * 8));
+		// `$n_bits / 8` because macro expects bytes
+		$crate::impl_quickcheck_arbitrary_for_uint!($name, ($n_bits / 8));
+		$crate::impl_arbitrary_for_uint!($name, ($n_bits / 8));
+		$crate::impl_proptest_arbitrary_for_uint!($name, ($n_bits / 8));
 	}
 }
 
@@ -1807,6 +2374,11 @@ macro_rules! impl_arbitrary_for_uint {
 				u.fill_buffer(&mut res)?;
 				Ok(Self::from(res))
 			}
+
+			#[inline]
+			fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+				($n_bytes, Some($n_bytes))
+			}
 		}
 	};
 }
@@ -1817,3 +2389,57 @@ macro_rules! impl_arbitrary_for_uint {
 macro_rules! impl_arbitrary_for_uint {
 	($uint: ty, $n_bytes: tt) => {};
 }
+
+#[cfg(feature = "proptest")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_proptest_arbitrary_for_uint {
+	($uint: ty, $n_bytes: tt) => {
+		impl $crate::strategy::Uint for $uint {
+			const BYTES: usize = $n_bytes;
+
+			fn bits(&self) -> usize {
+				<$uint>::bits(self)
+			}
+		}
+
+		impl $crate::proptest::arbitrary::Arbitrary for $uint {
+			type Parameters = ();
+			type Strategy = $crate::strategy::Any<$uint>;
+
+			fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+				$crate::strategy::Any::new()
+			}
+		}
+	};
+}
+
+#[cfg(not(feature = "proptest"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_proptest_arbitrary_for_uint {
+	($uint: ty, $n_bytes: tt) => {};
+}
+
+#[cfg(feature = "bytemuck")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_bytemuck_for_uint {
+	($uint: ty, zeroable) => {
+		// SAFETY: the type is `repr(C)` around a `[u64; N]`, for which zero is a valid value.
+		unsafe impl $crate::bytemuck::Zeroable for $uint {}
+	};
+	($uint: ty, pod) => {
+		$crate::impl_bytemuck_for_uint!($uint, zeroable);
+		// SAFETY: the type is `repr(C)` around a `[u64; N]` without bits above `BITS`, so it
+		// has no padding and every bit pattern is a valid value.
+		unsafe impl $crate::bytemuck::Pod for $uint {}
+	};
+}
+
+#[cfg(not(feature = "bytemuck"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! impl_bytemuck_for_uint {
+	($uint: ty, $bytemuck: ident) => {};
+}
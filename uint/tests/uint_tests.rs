@@ -10,7 +10,10 @@ use core::convert::TryInto;
 use core::str::FromStr;
 use core::u64::MAX;
 use crunchy::unroll;
-use uint::{construct_uint, overflowing, FromDecStrErr};
+use uint::{
+	construct_uint, overflowing, uint_full_mul_comba, uint_full_mul_reg, uint_full_square, FromDecStrErr,
+	FromStrRadixErrKind,
+};
 
 construct_uint! {
 	pub struct U256(4);
@@ -20,6 +23,18 @@ construct_uint! {
 	pub struct U512(8);
 }
 
+construct_uint! {
+	pub struct U160(3, 160);
+}
+
+construct_uint! {
+	pub struct U96(2, 96);
+}
+
+construct_uint! {
+	pub struct U24(1, 24);
+}
+
 #[cfg(feature = "std")]
 #[test]
 fn hash_impl_is_the_same_as_for_a_slice() {
@@ -93,6 +108,166 @@ fn uint256_checked_ops() {
 	assert_eq!(z.checked_neg(), Some(z));
 }
 
+#[test]
+fn checked_div_rem_by_nonzero() {
+	let dividend = U256::MAX - 12345;
+	let divisor = U256::from(u128::max_value()) + 2;
+	let (quotient, remainder) = dividend.div_mod(divisor);
+	assert_eq!(dividend.checked_div(divisor), Some(quotient));
+	assert_eq!(dividend.checked_rem(divisor), Some(remainder));
+	assert_eq!(quotient * divisor + remainder, dividend);
+
+	assert_eq!(U256::from(17).checked_div(U256::from(5)), Some(U256::from(3)));
+	assert_eq!(U256::from(17).checked_rem(U256::from(5)), Some(U256::from(2)));
+	assert_eq!(U256::zero().checked_div(U256::zero()), None);
+	assert_eq!(U256::zero().checked_rem(U256::zero()), None);
+}
+
+#[test]
+fn add_mod_and_mul_mod() {
+	let max = U256::MAX;
+	let two = U256::from(2);
+
+	assert_eq!(max.add_mod(max, max), U256::zero());
+	assert_eq!(max.add_mod(U256::one(), max), U256::one());
+	assert_eq!(max.add_mod(max, max - 1), two);
+	assert_eq!(max.add_mod(max, U256::from(12)), U256::from(6));
+	assert_eq!(max.add_mod(two, U256::from(3)), two);
+	assert_eq!(U256::from(5).add_mod(U256::from(7), U256::zero()), U256::zero());
+
+	assert_eq!(max.mul_mod(max, max), U256::zero());
+	assert_eq!(max.mul_mod(max, max - 1), U256::one());
+	assert_eq!((max - 1).mul_mod(max - 1, max), U256::one());
+	assert_eq!(max.mul_mod(max, U256::from(12)), U256::from(9));
+	assert_eq!((U256::one() << 255).mul_mod(two, max), U256::one());
+	assert_eq!((U256::one() << 255).mul_mod(U256::from(3), max), (U256::one() << 255) + 1);
+	assert_eq!(max.mul_mod(max - 1, (U256::one() << 128) + 3), U256::from(56));
+	assert_eq!(
+		((U256::one() << 200) + 12345).mul_mod((U256::one() << 199) + 999, (U256::one() << 190) + 17),
+		U256::from(39010415)
+	);
+	assert_eq!(max.mul_mod(max, U256::one()), U256::zero());
+	assert_eq!(max.mul_mod(max, U256::zero()), U256::zero());
+
+	assert_eq!(U160::MAX.mul_mod(U160::MAX, U160::MAX - 2), U160::from(4));
+	assert_eq!(U160::MAX.add_mod(U160::MAX, U160::MAX - 2), U160::from(4));
+}
+
+#[test]
+fn add_mod_and_mul_mod_match_primitives() {
+	let values = [0u64, 1, 2, 3, 7, 1 << 32, u64::max_value() - 1, u64::max_value()];
+	for &a in values.iter() {
+		for &b in values.iter() {
+			for &m in values.iter().filter(|&&m| m != 0) {
+				let expected_add = (a as u128 + b as u128) % m as u128;
+				let expected_mul = (a as u128 * b as u128) % m as u128;
+				assert_eq!(U256::from(a).add_mod(U256::from(b), U256::from(m)), U256::from(expected_add));
+				assert_eq!(U256::from(a).mul_mod(U256::from(b), U256::from(m)), U256::from(expected_mul));
+			}
+		}
+	}
+
+	// Widths which are not a multiple of 64 bits overflow at `BITS`.
+	let values = [0u64, 1, 2, 3, 7, 1 << 12, (1 << 24) - 2, (1 << 24) - 1];
+	for &a in values.iter() {
+		for &b in values.iter() {
+			for &m in values.iter().filter(|&&m| m != 0) {
+				assert_eq!(U24::from(a).add_mod(U24::from(b), U24::from(m)), U24::from((a + b) % m));
+				assert_eq!(U24::from(a).mul_mod(U24::from(b), U24::from(m)), U24::from(a * b % m));
+			}
+		}
+	}
+}
+
+#[test]
+fn mul_mod_matches_wide_remainder() {
+	let widen = |x: U256| U512([x.0[0], x.0[1], x.0[2], x.0[3], 0, 0, 0, 0]);
+	let values = [
+		U256::one(),
+		U256::from(u64::max_value()),
+		(U256::one() << 64) + 1,
+		U256::from(u128::max_value()),
+		U256([12767554894655550452, 16333049135534778834, 140317443000293558, 598963]),
+		U256([2096410819092764509, 8483673822214032535, 36306297304129857, 3453]),
+		U256::one() << 255,
+		U256::MAX - 1,
+		U256::MAX,
+	];
+	for &a in values.iter() {
+		for &b in values.iter() {
+			for &m in values.iter() {
+				let expected = U512(a.full_mul_generic(b)) % widen(m);
+				assert_eq!(widen(a.mul_mod(b, m)), expected, "{:?} * {:?} % {:?}", a, b, m);
+			}
+		}
+	}
+}
+
+#[test]
+fn uint256_wrapping_neg() {
+	assert_eq!(U256::zero().wrapping_neg(), U256::zero());
+	assert_eq!(U256::one().wrapping_neg(), U256::MAX);
+	assert_eq!(U256::MAX.wrapping_neg(), U256::one());
+	let min_signed = U256::one() << 255;
+	assert_eq!(min_signed.wrapping_neg(), min_signed);
+
+	for &x in [U256::zero(), U256::one(), U256::from(12345), U256::MAX >> 1, min_signed, U256::MAX].iter() {
+		assert_eq!(x.wrapping_neg().wrapping_neg(), x);
+		assert_eq!(x.overflowing_add(x.wrapping_neg()).0, U256::zero());
+	}
+
+	assert_eq!(U160::zero().wrapping_neg(), U160::zero());
+	assert_eq!(U160::one().wrapping_neg(), U160::MAX);
+	assert_eq!(U160::from(7).wrapping_neg().wrapping_neg(), U160::from(7));
+}
+
+#[test]
+fn uint256_is_negative_as_signed() {
+	assert!(!U256::zero().is_negative_as_signed());
+	assert!(!(U256::MAX >> 1).is_negative_as_signed());
+	assert!((U256::one() << 255).is_negative_as_signed());
+	assert!(U256::one().wrapping_neg().is_negative_as_signed());
+	assert!(U160::one().wrapping_neg().is_negative_as_signed());
+	assert!(!(U160::MAX >> 1).is_negative_as_signed());
+}
+
+#[test]
+fn uint256_reverse_bits() {
+	assert_eq!(U256::zero().reverse_bits(), U256::zero());
+	assert_eq!(U256::MAX.reverse_bits(), U256::MAX);
+	assert_eq!(U256::one().reverse_bits(), U256::one() << 255);
+	assert_eq!(U256::from(0b110).reverse_bits(), U256::from(0b011) << 253);
+	assert_eq!(U256::from(1u64 << 63).reverse_bits(), U256::one() << 192);
+
+	let x = U256::from_dec_str("123456789012345678901234567890123456789012345678901234567890").unwrap();
+	assert_eq!(x.reverse_bits().reverse_bits(), x);
+
+	assert_eq!(U160::one().reverse_bits(), U160::one() << 159);
+	assert_eq!(U160::MAX.reverse_bits(), U160::MAX);
+	assert_eq!((U160::one() << 159).reverse_bits(), U160::one());
+	let y = U160::from(0xdead_beef_u64) << 100;
+	assert_eq!(y.reverse_bits().reverse_bits(), y);
+}
+
+#[test]
+fn uint256_saturating_assign_ops() {
+	let mut a = U256::MAX - 5;
+	a.saturating_add_assign(U256::from(3));
+	assert_eq!(a, U256::MAX - 2);
+	a.saturating_add_assign(U256::from(3));
+	assert_eq!(a, U256::MAX);
+	a.saturating_add_assign(U256::MAX);
+	assert_eq!(a, U256::MAX);
+
+	let mut b = U256::from(5);
+	b.saturating_sub_assign(U256::from(3));
+	assert_eq!(b, U256::from(2));
+	b.saturating_sub_assign(U256::from(3));
+	assert_eq!(b, U256::zero());
+	b.saturating_sub_assign(U256::MAX);
+	assert_eq!(b, U256::zero());
+}
+
 #[test]
 fn uint256_from() {
 	let e = U256([10, 0, 0, 0]);
@@ -249,6 +424,58 @@ fn uint256_bits_test() {
 	assert_eq!(U256([0u64, 0, 0, (u64::max_value() >> 8) + 1]).byte(31), 0x01);
 }
 
+#[test]
+fn uint256_byte_accessors() {
+	let value = U256::from(0xabu64) << 248 | U256::from(0xcdu64);
+	assert_eq!(value.byte(31), 0xab);
+	assert_eq!(value.byte(0), 0xcd);
+	assert_eq!(value.byte(15), 0);
+
+	let mut value = U256::zero();
+	value.set_byte(0, 0x12);
+	value.set_byte(9, 0x34);
+	value.set_byte(31, 0x56);
+	assert_eq!(value.byte(0), 0x12);
+	assert_eq!(value.byte(9), 0x34);
+	assert_eq!(value.byte(31), 0x56);
+	assert_eq!(value, U256([0x12, 0x3400, 0, 0x56 << 56]));
+
+	value.set_byte(9, 0);
+	assert_eq!(value, U256([0x12, 0, 0, 0x56 << 56]));
+}
+
+#[test]
+fn uint256_big_endian_byte_accessors() {
+	let value = U256::from(0xabu64) << 248 | U256::from(0xcdu64);
+	assert_eq!(value.byte_be(0), 0xab);
+	assert_eq!(value.byte_be(31), 0xcd);
+	assert_eq!(value.byte_be(16), 0);
+
+	let mut value = U256::zero();
+	value.set_byte_be(0, 0x12);
+	value.set_byte_be(22, 0x34);
+	assert_eq!(value.byte_be(0), 0x12);
+	assert_eq!(value.byte_be(22), 0x34);
+	assert_eq!(value.byte(31), 0x12);
+	assert_eq!(value, U256([0, 0x3400, 0, 0x12 << 56]));
+
+	let value = U160::from_str("0102030405060708090a0b0c0d0e0f1011121314").unwrap();
+	assert_eq!(value.byte_be(0), 0x01);
+	assert_eq!(value.byte_be(19), 0x14);
+}
+
+#[test]
+#[should_panic]
+fn uint256_byte_be_out_of_range() {
+	let _ = U256::zero().byte_be(32);
+}
+
+#[test]
+#[should_panic]
+fn uint256_set_byte_out_of_range() {
+	U256::zero().set_byte(32, 1);
+}
+
 #[test]
 fn uint256_comp_test() {
 	let small = U256([10u64, 0, 0, 0]);
@@ -385,6 +612,43 @@ fn should_format_and_debug_correctly() {
 	test(0x1000, "1000", "4096");
 }
 
+#[test]
+fn hex_formatting_honors_width_and_flags() {
+	let x = U256::from(0xbeefu64);
+	assert_eq!(format!("{:#x}", x), "0xbeef");
+	assert_eq!(format!("{:#X}", x), "0xBEEF");
+	assert_eq!(format!("{:X}", x), "BEEF");
+	assert_eq!(format!("{:#066x}", x), format!("0x{:0>64}", "beef"));
+	assert_eq!(format!("{:#066x}", U256::MAX), format!("0x{}", "f".repeat(64)));
+	assert_eq!(format!("{:08x}", x), "0000beef");
+	assert_eq!(format!("{:>8x}", x), "    beef");
+	assert_eq!(format!("{:*<#8x}", x), "0xbeef**");
+	// A width smaller than the natural length does not truncate.
+	assert_eq!(format!("{:#03x}", x), "0xbeef");
+	assert_eq!(format!("{:2x}", U256::MAX), "f".repeat(64));
+	assert_eq!(format!("{:#06x}", U256::zero()), "0x0000");
+}
+
+#[test]
+fn formatting_matches_primitive_integers() {
+	for &value in [0u64, 7, 0xbeef, u64::max_value()].iter() {
+		let x = U256::from(value);
+		assert_eq!(format!("{}", x), format!("{}", value));
+		assert_eq!(format!("{:>24}", x), format!("{:>24}", value));
+		assert_eq!(format!("{:*^25}", x), format!("{:*^25}", value));
+		assert_eq!(format!("{:+}", x), format!("{:+}", value));
+		assert_eq!(format!("{:022}", x), format!("{:022}", value));
+		assert_eq!(format!("{:<22?}", x), format!("{:<22?}", value));
+		assert_eq!(format!("{:x}", x), format!("{:x}", value));
+		assert_eq!(format!("{:X}", x), format!("{:X}", value));
+		assert_eq!(format!("{:#x}", x), format!("{:#x}", value));
+		assert_eq!(format!("{:#X}", x), format!("{:#X}", value));
+		assert_eq!(format!("{:>20x}", x), format!("{:>20x}", value));
+		assert_eq!(format!("{:-<#20X}", x), format!("{:-<#20X}", value));
+		assert_eq!(format!("{:#020x}", x), format!("{:#020x}", value));
+	}
+}
+
 #[test]
 pub fn display_u256() {
 	let expected = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
@@ -433,6 +697,23 @@ fn uint256_overflowing_mul() {
 	);
 }
 
+#[test]
+fn uint256_saturating_mul() {
+	assert_eq!(U256::from(6).saturating_mul(U256::from(7)), U256::from(42));
+	assert_eq!(U256::zero().saturating_mul(U256::MAX), U256::zero());
+	assert_eq!(U256::MAX.saturating_mul(U256::one()), U256::MAX);
+	assert_eq!(U256::MAX.saturating_mul(U256::from(2)), U256::MAX);
+	assert_eq!((U256::MAX / 2).saturating_mul(U256::from(2)), U256::MAX - 1);
+	assert_eq!((U256::MAX / 2 + 1).saturating_mul(U256::from(2)), U256::MAX);
+	let half = U256::one() << 128;
+	assert_eq!((half - 1).saturating_mul(half), U256::MAX - (half - 1));
+	assert_eq!(half.saturating_mul(half), U256::MAX);
+	// Overflow into the bits above the width of a non-word-aligned type saturates as well.
+	assert_eq!(U160::MAX.saturating_mul(U160::from(2)), U160::MAX);
+	assert_eq!(U24::from(0x1000).saturating_mul(U24::from(0x1000)), U24::MAX);
+	assert_eq!(U24::from(0x1000).saturating_mul(U24::from(0xfff)), U24::from(0xfff000));
+}
+
 #[test]
 fn uint512_mul() {
 	assert_eq!(
@@ -443,6 +724,286 @@ fn uint512_mul() {
 	);
 }
 
+impl U256 {
+	fn full_mul_generic(self, other: U256) -> [u64; 8] {
+		uint_full_mul_reg!(U256, 4, self, other)
+	}
+
+	fn full_mul_comba(self, other: U256) -> [u64; 8] {
+		uint_full_mul_comba!(U256, 4, self, other)
+	}
+
+	fn full_square(self) -> [u64; 8] {
+		uint_full_square!(U256, 4, self)
+	}
+}
+
+impl U512 {
+	fn full_mul_generic(self, other: U512) -> [u64; 16] {
+		uint_full_mul_reg!(U512, 8, self, other)
+	}
+
+	fn full_mul_comba(self, other: U512) -> [u64; 16] {
+		uint_full_mul_comba!(U512, 8, self, other)
+	}
+
+	fn full_square(self) -> [u64; 16] {
+		uint_full_square!(U512, 8, self)
+	}
+}
+
+// SplitMix64, to get reproducible pseudo-random inputs without extra dependencies.
+fn next_random(state: &mut u64) -> u64 {
+	*state = state.wrapping_add(0x9e3779b97f4a7c15);
+	let mut z = *state;
+	z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+	z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+	z ^ (z >> 31)
+}
+
+fn fill_random_words(state: &mut u64, words: &mut [u64]) {
+	// Mix full-width words with zero and saturated ones to exercise carries.
+	for word in words.iter_mut() {
+		*word = match next_random(state) % 4 {
+			0 => 0,
+			1 => u64::max_value(),
+			_ => next_random(state),
+		};
+	}
+}
+
+#[test]
+fn uint256_mul_matches_generic() {
+	let mut state = 42;
+	let edge = [U256::zero(), U256::one(), U256::MAX, U256([0, 0, 0, 1]), U256([u64::max_value(), 0, 0, 0])];
+	let random = (0..2000).map(|_| {
+		let mut value = U256::zero();
+		fill_random_words(&mut state, &mut value.0);
+		value
+	});
+	let values: Vec<U256> = edge.iter().cloned().chain(random).collect();
+
+	for pair in values.windows(2) {
+		let (a, b) = (pair[0], pair[1]);
+		assert_eq!(a.full_mul_comba(b), a.full_mul_generic(b), "{:?} * {:?}", a, b);
+		assert_eq!(a.full_square(), a.full_mul_generic(a), "{:?}^2", a);
+		assert_eq!(a.overflowing_squared(), a.overflowing_mul(a));
+	}
+}
+
+#[test]
+fn uint512_mul_matches_generic() {
+	let mut state = 7;
+	let edge = [U512::zero(), U512::one(), U512::MAX, U512([0, 0, 0, 0, 0, 0, 0, 1])];
+	let random = (0..2000).map(|_| {
+		let mut value = U512::zero();
+		fill_random_words(&mut state, &mut value.0);
+		value
+	});
+	let values: Vec<U512> = edge.iter().cloned().chain(random).collect();
+
+	for pair in values.windows(2) {
+		let (a, b) = (pair[0], pair[1]);
+		assert_eq!(a.full_mul_comba(b), a.full_mul_generic(b), "{:?} * {:?}", a, b);
+		assert_eq!(a.full_square(), a.full_mul_generic(a), "{:?}^2", a);
+		assert_eq!(a.overflowing_squared(), a.overflowing_mul(a));
+	}
+}
+
+#[test]
+fn uint256_squared() {
+	assert_eq!(U256::zero().squared(), U256::zero());
+	assert_eq!(U256::from(12345).squared(), U256::from(12345 * 12345));
+	assert_eq!(U256::from(u128::max_value()).squared(), U256::from(u128::max_value()) * U256::from(u128::max_value()));
+	assert_eq!(U256::MAX.overflowing_squared(), (U256::one(), true));
+}
+
+#[test]
+#[should_panic]
+fn uint256_squared_overflow_panic() {
+	let _ = (U256::one() << 128).squared();
+}
+
+fn u160_to_u256(x: U160) -> U256 {
+	let mut bytes = [0u8; 20];
+	x.to_little_endian(&mut bytes);
+	U256::from_little_endian(&bytes)
+}
+
+fn u256_to_u160(x: U256) -> U160 {
+	let mut bytes = [0u8; 32];
+	x.to_little_endian(&mut bytes);
+	U160::from_little_endian(&bytes[..20])
+}
+
+fn random_u160s(seed: u64) -> Vec<U160> {
+	let mut state = seed;
+	let edge =
+		vec![U160::zero(), U160::one(), U160::MAX, U160::MAX - 1, U160::one() << 159, U160::from(u128::max_value())];
+	let random = (0..500).map(|_| {
+		let mut value = U256::zero();
+		fill_random_words(&mut state, &mut value.0);
+		u256_to_u160(value & mask_u256(160))
+	});
+	edge.into_iter().chain(random).collect()
+}
+
+fn mask_u256(bits: usize) -> U256 {
+	(U256::one() << bits) - 1
+}
+
+#[test]
+fn u160_constants() {
+	assert_eq!(U160::BITS, 160);
+	assert_eq!(U160::BYTES, 20);
+	assert_eq!(U160::MAX, U160([u64::max_value(), u64::max_value(), 0xffff_ffff]));
+	assert_eq!(U160::max_value(), U160::MAX);
+	assert_eq!(u160_to_u256(U160::MAX), mask_u256(160));
+	assert_eq!(U160::MAX.bits(), 160);
+	assert_eq!(U160::zero().leading_zeros(), 160);
+	assert_eq!(U160::zero().trailing_zeros(), 160);
+	assert_eq!(U160::one().leading_zeros(), 159);
+	assert_eq!(U160::MAX.leading_zeros(), 0);
+
+	assert_eq!(U96::MAX, U96([u64::max_value(), 0xffff_ffff]));
+	assert_eq!(U96::BYTES, 12);
+	assert_eq!(U96::zero().leading_zeros(), 96);
+	assert_eq!(U24::MAX, U24([0xff_ffff]));
+	assert_eq!(U24::BYTES, 3);
+	assert_eq!(U24::from(1u64).leading_zeros(), 23);
+}
+
+#[test]
+fn u160_arithmetic_matches_masked_u256() {
+	let values = random_u160s(160);
+	let mask = mask_u256(160);
+
+	for pair in values.windows(2) {
+		let (a, b) = (pair[0], pair[1]);
+		let (wa, wb) = (u160_to_u256(a), u160_to_u256(b));
+
+		let sum = wa + wb;
+		assert_eq!(a.overflowing_add(b), (u256_to_u160(sum & mask), sum > mask), "{} + {}", a, b);
+
+		let (diff, borrow) = wa.overflowing_sub(wb);
+		assert_eq!(a.overflowing_sub(b), (u256_to_u160(diff & mask), borrow), "{} - {}", a, b);
+
+		let product = U512(wa.full_mul_generic(wb));
+		let expected = U256([product.0[0], product.0[1], product.0[2], product.0[3]]) & mask;
+		let overflow = product > U512([mask.0[0], mask.0[1], mask.0[2], mask.0[3], 0, 0, 0, 0]);
+		assert_eq!(a.overflowing_mul(b), (u256_to_u160(expected), overflow), "{} * {}", a, b);
+		assert_eq!(a.overflowing_squared(), a.overflowing_mul(a));
+
+		if !b.is_zero() {
+			assert_eq!(u160_to_u256(a / b), wa / wb);
+			assert_eq!(u160_to_u256(a % b), wa % wb);
+		}
+
+		let shift = (b.low_u64() % 200) as usize;
+		assert_eq!(u160_to_u256(a << shift), (wa << shift) & mask, "{} << {}", a, shift);
+		assert_eq!(u160_to_u256(a >> shift), wa >> shift, "{} >> {}", a, shift);
+		assert_eq!(u160_to_u256(!a), !wa & mask);
+		assert_eq!(u160_to_u256(a & b), wa & wb);
+		assert_eq!(u160_to_u256(a | b), wa | wb);
+		assert_eq!(u160_to_u256(a ^ b), wa ^ wb);
+		assert_eq!(a.leading_zeros(), wa.leading_zeros() - 96);
+		assert_eq!(a.bits(), wa.bits());
+		assert_eq!(a.cmp(&b), wa.cmp(&wb));
+		assert_eq!(format!("{}", a), format!("{}", wa));
+		assert_eq!(format!("{:x}", a), format!("{:x}", wa));
+	}
+}
+
+#[test]
+fn u160_overflow_flags() {
+	assert_eq!(U160::MAX.overflowing_add(U160::one()), (U160::zero(), true));
+	assert_eq!(U160::zero().overflowing_sub(U160::one()), (U160::MAX, true));
+	assert_eq!((U160::one() << 159).overflowing_mul(U160::from(2)), (U160::zero(), true));
+	assert_eq!((U160::one() << 80).overflowing_squared(), (U160::zero(), true));
+	assert_eq!((U160::one() << 79).overflowing_squared(), (U160::one() << 158, false));
+	assert_eq!(U160::from(2).overflowing_pow(U160::from(160)), (U160::zero(), true));
+	assert_eq!(U160::from(2).overflowing_pow(U160::from(159)), (U160::one() << 159, false));
+	assert_eq!(U160::MAX.checked_add(U160::one()), None);
+	assert_eq!(U160::MAX.saturating_add(U160::one()), U160::MAX);
+	assert_eq!(U160::MAX.checked_mul(U160::from(2)), None);
+	assert_eq!(U160::one() << 160, U160::zero());
+	assert_eq!(U96::MAX.overflowing_add(U96::one()), (U96::zero(), true));
+	assert_eq!(U24::MAX.overflowing_add(U24::one()), (U24::zero(), true));
+	assert_eq!(U24::from(0x1000).overflowing_mul(U24::from(0x1000)), (U24::zero(), true));
+	assert_eq!(U24::from(0xfff).overflowing_mul(U24::from(0x1000)), (U24::from(0xfff000), false));
+	assert_eq!(U24::MAX.checked_mul(U24::from(2)), None);
+}
+
+#[test]
+#[should_panic]
+fn u160_mul_primitive_overflow_panics() {
+	let _ = U160::MAX * 2u64;
+}
+
+#[test]
+#[should_panic]
+fn u24_from_u64_overflow_panics() {
+	let _ = U24::from(1u64 << 24);
+}
+
+#[test]
+#[should_panic]
+fn u96_from_u128_overflow_panics() {
+	let _ = U96::from(1u128 << 96);
+}
+
+#[test]
+fn u160_from_u128() {
+	assert_eq!(U160::from(u128::max_value()), U160([u64::max_value(), u64::max_value(), 0]));
+	assert_eq!(U160::from(u128::max_value()).as_u128(), u128::max_value());
+	assert_eq!(U96::from((1u128 << 96) - 1), U96::MAX);
+}
+
+#[test]
+fn u160_byte_conversions() {
+	let value = U160::from_str("0102030405060708090a0b0c0d0e0f1011121314").unwrap();
+	let mut be = [0u8; 20];
+	value.to_big_endian(&mut be);
+	assert_eq!(be, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20]);
+	let mut le = [0u8; 20];
+	value.to_little_endian(&mut le);
+	be.reverse();
+	assert_eq!(le, be);
+	assert_eq!(U160::from_little_endian(&le), value);
+	assert_eq!(<[u8; 20]>::from(value)[0], 1);
+	assert_eq!(U160::from([0xffu8; 20]), U160::MAX);
+	assert_eq!(U160::from(&[0u8; 20]), U160::zero());
+	assert_eq!(U24::from([0xab, 0xcd, 0xef]), U24::from(0xabcdef));
+
+	assert!(U160::from_str("ffffffffffffffffffffffffffffffffffffffff").is_ok());
+	assert!(U160::from_str("1ffffffffffffffffffffffffffffffffffffffff").is_err());
+	assert_eq!(U160::from_dec_str("1461501637330902918203684832716283019655932542975"), Ok(U160::MAX));
+	assert_eq!(
+		U160::from_dec_str("1461501637330902918203684832716283019655932542976"),
+		Err(FromDecStrErr::InvalidLength)
+	);
+	assert_eq!(U24::from_dec_str("16777216"), Err(FromDecStrErr::InvalidLength));
+}
+
+#[test]
+#[should_panic]
+fn u160_from_big_endian_too_long() {
+	let _ = U160::from_big_endian(&[0u8; 21]);
+}
+
+#[test]
+#[should_panic]
+fn u160_byte_out_of_range() {
+	let _ = U160::MAX.byte(20);
+}
+
+#[test]
+#[should_panic]
+fn u160_set_byte_out_of_range() {
+	let mut value = U160::zero();
+	value.set_byte(20, 1);
+}
+
 #[test]
 fn uint256_mul_overflow() {
 	assert_eq!(
@@ -518,6 +1079,149 @@ fn uint256_rem() {
 	assert_eq!(U256::from(10u64) % U256::from(3u64), U256::from(1u64));
 }
 
+#[test]
+fn uint256_euclid_matches_u128() {
+	let pairs = [
+		(0u128, 1u128),
+		(10, 3),
+		(u128::max_value(), 7),
+		(u128::max_value(), u128::max_value()),
+		(12345678901234567890, 98765),
+	];
+	for &(a, b) in pairs.iter() {
+		let (x, y) = (U256::from(a), U256::from(b));
+		assert_eq!(x.div_euclid(y), U256::from(a.div_euclid(b)));
+		assert_eq!(x.rem_euclid(y), U256::from(a.rem_euclid(b)));
+		assert_eq!(x.checked_div_euclid(y), a.checked_div_euclid(b).map(U256::from));
+		assert_eq!(x.checked_rem_euclid(y), a.checked_rem_euclid(b).map(U256::from));
+		let (q, overflow) = a.overflowing_div_euclid(b);
+		assert_eq!(x.overflowing_div_euclid(y), (U256::from(q), overflow));
+		assert_eq!(x.wrapping_div_euclid(y), U256::from(a.wrapping_div_euclid(b)));
+	}
+
+	assert_eq!(U256::from(10).checked_div_euclid(U256::zero()), None);
+	assert_eq!(U256::from(10).checked_rem_euclid(U256::zero()), None);
+}
+
+#[test]
+#[should_panic]
+fn uint256_div_euclid_by_zero() {
+	let _ = U256::from(10).div_euclid(U256::zero());
+}
+
+#[test]
+#[should_panic]
+fn uint256_rem_euclid_by_zero() {
+	let _ = U256::from(10).rem_euclid(U256::zero());
+}
+
+#[test]
+#[should_panic]
+fn uint256_overflowing_div_euclid_by_zero() {
+	let _ = U256::from(10).overflowing_div_euclid(U256::zero());
+}
+
+#[test]
+#[should_panic]
+fn uint256_wrapping_div_euclid_by_zero() {
+	let _ = U256::from(10).wrapping_div_euclid(U256::zero());
+}
+
+#[test]
+fn uint256_gcd() {
+	assert_eq!(U256::zero().gcd(U256::zero()), U256::zero());
+	assert_eq!(U256::zero().gcd(U256::from(42)), U256::from(42));
+	assert_eq!(U256::from(42).gcd(U256::zero()), U256::from(42));
+	assert_eq!(U256::from(12).gcd(U256::from(18)), U256::from(6));
+	assert_eq!(U256::from(48).gcd(U256::from(180)), U256::from(12));
+	assert_eq!(U256::from(17).gcd(U256::from(31)), U256::one());
+	assert_eq!(U256::from(1u64 << 40).gcd(U256::from(3u64 << 20)), U256::from(1u64 << 20));
+	// Consecutive Fibonacci numbers are coprime.
+	assert_eq!(U256::from(12200160415121876738u64).gcd(U256::from(19740274219868223167u128)), U256::one());
+	let p = U256::from_dec_str("340282366920938463463374607431768211297").unwrap();
+	assert_eq!((p * U256::from(6)).gcd(p * U256::from(15)), p * U256::from(3));
+	assert_eq!(U256::MAX.gcd(U256::MAX), U256::MAX);
+
+	let pairs =
+		[(0u128, 7u128), (270, 192), (u128::max_value(), 1 << 100), (1 << 127, 3 << 64), (987654321, 123456789)];
+	for &(a, b) in pairs.iter() {
+		let (mut x, mut y) = (a, b);
+		while y != 0 {
+			let t = x % y;
+			x = y;
+			y = t;
+		}
+		assert_eq!(U256::from(a).gcd(U256::from(b)), U256::from(x));
+	}
+}
+
+#[test]
+fn uint256_checked_lcm() {
+	assert_eq!(U256::zero().checked_lcm(U256::from(5)), Some(U256::zero()));
+	assert_eq!(U256::from(5).checked_lcm(U256::zero()), Some(U256::zero()));
+	assert_eq!(U256::from(4).checked_lcm(U256::from(6)), Some(U256::from(12)));
+	assert_eq!(U256::from(21).checked_lcm(U256::from(6)), Some(U256::from(42)));
+	assert_eq!(U256::from(17).checked_lcm(U256::from(31)), Some(U256::from(527)));
+	assert_eq!(U256::MAX.checked_lcm(U256::one()), Some(U256::MAX));
+	assert_eq!(U256::MAX.checked_lcm(U256::MAX), Some(U256::MAX));
+
+	// Coprime 129-bit numbers, their product needs 257 bits.
+	let a = (U256::one() << 128) + 1;
+	let b = (U256::one() << 128) + 3;
+	assert_eq!(a.gcd(b), U256::one());
+	assert_eq!(a.checked_lcm(b), None);
+	assert_eq!((U256::one() << 255).checked_lcm(U256::from(3)), None);
+}
+
+#[test]
+fn uint256_native_endian_bytes() {
+	let value = U256::from_str("0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20").unwrap();
+	let be = value.to_be_bytes();
+	let le = value.to_le_bytes();
+	assert_eq!(be[0], 1);
+	assert_eq!(le[0], 0x20);
+	assert_eq!(U256::from_be_bytes(be), value);
+	assert_eq!(U256::from_le_bytes(le), value);
+
+	if cfg!(target_endian = "big") {
+		assert_eq!(value.to_ne_bytes(), be);
+	} else {
+		assert_eq!(value.to_ne_bytes(), le);
+	}
+	assert_eq!(U256::from_ne_bytes(value.to_ne_bytes()), value);
+
+	let value = U160::MAX - 1;
+	assert_eq!(U160::from_ne_bytes(value.to_ne_bytes()), value);
+	assert_eq!(value.to_be_bytes().len(), 20);
+}
+
+#[test]
+fn uint256_limbs() {
+	let mut value = U256::from_limbs([1, 2, 3, 4]);
+	assert_eq!(value.as_limbs(), &[1, 2, 3, 4]);
+	assert_eq!(value, (U256::from(4) << 192) + (U256::from(3) << 128) + (U256::from(2) << 64) + 1);
+
+	value.as_limbs_mut()[3] = 0;
+	assert_eq!(value, U256([1, 2, 3, 0]));
+
+	assert_eq!(U160::from_limbs([u64::max_value(), u64::max_value(), 0xffff_ffff]), U160::MAX);
+}
+
+#[test]
+fn limbs_round_trip() {
+	let value = U256::from_dec_str("98765432109876543210987654321098765432109876543210").unwrap();
+	assert_eq!(U256::from_limbs(*value.as_limbs()), value);
+	assert_eq!(value.as_limbs()[0], value.low_u64());
+	assert_eq!(value.as_limbs()[1], (value >> 64).low_u64());
+	assert_eq!(U256::from(0xabcdu64).as_limbs(), &[0xabcd, 0, 0, 0]);
+}
+
+#[test]
+#[should_panic]
+fn u160_from_limbs_out_of_range() {
+	let _ = U160::from_limbs([0, 0, 1 << 32]);
+}
+
 #[test]
 fn uint256_from_dec_str() {
 	assert_eq!(U256::from_dec_str("10").unwrap(), U256::from(10u64));
@@ -527,6 +1231,31 @@ fn uint256_from_dec_str() {
 		Err(FromDecStrErr::InvalidLength)
 	);
 	assert_eq!(U256::from_dec_str("0x11"), Err(FromDecStrErr::InvalidCharacter));
+	assert_eq!(U256::from_dec_str("-1"), Err(FromDecStrErr::InvalidCharacter));
+
+	assert_eq!(U256::from_dec_str("0"), Ok(U256::zero()));
+	assert_eq!(U256::from_dec_str(""), Err(FromDecStrErr::Empty));
+	assert_eq!(U256::from_dec_str("  42  "), Err(FromDecStrErr::Whitespace));
+	assert_eq!(U256::from_dec_str("42\n"), Err(FromDecStrErr::Whitespace));
+	assert_eq!(U256::from_dec_str("4 2"), Err(FromDecStrErr::InvalidCharacter));
+	assert_eq!(U256::from_dec_str("007"), Err(FromDecStrErr::LeadingZero));
+	assert_eq!(U256::from_dec_str("00"), Err(FromDecStrErr::LeadingZero));
+	assert_eq!(U256::from_str_radix("007", 10).unwrap_err().kind(), FromStrRadixErrKind::InvalidCharacter);
+}
+
+#[test]
+fn uint256_from_dec_str_lenient() {
+	assert_eq!(U256::from_dec_str_lenient("0"), Ok(U256::zero()));
+	assert_eq!(U256::from_dec_str_lenient(""), Err(FromDecStrErr::Empty));
+	assert_eq!(U256::from_dec_str_lenient(" \t "), Err(FromDecStrErr::Empty));
+	assert_eq!(U256::from_dec_str_lenient("  42  "), Ok(U256::from(42)));
+	assert_eq!(U256::from_dec_str_lenient("007"), Ok(U256::from(7)));
+	assert_eq!(U256::from_dec_str_lenient("000"), Ok(U256::zero()));
+	assert_eq!(U256::from_dec_str_lenient("4 2"), Err(FromDecStrErr::InvalidCharacter));
+	assert_eq!(
+		U256::from_dec_str_lenient(" 0115792089237316195423570985008687907853269984665640564039457584007913129639936"),
+		Err(FromDecStrErr::InvalidLength)
+	);
 }
 
 #[test]
@@ -1235,9 +1964,131 @@ pub mod laws {
 	construct_uint! {
 		pub struct U1024(16);
 	}
+	construct_uint! {
+		pub struct U160(3, 160);
+	}
 
+	uint_laws!(u160, U160);
 	uint_laws!(u64, U64);
 	uint_laws!(u256, U256);
 	uint_laws!(u512, U512);
 	uint_laws!(u1024, U1024);
 }
+
+#[cfg(feature = "bytemuck")]
+mod bytemuck {
+	use super::{U160, U256};
+	use uint::bytemuck::{cast_slice, cast_slice_mut, Zeroable};
+
+	#[test]
+	fn cast_slice_roundtrip() {
+		let values = [U256::from(1), U256::MAX >> 1, U256([1, 2, 3, 4])];
+		let bytes: &[u8] = cast_slice(&values);
+		assert_eq!(bytes.len(), 96);
+
+		for (value, chunk) in values.iter().zip(bytes.chunks(32)) {
+			let mut expected = [0u8; 32];
+			for (limb, out) in value.as_limbs().iter().zip(expected.chunks_mut(8)) {
+				out.copy_from_slice(&limb.to_ne_bytes());
+			}
+			assert_eq!(chunk, &expected[..]);
+		}
+
+		let back: &[U256] = cast_slice(bytes);
+		assert_eq!(back, &values[..]);
+	}
+
+	#[test]
+	fn zeroed_and_mutable_cast() {
+		assert_eq!(U256::zeroed(), U256::zero());
+		// Types of an explicit width are only `Zeroable`.
+		assert_eq!(U160::zeroed(), U160::zero());
+
+		let mut values = [U256::zero(); 2];
+		cast_slice_mut::<U256, u64>(&mut values)[4] = 7;
+		assert_eq!(values[1], U256::from(7));
+	}
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary {
+	use super::{U160, U256, U512};
+	use uint::arbitrary::{Arbitrary, Unstructured};
+
+	#[test]
+	fn size_hint() {
+		assert_eq!(U256::size_hint(0), (32, Some(32)));
+		assert_eq!(U512::size_hint(0), (64, Some(64)));
+		assert_eq!(U160::size_hint(0), (20, Some(20)));
+	}
+
+	#[test]
+	fn from_corpus() {
+		let corpus = [0xffu8; 32];
+		let mut u = Unstructured::new(&corpus);
+		assert_eq!(U256::arbitrary(&mut u).unwrap(), U256::MAX);
+
+		let corpus: Vec<u8> = (1..=64).collect();
+		let mut u = Unstructured::new(&corpus);
+		let value = U512::arbitrary(&mut u).unwrap();
+		assert_eq!(value, U512::from_big_endian(&corpus));
+		assert!(value.bits() > 128);
+	}
+}
+
+#[cfg(feature = "proptest")]
+mod proptests {
+	use super::{U160, U256, U512};
+	use uint::proptest::prelude::*;
+	use uint::proptest::test_runner::{Config, TestError, TestRunner};
+	use uint::strategy::uniform_range;
+
+	proptest! {
+		#[test]
+		fn add_sub_roundtrip(a: U256, b: U256) {
+			if let Some(sum) = a.checked_add(b) {
+				prop_assert_eq!(sum - b, a);
+			}
+		}
+
+		#[test]
+		fn uniform_range_in_bounds(x in uniform_range(U512::from(1000)..U512::MAX >> 1)) {
+			prop_assert!(x >= U512::from(1000));
+			prop_assert!(x < U512::MAX >> 1);
+		}
+
+		#[test]
+		fn u160_values_are_canonical(x: U160) {
+			prop_assert!(x.bits() <= 160);
+			prop_assert_eq!(x.0[2] >> 32, 0);
+		}
+	}
+
+	#[test]
+	fn shrinks_to_minimal_failing_value() {
+		let mut runner = TestRunner::new(Config { failure_persistence: None, ..Config::default() });
+		let result = runner.run(&any::<U256>(), |x| {
+			prop_assert!(x < U256::from(1_000_000));
+			Ok(())
+		});
+		match result {
+			Err(TestError::Fail(_, value)) => assert_eq!(value, U256::from(1_000_000)),
+			other => panic!("unexpected result: {:?}", other),
+		}
+	}
+
+	#[test]
+	fn uniform_range_shrinks_to_start() {
+		let start = U256::from(u64::max_value());
+		let end = U256::MAX;
+		let mut runner = TestRunner::new(Config { failure_persistence: None, ..Config::default() });
+		let result = runner.run(&uniform_range(start..end), |_| {
+			prop_assert!(false);
+			Ok(())
+		});
+		match result {
+			Err(TestError::Fail(_, value)) => assert_eq!(value, start),
+			other => panic!("unexpected result: {:?}", other),
+		}
+	}
+}